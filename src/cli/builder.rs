@@ -71,6 +71,7 @@ pub enum BuilderSubcommand {
 
 impl BuilderCommand {
     pub async fn execute(self) -> Result<()> {
+        ConfKitEngine::require_engine("builder").await?;
         ConfKitEngine::ensure_running().await?;
 
         match self.command {
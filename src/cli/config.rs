@@ -5,7 +5,10 @@
 use anyhow::Result;
 use clap::{Args, Subcommand};
 
+use crate::core::validator::{ConfigValidator, Severity};
 use crate::infra::config::ConfKitConfigLoader;
+use crate::infra::schema::{ConfigSchema, ConfigSchemaKind};
+use crate::shared::constants::CONFKIT_CONFIG_FILE;
 
 #[derive(Args)]
 pub struct ConfigCommand {
@@ -19,6 +22,15 @@ pub enum ConfigSubcommand {
     Show,
     /// Validate configuration file.
     Validate,
+    /// Print the JSON Schema of config files (default: .confkit.yml).
+    Schema {
+        /// Schema of space project files.
+        #[arg(long, conflicts_with = "root")]
+        project: bool,
+        /// Schema of .confkit.yml.
+        #[arg(long)]
+        root: bool,
+    },
 }
 
 impl ConfigCommand {
@@ -26,8 +38,22 @@ impl ConfigCommand {
         match self.command {
             ConfigSubcommand::Show => handle_show().await,
             ConfigSubcommand::Validate => handle_validate().await,
+            ConfigSubcommand::Schema { project, .. } => handle_schema(project),
         }
     }
+
+    /// 是否依赖项目配置与容器引擎
+    ///
+    /// validate 自行读取并解析配置文件, 不依赖已加载的配置与引擎,
+    /// 否则配置错误或引擎不可用时无法输出校验报告
+    pub fn requires_project(&self) -> bool {
+        !matches!(self.command, ConfigSubcommand::Validate | ConfigSubcommand::Schema { .. })
+    }
+
+    /// 是否向标准输出写入结构化数据
+    pub fn writes_data_to_stdout(&self) -> bool {
+        matches!(self.command, ConfigSubcommand::Schema { .. })
+    }
 }
 
 async fn handle_show() -> Result<()> {
@@ -76,77 +102,37 @@ async fn handle_show() -> Result<()> {
 }
 
 async fn handle_validate() -> Result<()> {
-    tracing::info!("Validating .confkit.yml...");
-
-    let config = ConfKitConfigLoader::get_config();
-    let mut errors: Vec<String> = vec![];
+    let path = ConfKitConfigLoader::config_file_path().unwrap_or(CONFKIT_CONFIG_FILE);
+    tracing::info!("Validating {}...", path);
 
-    // 校验 spaces
-    if config.spaces.is_empty() {
-        errors.push("No spaces defined".to_string());
-    }
-
-    for space in &config.spaces {
-        if space.name.is_empty() {
-            errors.push("Space name is empty".to_string());
-        }
-        if space.path.is_empty() {
-            errors.push(format!("Space '{}' has empty path", space.name));
-        }
-        if !std::path::Path::new(&space.path).exists() {
-            errors.push(format!("Space '{}' path does not exist: {}", space.name, space.path));
-        }
+    let report = ConfigValidator::validate_file(path);
 
-        // 校验 space 下的项目配置
-        match ConfKitConfigLoader::get_project_config_list(&space.name).await {
-            Ok(projects) => {
-                if projects.is_empty() {
-                    errors.push(format!(
-                        "Space '{}' has no project configs in: {}",
-                        space.name, space.path
-                    ));
-                }
-                for project in &projects {
-                    if project.steps.is_empty() {
-                        errors.push(format!(
-                            "Project '{}/{}' has no steps",
-                            space.name, project.name
-                        ));
-                    }
-                }
-            }
-            Err(e) => {
-                errors.push(format!("Failed to load projects for space '{}': {}", space.name, e));
-            }
+    for diagnostic in &report.diagnostics {
+        match diagnostic.severity {
+            Severity::Error => tracing::error!("{}", diagnostic),
+            Severity::Warning => tracing::warn!("{}", diagnostic),
         }
     }
 
-    // 校验 images
-    for image in &config.images {
-        if image.name.is_empty() {
-            errors.push("Image name is empty".to_string());
-        }
-        if !std::path::Path::new(&image.context).exists() {
-            errors.push(format!(
-                "Image '{}:{}' context does not exist: {}",
-                image.name, image.tag, image.context
-            ));
-        }
+    if report.has_errors() {
+        return Err(anyhow::anyhow!(
+            "Configuration is invalid: {} error(s), {} warning(s)",
+            report.error_count(),
+            report.warning_count()
+        ));
     }
 
-    // 校验 compose file
-    if !std::path::Path::new(&config.engine_compose.file).exists() {
-        errors.push(format!("Compose file does not exist: {}", config.engine_compose.file));
-    }
-
-    if errors.is_empty() {
-        tracing::info!("Configuration is valid");
+    if report.warning_count() > 0 {
+        tracing::info!("Configuration is valid with {} warning(s)", report.warning_count());
     } else {
-        tracing::warn!("Found {} issue(s):", errors.len());
-        for (i, err) in errors.iter().enumerate() {
-            tracing::warn!("  {}. {}", i + 1, err);
-        }
+        tracing::info!("Configuration is valid");
     }
 
     Ok(())
 }
+
+fn handle_schema(project: bool) -> Result<()> {
+    let kind = if project { ConfigSchemaKind::Project } else { ConfigSchemaKind::Root };
+    println!("{}", ConfigSchema::to_json(kind)?);
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Doctor subcommand implementation
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::core::doctor::{CheckStatus, Doctor};
+use crate::formatter::doctor::DoctorFormatter;
+
+#[derive(Debug, Args)]
+pub struct DoctorArgs {
+    /// Print as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// 处理 doctor 命令
+pub async fn handle_doctor(args: &DoctorArgs) -> Result<()> {
+    let report = Doctor::run().await;
+
+    if args.json {
+        DoctorFormatter::print_json(&report)?;
+    } else {
+        DoctorFormatter::print_table(&report);
+        tracing::info!(
+            "{} passed, {} warning(s), {} failed",
+            report.count(CheckStatus::Pass),
+            report.count(CheckStatus::Warn),
+            report.count(CheckStatus::Fail)
+        );
+    }
+
+    if report.has_failures() {
+        return Err(anyhow::anyhow!("{} check(s) failed", report.count(CheckStatus::Fail)));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Env subcommand implementation
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::core::executor::context::ExecutionContext;
+use crate::formatter::environment::EnvironmentFormatter;
+use crate::infra::config::ConfKitConfigLoader;
+
+use super::run::parse_environments;
+
+#[derive(Args)]
+pub struct EnvCommand {
+    #[command(subcommand)]
+    command: EnvSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum EnvSubcommand {
+    /// Show the final environment of a project with the source of each variable.
+    Show {
+        /// Space name.
+        #[arg(short, long)]
+        space: String,
+        /// Project name.
+        #[arg(short, long)]
+        project: String,
+        /// Environments passed as if from command line (KEY=VALUE).
+        #[arg(short, long)]
+        environments: Option<Vec<String>>,
+        /// Print as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+impl EnvCommand {
+    pub async fn execute(self) -> Result<()> {
+        match self.command {
+            EnvSubcommand::Show { space, project, environments, json } => {
+                handle_show(&space, &project, environments, json).await
+            }
+        }
+    }
+
+    /// 是否向标准输出写入结构化数据
+    pub fn writes_data_to_stdout(&self) -> bool {
+        matches!(self.command, EnvSubcommand::Show { json: true, .. })
+    }
+}
+
+async fn handle_show(
+    space_name: &str,
+    project_name: &str,
+    environments: Option<Vec<String>>,
+    json: bool,
+) -> Result<()> {
+    let project_config = ConfKitConfigLoader::get_project_config(space_name, project_name).await?;
+
+    let project_config = match project_config {
+        Some(config) => config,
+        None => {
+            return Err(anyhow::anyhow!(
+                "Project '{}' not found in space '{}'",
+                project_name,
+                space_name
+            ));
+        }
+    };
+
+    let environment_from_args = parse_environments(environments).await?;
+
+    let layers = ExecutionContext::preview_environment(
+        space_name,
+        project_name,
+        &project_config,
+        environment_from_args,
+    )
+    .await?
+    .masked();
+
+    if json {
+        EnvironmentFormatter::print_json(space_name, project_name, &layers)?;
+    } else {
+        EnvironmentFormatter::print_table(&layers);
+    }
+
+    Ok(())
+}
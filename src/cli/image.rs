@@ -5,7 +5,8 @@
 use anyhow::Result;
 use clap::{Args, Subcommand};
 
-use crate::core::builder::image::ImageBuilder;
+use super::run::parse_environments;
+use crate::core::builder::{image::ImageBuilder, prune::PrunePolicy};
 use crate::engine::ConfKitEngine;
 
 #[derive(Args)]
@@ -18,7 +19,11 @@ pub struct ImageCommand {
 pub enum ImageSubcommand {
     /// List all builder images.
     /// (eg: confkit builder image list)
-    List {},
+    List {
+        /// Show images as a tree of local base images.
+        #[arg(long)]
+        tree: bool,
+    },
     /// Create/pull image.
     Create {
         /// Create all images.
@@ -31,6 +36,38 @@ pub enum ImageSubcommand {
         #[arg(short, long)]
         tag: String,
     },
+    /// Push image to the registry configured under `push`.
+    /// (eg: confkit image push -n node-builder -t 20 -e PROJECT_VERSION=1.2.0)
+    Push {
+        /// Push all images with a push configuration.
+        #[arg(long)]
+        all: bool,
+        /// Image name.
+        #[arg(short, long, required_unless_present = "all")]
+        name: Option<String>,
+        /// Image tag.
+        #[arg(short, long, required_unless_present = "all")]
+        tag: Option<String>,
+        /// Variables for push tag templates (KEY=VALUE).
+        #[arg(short, long)]
+        environments: Option<Vec<String>>,
+    },
+    /// Remove old local tags of configured images.
+    /// (eg: confkit image prune --older-than 30d --keep 3 --dry-run)
+    Prune {
+        /// Only prune this image.
+        #[arg(short, long)]
+        name: Option<String>,
+        /// Remove tags created longer ago than this (eg: 12h, 30d, 2w).
+        #[arg(long)]
+        older_than: Option<String>,
+        /// Always keep the newest N tags of each image.
+        #[arg(long)]
+        keep: Option<usize>,
+        /// Show the tags that would be removed without removing them.
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Remove image.
     Remove {
         /// Image name.
@@ -44,15 +81,33 @@ pub enum ImageSubcommand {
 
 impl ImageCommand {
     pub async fn execute(self) -> Result<()> {
+        ConfKitEngine::require_engine("image").await?;
         ConfKitEngine::ensure_running().await?;
 
         match self.command {
-            ImageSubcommand::List {} => {
-                ImageBuilder::print_list().await?;
+            ImageSubcommand::List { tree } => {
+                if tree {
+                    ImageBuilder::print_tree().await?;
+                } else {
+                    ImageBuilder::print_list().await?;
+                }
             }
             ImageSubcommand::Create { all, name, tag } => {
                 create_image(all, name, tag).await?;
             }
+            ImageSubcommand::Push { all, name, tag, environments } => {
+                let overrides = parse_environments(environments).await?;
+                match (all, name, tag) {
+                    (false, Some(name), Some(tag)) => {
+                        ImageBuilder::push(&name, &tag, &overrides).await?
+                    }
+                    _ => ImageBuilder::push_all(&overrides).await?,
+                }
+            }
+            ImageSubcommand::Prune { name, older_than, keep, dry_run } => {
+                let policy = PrunePolicy::new(older_than.as_deref(), keep)?;
+                ImageBuilder::prune(name.as_deref(), &policy, dry_run).await?;
+            }
             ImageSubcommand::Remove { name, tag } => {
                 ImageBuilder::remove(&name, &tag).await?;
             }
@@ -0,0 +1,85 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Init subcommand implementation
+
+use anyhow::Result;
+use clap::Args;
+use inquire::{Select, Text};
+
+use crate::core::init::{InitOptions, ProjectScaffold};
+use crate::types::config::Engine;
+
+#[derive(Debug, Args)]
+pub struct InitArgs {
+    /// Accept defaults without prompting
+    #[arg(short, long)]
+    pub yes: bool,
+
+    /// Overwrite existing files
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// 处理 init 命令
+pub async fn handle_init(args: &InitArgs) -> Result<()> {
+    let detected = ProjectScaffold::detect_engines();
+    if detected.is_empty() {
+        tracing::warn!(
+            "No container engine detected (docker/podman/nerdctl), defaulting to docker"
+        );
+    }
+
+    let mut options = InitOptions {
+        engine: detected.first().cloned().unwrap_or(Engine::Docker),
+        ..InitOptions::default()
+    };
+
+    if !args.yes {
+        prompt_options(&mut options, &detected)?;
+    }
+
+    let root = std::env::current_dir()?;
+    let written = ProjectScaffold::generate(&root, &options, args.force)?;
+
+    for path in &written {
+        let path = path.strip_prefix(&root).unwrap_or(path);
+        tracing::info!("Created {}", path.display());
+    }
+    tracing::info!("Project initialized, next steps:");
+    tracing::info!("  confkit config validate");
+    tracing::info!(
+        "  confkit image create -n {} -t {}",
+        options.builder_name,
+        options.base_image_tag
+    );
+    tracing::info!("  confkit builder create -n {}", options.builder_name);
+    tracing::info!("  confkit run -s {} -p {}", options.space_name, options.project_name);
+
+    Ok(())
+}
+
+/// 交互式填写初始化选项
+fn prompt_options(options: &mut InitOptions, detected: &[Engine]) -> Result<()> {
+    let mut engines = if detected.is_empty() {
+        vec![Engine::Docker, Engine::Podman, Engine::Nerdctl]
+    } else {
+        detected.to_vec()
+    };
+    // 仅在宿主机执行步骤
+    engines.push(Engine::None);
+    options.engine = Select::new("Container engine:", engines).prompt()?;
+
+    options.space_name = prompt_text("Space name:", &options.space_name)?;
+    options.project_name = prompt_text("Project name:", &options.project_name)?;
+    options.git_repo = prompt_text("Git repository:", &options.git_repo)?;
+    options.git_branch = prompt_text("Git branch:", &options.git_branch)?;
+    options.builder_name = prompt_text("Builder name:", &options.builder_name)?;
+
+    Ok(())
+}
+
+fn prompt_text(message: &str, default: &str) -> Result<String> {
+    let value = Text::new(message).with_default(default).prompt()?;
+    let value = value.trim();
+    Ok(if value.is_empty() { default.to_string() } else { value.to_string() })
+}
@@ -8,18 +8,28 @@ use clap::{Parser, Subcommand};
 mod builder;
 mod clean;
 mod config;
+mod doctor;
+mod env;
 mod image;
+mod init;
 mod interactive;
 mod log;
+mod registry;
 mod run;
+mod secret;
 
 use builder::BuilderCommand;
 use clean::CleanCommand;
 use config::ConfigCommand;
+use doctor::DoctorArgs;
+use env::EnvCommand;
 use image::ImageCommand;
+use init::InitArgs;
 use interactive::InteractiveCommand;
 use log::LogCommand;
+use registry::RegistryCommand;
 use run::RunArgs;
+use secret::SecretCommand;
 
 #[derive(Parser)]
 #[command(name = "confkit")]
@@ -44,6 +54,8 @@ pub enum Commands {
     Builder(BuilderCommand),
     /// Image management.
     Image(ImageCommand),
+    /// Scaffold a new confkit project in the current directory.
+    Init(InitArgs),
     /// Run build task
     Run(RunArgs),
     /// Clean resources.
@@ -52,6 +64,14 @@ pub enum Commands {
     Log(LogCommand),
     /// Configuration management.
     Config(ConfigCommand),
+    /// Environment inspection.
+    Env(EnvCommand),
+    /// Encrypted secret management.
+    Secret(SecretCommand),
+    /// Container registry credentials.
+    Registry(RegistryCommand),
+    /// Diagnose the host environment.
+    Doctor(DoctorArgs),
 }
 
 impl Cli {
@@ -59,10 +79,15 @@ impl Cli {
         let result = match self.command {
             Some(Commands::Builder(cmd)) => cmd.execute().await,
             Some(Commands::Image(cmd)) => cmd.execute().await,
+            Some(Commands::Init(args)) => init::handle_init(&args).await,
             Some(Commands::Run(args)) => run::handle_run(&args).await,
             Some(Commands::Clean(cmd)) => cmd.execute().await,
             Some(Commands::Log(cmd)) => cmd.execute().await,
             Some(Commands::Config(cmd)) => cmd.execute().await,
+            Some(Commands::Env(cmd)) => cmd.execute().await,
+            Some(Commands::Secret(cmd)) => cmd.execute().await,
+            Some(Commands::Registry(cmd)) => cmd.execute().await,
+            Some(Commands::Doctor(args)) => doctor::handle_doctor(&args).await,
             None => InteractiveCommand::execute().await,
         };
 
@@ -83,4 +108,26 @@ impl Cli {
     pub fn parse_args() -> Self {
         Self::parse()
     }
+
+    /// 命令是否依赖项目配置 (.confkit.yml) 与容器引擎
+    pub fn requires_project(&self) -> bool {
+        match &self.command {
+            Some(Commands::Init(_)) => false,
+            Some(Commands::Doctor(_)) => false,
+            Some(Commands::Registry(_)) => false,
+            Some(Commands::Config(cmd)) => cmd.requires_project(),
+            _ => true,
+        }
+    }
+
+    /// 命令是否向标准输出写入结构化数据, 此时日志全部输出到标准错误
+    pub fn writes_data_to_stdout(&self) -> bool {
+        match &self.command {
+            Some(Commands::Config(cmd)) => cmd.writes_data_to_stdout(),
+            Some(Commands::Env(cmd)) => cmd.writes_data_to_stdout(),
+            Some(Commands::Secret(cmd)) => cmd.writes_data_to_stdout(),
+            Some(Commands::Doctor(args)) => args.json,
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,116 @@
+//! Author: xiaoYown
+//! Created: 2026-10-19
+//! Description: Registry login subcommand implementation
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::io::{IsTerminal, Read};
+
+use crate::infra::registry::{DockerConfig, RegistryClient};
+use crate::types::config::RegistryAuth;
+
+#[derive(Args)]
+pub struct RegistryCommand {
+    #[command(subcommand)]
+    command: RegistrySubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum RegistrySubcommand {
+    /// Verify credentials and save them to the docker config for image push.
+    /// (eg: echo $TOKEN | confkit registry login ghcr.io -u ci-bot --password-stdin)
+    Login {
+        /// Registry address (eg: ghcr.io, localhost:5000).
+        registry: String,
+        /// Username.
+        #[arg(short, long)]
+        username: String,
+        /// Read the password from stdin.
+        #[arg(long)]
+        password_stdin: bool,
+        /// Use plain http (localhost registries always use http).
+        #[arg(long)]
+        insecure: bool,
+    },
+    /// Remove saved credentials of a registry.
+    Logout {
+        /// Registry address.
+        registry: String,
+    },
+}
+
+impl RegistryCommand {
+    pub async fn execute(self) -> Result<()> {
+        match self.command {
+            RegistrySubcommand::Login { registry, username, password_stdin, insecure } => {
+                handle_login(&registry, &username, password_stdin, insecure).await
+            }
+            RegistrySubcommand::Logout { registry } => handle_logout(&registry),
+        }
+    }
+}
+
+/// 读取密码: 标准输入 (--password-stdin) > 隐藏输入提示
+fn read_password(registry: &str, password_stdin: bool) -> Result<String> {
+    if password_stdin {
+        let mut password = String::new();
+        std::io::stdin().read_to_string(&mut password)?;
+        return Ok(password.trim_end_matches(['\r', '\n']).to_string());
+    }
+
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow::anyhow!("No terminal for the password prompt, use --password-stdin"));
+    }
+    let password = inquire::Password::new(&format!("Password for {registry}:"))
+        .without_confirmation()
+        .prompt()?;
+    Ok(password)
+}
+
+async fn handle_login(
+    registry: &str,
+    username: &str,
+    password_stdin: bool,
+    insecure: bool,
+) -> Result<()> {
+    let password = read_password(registry, password_stdin)?;
+    if password.is_empty() {
+        return Err(anyhow::anyhow!("Password must not be empty"));
+    }
+
+    let auth = RegistryAuth {
+        username: username.to_string(),
+        password,
+        server_address: registry.to_string(),
+    };
+    RegistryClient::new(registry, insecure).check_login(&auth).await?;
+
+    let mut config = DockerConfig::load(&DockerConfig::default_path())?;
+    if let Some(store) = config.credentials_store() {
+        tracing::warn!(
+            "{} uses credsStore '{}', engine CLIs may ignore credentials saved by confkit",
+            config.path().display(),
+            store
+        );
+    }
+    config.set_auth(&auth);
+    config.save()?;
+
+    tracing::info!("Login succeeded, credentials saved to {}", config.path().display());
+    Ok(())
+}
+
+fn handle_logout(registry: &str) -> Result<()> {
+    let mut config = DockerConfig::load(&DockerConfig::default_path())?;
+    if !config.remove_auth(registry) {
+        return Err(anyhow::anyhow!(
+            "No credentials for {} in {}",
+            registry,
+            config.path().display()
+        ));
+    }
+
+    config.save()?;
+    tracing::info!("Removed credentials for {}", registry);
+    Ok(())
+}
@@ -7,6 +7,7 @@ use std::collections::HashMap;
 use crate::core::condition::evaluator::ConditionEvaluator;
 use crate::core::executor::runner::Runner;
 use crate::infra::config::ConfKitConfigLoader;
+use crate::types::config::ConditionErrorPolicy;
 use anyhow::Result;
 use clap::Args;
 
@@ -47,11 +48,10 @@ pub async fn handle_run(args: &RunArgs) -> Result<()> {
 
 /// dry-run: 预览步骤，不实际执行
 async fn handle_dry_run(args: &RunArgs) -> Result<()> {
-    let project_config =
-        ConfKitConfigLoader::get_project_config(&args.space, &args.project).await?;
+    let project = ConfKitConfigLoader::get_indexed_project(&args.space, &args.project).await?;
 
-    let project_config = match project_config {
-        Some(config) => config,
+    let (project_path, modified, project_config) = match project {
+        Some(project) => (project.path, project.modified, project.config),
         None => {
             tracing::error!("Project '{}' not found in space '{}'", args.project, args.space);
             return Ok(());
@@ -72,6 +72,14 @@ async fn handle_dry_run(args: &RunArgs) -> Result<()> {
     let total = project_config.steps.len();
 
     tracing::info!("Dry run: {}/{}", args.space, args.project);
+    match modified {
+        Some(modified) => tracing::info!(
+            "Config: {} (modified {})",
+            project_path.display(),
+            chrono::DateTime::<chrono::Local>::from(modified).format("%Y-%m-%d %H:%M:%S")
+        ),
+        None => tracing::info!("Config: {}", project_path.display()),
+    }
     tracing::info!("{}", "=".repeat(50));
     tracing::info!("Steps ({}):", total);
 
@@ -79,25 +87,31 @@ async fn handle_dry_run(args: &RunArgs) -> Result<()> {
         let step_num = i + 1;
         let target = step.container.as_deref().unwrap_or("host");
 
-        // 求值条件
-        let condition_result = if let Some(condition) = &step.condition {
-            match evaluator.evaluate_string(condition) {
-                Ok(true) => format!("condition: {} -> PASS", condition),
-                Ok(false) => format!("condition: {} -> SKIP", condition),
-                Err(e) => format!("condition: {} -> ERROR({})", condition, e),
-            }
-        } else {
-            "no condition".to_string()
+        // 求值条件, 出错时按 on_condition_error 处理
+        let policy =
+            step.on_condition_error.or(project_config.on_condition_error).unwrap_or_default();
+        let (condition_result, status) = match &step.condition {
+            Some(condition) => match evaluator.try_evaluate_string(condition) {
+                Ok(true) => (format!("condition: {} -> PASS", condition), "RUN"),
+                Ok(false) => (format!("condition: {} -> SKIP", condition), "SKIP"),
+                Err(e) => {
+                    let status = match policy {
+                        ConditionErrorPolicy::Run => "RUN",
+                        ConditionErrorPolicy::Skip => "SKIP",
+                        ConditionErrorPolicy::Fail => "FAIL",
+                    };
+                    (
+                        format!(
+                            "condition: {} -> ERROR({}), on_condition_error: {}",
+                            condition, e, policy
+                        ),
+                        status,
+                    )
+                }
+            },
+            None => ("no condition".to_string(), "RUN"),
         };
 
-        let will_skip = if let Some(condition) = &step.condition {
-            matches!(evaluator.evaluate_string(condition), Ok(false))
-        } else {
-            false
-        };
-
-        let status = if will_skip { "SKIP" } else { "RUN" };
-
         tracing::info!("");
         tracing::info!("  [Step {}/{}] {} [{}]", step_num, total, step.name, status);
         tracing::info!("    target:    {}", target);
@@ -115,7 +129,9 @@ async fn handle_dry_run(args: &RunArgs) -> Result<()> {
 }
 
 // 解析环境变量
-async fn parse_environments(environments: Option<Vec<String>>) -> Result<HashMap<String, String>> {
+pub async fn parse_environments(
+    environments: Option<Vec<String>>,
+) -> Result<HashMap<String, String>> {
     let mut env_map = HashMap::new();
 
     if let Some(env_strings) = environments {
@@ -0,0 +1,173 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Secret subcommand implementation
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::io::{IsTerminal, Read};
+
+use crate::infra::config::ConfKitConfigLoader;
+use crate::infra::secret::{SecretScope, SecretStore};
+
+#[derive(Args)]
+pub struct SecretCommand {
+    #[command(subcommand)]
+    command: SecretSubcommand,
+}
+
+#[derive(Args)]
+pub struct SecretScopeArgs {
+    /// Space name.
+    #[arg(short, long)]
+    space: String,
+    /// Project name (secrets scoped to a single project).
+    #[arg(short, long)]
+    project: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum SecretSubcommand {
+    /// Set a secret (value from --value, stdin or a hidden prompt).
+    Set {
+        #[command(flatten)]
+        scope: SecretScopeArgs,
+        /// Secret name.
+        name: String,
+        /// Secret value. Prefer stdin or the prompt to keep it out of shell history.
+        #[arg(long)]
+        value: Option<String>,
+    },
+    /// Print a secret value.
+    Get {
+        #[command(flatten)]
+        scope: SecretScopeArgs,
+        /// Secret name.
+        name: String,
+    },
+    /// List secret names.
+    List {
+        #[command(flatten)]
+        scope: SecretScopeArgs,
+    },
+    /// Remove a secret.
+    Rm {
+        #[command(flatten)]
+        scope: SecretScopeArgs,
+        /// Secret name.
+        name: String,
+    },
+    /// Rotate the master key and re-encrypt all secret files.
+    RotateKey,
+}
+
+impl SecretCommand {
+    pub async fn execute(self) -> Result<()> {
+        match self.command {
+            SecretSubcommand::Set { scope, name, value } => handle_set(&scope, &name, value).await,
+            SecretSubcommand::Get { scope, name } => handle_get(&scope, &name).await,
+            SecretSubcommand::List { scope } => handle_list(&scope).await,
+            SecretSubcommand::Rm { scope, name } => handle_rm(&scope, &name).await,
+            SecretSubcommand::RotateKey => handle_rotate_key(),
+        }
+    }
+
+    /// 是否向标准输出写入结构化数据
+    pub fn writes_data_to_stdout(&self) -> bool {
+        matches!(self.command, SecretSubcommand::Get { .. })
+    }
+}
+
+/// 校验 space / 项目存在并返回密钥作用域
+async fn resolve_scope(args: &SecretScopeArgs) -> Result<SecretScope> {
+    if ConfKitConfigLoader::get_space_config(&args.space).await?.is_none() {
+        return Err(anyhow::anyhow!("Space '{}' not found", args.space));
+    }
+
+    match &args.project {
+        Some(project) => {
+            if ConfKitConfigLoader::get_project_config(&args.space, project).await?.is_none() {
+                return Err(anyhow::anyhow!(
+                    "Project '{}' not found in space '{}'",
+                    project,
+                    args.space
+                ));
+            }
+            Ok(SecretScope::project(&args.space, project))
+        }
+        None => Ok(SecretScope::space(&args.space)),
+    }
+}
+
+/// 读取密钥值: 参数 > 标准输入 > 隐藏输入提示
+fn read_secret_value(name: &str, value: Option<String>) -> Result<String> {
+    if let Some(value) = value {
+        return Ok(value);
+    }
+
+    if std::io::stdin().is_terminal() {
+        let value = inquire::Password::new(&format!("Value of secret '{name}':"))
+            .without_confirmation()
+            .prompt()?;
+        return Ok(value);
+    }
+
+    let mut value = String::new();
+    std::io::stdin().read_to_string(&mut value)?;
+    Ok(value.trim_end_matches(['\r', '\n']).to_string())
+}
+
+async fn handle_set(args: &SecretScopeArgs, name: &str, value: Option<String>) -> Result<()> {
+    let scope = resolve_scope(args).await?;
+    let value = read_secret_value(name, value)?;
+    if value.is_empty() {
+        return Err(anyhow::anyhow!("Secret value must not be empty"));
+    }
+
+    SecretStore::set(&scope, name, &value)?;
+    tracing::info!("Secret '{}' saved to {}", name, scope.file_path().display());
+    Ok(())
+}
+
+async fn handle_get(args: &SecretScopeArgs, name: &str) -> Result<()> {
+    let scope = resolve_scope(args).await?;
+    match SecretStore::get(&scope, name)? {
+        // 密钥值直接输出到标准输出, 便于管道使用
+        Some(value) => {
+            println!("{value}");
+            Ok(())
+        }
+        None => Err(anyhow::anyhow!("Secret '{}' not found in '{}'", name, scope)),
+    }
+}
+
+async fn handle_list(args: &SecretScopeArgs) -> Result<()> {
+    let scope = resolve_scope(args).await?;
+    let names = SecretStore::list(&scope)?;
+
+    if names.is_empty() {
+        tracing::info!("No secrets in '{}'", scope);
+        return Ok(());
+    }
+
+    tracing::info!("Secrets in '{}' ({}):", scope, names.len());
+    for name in names {
+        tracing::info!("  - {}", name);
+    }
+    Ok(())
+}
+
+async fn handle_rm(args: &SecretScopeArgs, name: &str) -> Result<()> {
+    let scope = resolve_scope(args).await?;
+    if !SecretStore::remove(&scope, name)? {
+        return Err(anyhow::anyhow!("Secret '{}' not found in '{}'", name, scope));
+    }
+
+    tracing::info!("Secret '{}' removed from '{}'", name, scope);
+    Ok(())
+}
+
+fn handle_rotate_key() -> Result<()> {
+    let count = SecretStore::rotate_key()?;
+    tracing::info!("Secret key rotated, {} secret file(s) re-encrypted", count);
+    Ok(())
+}
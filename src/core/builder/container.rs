@@ -21,7 +21,7 @@ impl ContainerBuilder {
     // 创建容器
     pub async fn create(name: &str, force: bool) -> Result<()> {
         if force {
-            ConfKitEngine::remove_container(name).await?;
+            Self::remove(name, true).await?;
         }
 
         if ConfKitEngine::check_container_exists(name).await? {
@@ -0,0 +1,139 @@
+//! Author: xiaoYown
+//! Created: 2026-10-19
+//! Description: Dependency graph between configured images (base_image -> image)
+
+use anyhow::Result;
+
+use crate::types::config::ConfKitImageConfig;
+
+/// 图中的镜像
+#[derive(Debug, Clone)]
+struct ImageNode {
+    name: String,
+    base_image: String,
+    tag: String,
+}
+
+/// 镜像依赖图
+///
+/// 基础镜像与目标镜像共用 tag, 因此 `base_image:tag` 与某个已配置镜像的 `name:tag` 相同时,
+/// 视为依赖该本地镜像; 每个镜像至多有一个本地基础镜像, 图为森林或含有循环。
+#[derive(Debug, Clone)]
+pub struct ImageGraph {
+    nodes: Vec<ImageNode>,
+}
+
+impl ImageGraph {
+    /// 按声明顺序传入 (name, base_image, tag)
+    pub fn new<'a>(images: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>) -> Self {
+        let nodes = images
+            .into_iter()
+            .map(|(name, base_image, tag)| ImageNode {
+                name: name.to_string(),
+                base_image: base_image.to_string(),
+                tag: tag.to_string(),
+            })
+            .collect();
+        Self { nodes }
+    }
+
+    pub fn from_configs(images: &[ConfKitImageConfig]) -> Self {
+        Self::new(
+            images
+                .iter()
+                .map(|image| (image.name.as_str(), image.base_image.as_str(), image.tag.as_str())),
+        )
+    }
+
+    /// 镜像下标
+    pub fn find(&self, name: &str, tag: &str) -> Option<usize> {
+        self.nodes.iter().position(|node| node.name == name && node.tag == tag)
+    }
+
+    /// 本地基础镜像的下标, 基础镜像需从远程拉取时为 None
+    pub fn local_base(&self, index: usize) -> Option<usize> {
+        let node = &self.nodes[index];
+        self.find(&node.base_image, &node.tag)
+    }
+
+    /// 以该镜像为本地基础镜像的镜像, 按声明顺序
+    pub fn children(&self, index: usize) -> Vec<usize> {
+        (0..self.nodes.len()).filter(|&child| self.local_base(child) == Some(index)).collect()
+    }
+
+    /// 没有本地基础镜像的镜像, 按声明顺序
+    pub fn roots(&self) -> Vec<usize> {
+        (0..self.nodes.len()).filter(|&index| self.local_base(index).is_none()).collect()
+    }
+
+    /// 第一个循环依赖, 返回首尾相同的下标路径 (如 a -> b -> a)
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        for start in 0..self.nodes.len() {
+            let mut path = vec![start];
+            let mut current = start;
+            while let Some(base) = self.local_base(current) {
+                if let Some(position) = path.iter().position(|&index| index == base) {
+                    let mut cycle = path[position..].to_vec();
+                    cycle.push(base);
+                    return Some(cycle);
+                }
+                path.push(base);
+                current = base;
+            }
+        }
+        None
+    }
+
+    /// 循环依赖的描述, 如 `a:1 -> b:1 -> a:1`
+    pub fn describe(&self, path: &[usize]) -> String {
+        path.iter().map(|&index| self.reference(index)).collect::<Vec<_>>().join(" -> ")
+    }
+
+    /// `name:tag`
+    pub fn reference(&self, index: usize) -> String {
+        format!("{}:{}", self.nodes[index].name, self.nodes[index].tag)
+    }
+
+    /// 全部镜像的构建顺序: 本地基础镜像在前, 其余保持声明顺序
+    pub fn build_order(&self) -> Result<Vec<usize>> {
+        self.check_cycle()?;
+
+        let mut order = vec![];
+        for index in 0..self.nodes.len() {
+            self.push_with_bases(index, &mut order);
+        }
+        Ok(order)
+    }
+
+    /// 构建指定镜像所需的顺序: 本地基础镜像链在前, 镜像自身在最后
+    pub fn ancestors(&self, index: usize) -> Result<Vec<usize>> {
+        self.check_cycle()?;
+
+        let mut order = vec![];
+        self.push_with_bases(index, &mut order);
+        Ok(order)
+    }
+
+    fn check_cycle(&self) -> Result<()> {
+        match self.find_cycle() {
+            Some(cycle) => anyhow::bail!("Image dependency cycle: {}", self.describe(&cycle)),
+            None => Ok(()),
+        }
+    }
+
+    // 先加入尚未加入的基础镜像链, 调用前已确认无循环
+    fn push_with_bases(&self, index: usize, order: &mut Vec<usize>) {
+        let mut chain = vec![index];
+        let mut current = index;
+        while let Some(base) = self.local_base(current) {
+            chain.push(base);
+            current = base;
+        }
+
+        for index in chain.into_iter().rev() {
+            if !order.contains(&index) {
+                order.push(index);
+            }
+        }
+    }
+}
@@ -2,11 +2,36 @@
 //! Created: 2025-07-21
 //! Description: Image builder implementation
 
-use anyhow::Result;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use regex::{Captures, Regex};
+use ring::digest;
 
 use crate::{
-    engine::ConfKitEngine, formatter::builder_image::BuilderImageFormatter,
-    infra::config::ConfKitConfigLoader, types::config::ConfKitImageInfo,
+    core::builder::{
+        graph::ImageGraph,
+        prune::{self, PrunePolicy},
+    },
+    engine::ConfKitEngine,
+    formatter::builder_image::BuilderImageFormatter,
+    infra::{
+        config::ConfKitConfigLoader,
+        git::{GitClient, LocalRevision},
+        registry::DockerConfig,
+        secret::SecretResolver,
+    },
+    shared::constants::{
+        IMAGE_LABEL_CONFKIT_IMAGE, IMAGE_LABEL_CONFKIT_VERSION, IMAGE_LABEL_CONTENT_HASH,
+    },
+    types::config::{
+        BuildSecret, ConfKitImageConfig, ConfKitImageInfo, ConfKitImagePushConfig,
+        ConfKitSecretSource, ContainerStatus, EngineMode, ImageBuildOptions, ImageStatus,
+        RegistryAuth,
+    },
+    utils::dockerignore::context_files,
 };
 
 pub struct ImageBuilder;
@@ -21,7 +46,10 @@ impl ImageBuilder {
         }
         let image_config = image_config.unwrap();
 
-        let image_info = ConfKitEngine::get_image_info(name, tag).await?;
+        let mut image_info = ConfKitEngine::get_image_info(name, tag).await?;
+        if image_info.status == ImageStatus::Built && Self::is_stale(&image_config).await? {
+            image_info.status = ImageStatus::Stale;
+        }
 
         let confkit_image_info = ConfKitImageInfo {
             name: name.to_string(),
@@ -55,54 +83,399 @@ impl ImageBuilder {
         Ok(images)
     }
 
-    // 构建镜像
+    // 构建镜像, 依赖的本地基础镜像先按需构建
     pub async fn build(name: &str, tag: &str) -> Result<()> {
-        // 检查目标镜像是否已经存在
-        if ConfKitEngine::check_image_exists(name, tag).await? {
-            tracing::info!("Image {} already exists", name);
-            return Ok(());
-        }
-
-        let config = ConfKitConfigLoader::get_image_config(name, tag).await?;
+        let config = ConfKitConfigLoader::get_config();
+        let graph = ImageGraph::from_configs(&config.images);
 
         // 检查配置中是否存在镜像
-        let config = match config {
-            Some(cfg) => cfg,
+        let index = match graph.find(name, tag) {
+            Some(index) => index,
             None => return Err(anyhow::anyhow!("Image {} not found", name)),
         };
 
-        let is_base_image_exists =
-            ConfKitEngine::check_image_exists(&config.base_image, tag).await?;
+        Self::build_in_order(&config.images, &graph, &graph.ancestors(index)?).await
+    }
+
+    // 按顺序构建, 本地基础镜像重新构建后其下游镜像也重新构建
+    async fn build_in_order(
+        images: &[ConfKitImageConfig],
+        graph: &ImageGraph,
+        order: &[usize],
+    ) -> Result<()> {
+        let mut rebuilt = HashSet::new();
+
+        for &index in order {
+            let base = graph.local_base(index);
+            let base_rebuilt = base.is_some_and(|base| rebuilt.contains(&base));
+            if Self::build_image(&images[index], base.is_some(), base_rebuilt).await? {
+                rebuilt.insert(index);
+            }
+        }
+
+        Ok(())
+    }
+
+    // 构建单个镜像, 返回是否实际构建; 本地基础镜像不从远程拉取
+    async fn build_image(
+        config: &ConfKitImageConfig,
+        local_base: bool,
+        base_rebuilt: bool,
+    ) -> Result<bool> {
+        let (name, tag) = (config.name.as_str(), config.tag.as_str());
+        let content_hash =
+            Self::content_hash(Path::new(&config.engine_file), Path::new(&config.context))?;
+
+        // 目标镜像已存在且内容与基础镜像均未变更时跳过
+        if ConfKitEngine::check_image_exists(name, tag).await? {
+            let labels = ConfKitEngine::get_image_labels(name, tag).await?;
+            if base_rebuilt {
+                tracing::info!(
+                    "Base image {}:{} was rebuilt, rebuilding {}:{}",
+                    config.base_image,
+                    tag,
+                    name,
+                    tag
+                );
+            } else if labels.get(IMAGE_LABEL_CONTENT_HASH) == Some(&content_hash) {
+                tracing::info!("Image {} already exists and is up to date", name);
+                return Ok(false);
+            } else {
+                tracing::info!(
+                    "Image {}:{} is stale (Dockerfile or build context changed), rebuilding",
+                    name,
+                    tag
+                );
+            }
+        }
+
+        let mut options = Self::build_options(config)?;
 
-        tracing::debug!("Checking base image: {}", config.base_image);
+        if local_base {
+            tracing::debug!("Using local base image: {}:{}", config.base_image, tag);
+            // 本地基础镜像不在远程仓库中, 不能要求构建时拉取
+            if options.pull {
+                tracing::warn!(
+                    "Ignoring pull for {}:{}, base image {}:{} is built locally",
+                    name,
+                    tag,
+                    config.base_image,
+                    tag
+                );
+                options.pull = false;
+            }
+        } else {
+            tracing::debug!("Checking base image: {}", config.base_image);
 
-        // 检查基础镜像是否存在, 不存在则拉取
-        if !is_base_image_exists {
-            tracing::info!("Pulling base image: {}", config.base_image);
-            ConfKitEngine::pull_image(&config.base_image, tag).await?;
+            // 检查基础镜像是否存在, 不存在则拉取
+            if !ConfKitEngine::check_image_exists(&config.base_image, tag).await? {
+                tracing::info!("Pulling base image: {}", config.base_image);
+                ConfKitEngine::pull_image(&config.base_image, tag).await?;
+            }
         }
 
         tracing::debug!("Building image: {} with tag: {}", name, tag);
 
         // 构建镜像
-        ConfKitEngine::build_image(&config.name, &config.tag, &config.engine_file, None).await?;
+        options.labels.insert(IMAGE_LABEL_CONTENT_HASH.to_string(), content_hash);
+        ConfKitEngine::build_image(
+            &config.name,
+            &config.tag,
+            &config.engine_file,
+            Some(&config.context),
+            &options,
+        )
+        .await?;
 
         let engine_image_info = Self::get_info(name, tag).await?;
 
         BuilderImageFormatter::print_image_info(engine_image_info.as_ref());
 
-        Ok(())
+        Ok(true)
+    }
+
+    // 镜像配置转换为构建选项, 并添加 git / confkit 标签 (配置中的同名标签优先)
+    pub fn build_options(config: &ConfKitImageConfig) -> Result<ImageBuildOptions> {
+        let revision = GitClient::local_revision(Path::new(&config.context));
+        let mut labels = Self::default_labels(config, revision.as_ref());
+        labels.extend(config.labels.clone().unwrap_or_default());
+
+        let mut secrets = vec![];
+        for secret in config.secrets.iter().flatten() {
+            let id = secret.name.clone();
+            secrets.push(match secret.from {
+                ConfKitSecretSource::File => match &secret.path {
+                    Some(path) => BuildSecret::File { id, path: path.clone() },
+                    None => anyhow::bail!("Build secret '{id}' (from: file) requires a path"),
+                },
+                ConfKitSecretSource::Env => {
+                    let var = secret.env.clone().unwrap_or_else(|| id.clone());
+                    BuildSecret::Env { id, var }
+                }
+                ConfKitSecretSource::Encrypted => anyhow::bail!(
+                    "Build secret '{id}': from: encrypted is not supported for images, use file or env"
+                ),
+            });
+        }
+
+        Ok(ImageBuildOptions {
+            build_args: config.build_args.clone().unwrap_or_default(),
+            target: config.target.clone(),
+            labels,
+            platforms: config.platforms.clone().unwrap_or_default(),
+            no_cache: config.no_cache.unwrap_or(false),
+            pull: config.pull.unwrap_or(false),
+            secrets,
+        })
+    }
+
+    // Dockerfile 与构建上下文 (遵循 .dockerignore) 的 SHA-256, 符号链接按其指向计算
+    pub fn content_hash(engine_file: &Path, context: &Path) -> Result<String> {
+        let mut hasher = digest::Context::new(&digest::SHA256);
+        let mut update = |name: &str, content: &[u8]| {
+            hasher.update(name.as_bytes());
+            hasher.update(&[0]);
+            hasher.update(&(content.len() as u64).to_be_bytes());
+            hasher.update(content);
+        };
+
+        let dockerfile = fs::read(engine_file)
+            .with_context(|| format!("Failed to read {}", engine_file.display()))?;
+        update("", &dockerfile);
+
+        for file in context_files(context)? {
+            let path = context.join(&file);
+            let content = if fs::symlink_metadata(&path)?.file_type().is_symlink() {
+                fs::read_link(&path)?.to_string_lossy().as_bytes().to_vec()
+            } else {
+                fs::read(&path)?
+            };
+            update(&file.to_string_lossy().replace('\\', "/"), &content);
+        }
+
+        Ok(hasher.finish().as_ref().iter().map(|byte| format!("{byte:02x}")).collect())
+    }
+
+    // 已构建的镜像是否落后于当前的 Dockerfile / 构建上下文, 无法计算哈希时不视为过期
+    async fn is_stale(config: &ConfKitImageConfig) -> Result<bool> {
+        let expected =
+            match Self::content_hash(Path::new(&config.engine_file), Path::new(&config.context)) {
+                Ok(hash) => hash,
+                Err(e) => {
+                    tracing::warn!("Failed to hash image {}:{}: {}", config.name, config.tag, e);
+                    return Ok(false);
+                }
+            };
+
+        let labels = ConfKitEngine::get_image_labels(&config.name, &config.tag).await?;
+        Ok(labels.get(IMAGE_LABEL_CONTENT_HASH) != Some(&expected))
+    }
+
+    // 自动添加的镜像标签, 构建上下文不在 git 仓库中时不含 revision / source
+    pub fn default_labels(
+        config: &ConfKitImageConfig,
+        revision: Option<&LocalRevision>,
+    ) -> BTreeMap<String, String> {
+        let mut labels = BTreeMap::from([
+            (IMAGE_LABEL_CONFKIT_VERSION.to_string(), env!("CARGO_PKG_VERSION").to_string()),
+            (IMAGE_LABEL_CONFKIT_IMAGE.to_string(), format!("{}:{}", config.name, config.tag)),
+            (
+                "org.opencontainers.image.base.name".to_string(),
+                format!("{}:{}", config.base_image, config.tag),
+            ),
+        ]);
+
+        if let Some(revision) = revision {
+            labels.insert(
+                "org.opencontainers.image.revision".to_string(),
+                revision.commit_hash.clone(),
+            );
+            if let Some(repo_url) = &revision.repo_url {
+                labels.insert("org.opencontainers.image.source".to_string(), repo_url.clone());
+            }
+        }
+
+        labels
     }
 
-    // 构建所有镜像
+    // 构建所有镜像, 本地基础镜像先于依赖它的镜像构建
     pub async fn build_all() -> Result<()> {
         let config = ConfKitConfigLoader::get_config();
-        for image in config.images {
-            Self::build(&image.name, &image.tag).await?;
+        let graph = ImageGraph::from_configs(&config.images);
+        Self::build_in_order(&config.images, &graph, &graph.build_order()?).await
+    }
+
+    // 推送镜像到配置的镜像仓库, overrides 覆盖标签模板中的变量
+    pub async fn push(name: &str, tag: &str, overrides: &HashMap<String, String>) -> Result<()> {
+        let config = ConfKitConfigLoader::get_image_config(name, tag)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Image {} not found", name))?;
+        let push = config
+            .push
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Image {}:{} has no push configuration", name, tag))?;
+
+        if !ConfKitEngine::check_image_exists(name, tag).await? {
+            anyhow::bail!(
+                "Image {name}:{tag} is not built, run `confkit image create -n {name} -t {tag}` first"
+            );
+        }
+        if Self::is_stale(&config).await? {
+            tracing::warn!("Image {}:{} is stale, pushing the existing build", name, tag);
+        }
+
+        // 标签模板变量: 宿主机环境变量 < 构建上下文的 git / 版本信息 < 命令行参数
+        let context = Path::new(&config.context);
+        let mut variables: HashMap<String, String> = std::env::vars().collect();
+        variables.extend(Self::push_variables(
+            &config,
+            GitClient::local_revision(context).as_ref(),
+            GitClient::local_project_version(context),
+        ));
+        variables.extend(overrides.clone());
+
+        let targets = Self::push_targets(&config, &variables)?;
+        // cli 模式下引擎自行读取 docker config 中的凭据, 只有配置的 credentials 需要登录
+        let auth = if push.credentials.is_none()
+            && ConfKitConfigLoader::get_config().engine_mode == EngineMode::Cli
+        {
+            None
+        } else {
+            Self::registry_auth(push, &DockerConfig::default_path())?
+        };
+
+        for (repository, target_tag) in &targets {
+            ConfKitEngine::tag_image(name, tag, repository, target_tag).await?;
+            ConfKitEngine::push_image(repository, target_tag, auth.as_ref()).await?;
+            tracing::info!("Pushed {}:{}", repository, target_tag);
+        }
+
+        Ok(())
+    }
+
+    // 推送所有配置了 push 的镜像
+    pub async fn push_all(overrides: &HashMap<String, String>) -> Result<()> {
+        let config = ConfKitConfigLoader::get_config();
+        let images: Vec<_> = config.images.iter().filter(|image| image.push.is_some()).collect();
+
+        if images.is_empty() {
+            tracing::info!("No images with push configuration");
+            return Ok(());
+        }
+        for image in images {
+            Self::push(&image.name, &image.tag, overrides).await?;
         }
         Ok(())
     }
 
+    // 镜像自身提供的标签模板变量, 构建上下文不在 git 仓库中时不含 GIT_HASH / GIT_HASH_SHORT
+    pub fn push_variables(
+        config: &ConfKitImageConfig,
+        revision: Option<&LocalRevision>,
+        project_version: Option<String>,
+    ) -> HashMap<String, String> {
+        let mut variables = HashMap::from([("TAG".to_string(), config.tag.clone())]);
+
+        if let Some(revision) = revision {
+            let short = revision.commit_hash.chars().take(8).collect();
+            variables.insert("GIT_HASH".to_string(), revision.commit_hash.clone());
+            variables.insert("GIT_HASH_SHORT".to_string(), short);
+        }
+        if let Some(version) = project_version {
+            variables.insert("PROJECT_VERSION".to_string(), version);
+        }
+
+        variables
+    }
+
+    // 推送目标 (registry/repository, 标签), 标签去重并保持配置顺序
+    pub fn push_targets(
+        config: &ConfKitImageConfig,
+        variables: &HashMap<String, String>,
+    ) -> Result<Vec<(String, String)>> {
+        let push = config
+            .push
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Image {} has no push configuration", config.name))?;
+
+        let registry = push
+            .registry
+            .trim()
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_end_matches('/');
+        let repository = push.repository.as_deref().unwrap_or(&config.name).trim_matches('/');
+        let repository = format!("{registry}/{repository}");
+
+        let templates = push.tags.clone().unwrap_or_else(|| vec!["${TAG}".to_string()]);
+        let mut targets: Vec<(String, String)> = vec![];
+        for template in &templates {
+            let tag = Self::render_tag(template, variables)?;
+            if !targets.iter().any(|(_, existing)| *existing == tag) {
+                targets.push((repository.clone(), tag));
+            }
+        }
+
+        Ok(targets)
+    }
+
+    // 替换标签模板中的 `${VAR}`, 变量未定义或结果不是合法标签时返回错误
+    pub fn render_tag(template: &str, variables: &HashMap<String, String>) -> Result<String> {
+        let variable = Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap();
+        let mut missing: Option<String> = None;
+        let tag = variable.replace_all(template, |caps: &Captures| match variables.get(&caps[1]) {
+            Some(value) => value.clone(),
+            None => {
+                missing.get_or_insert_with(|| caps[1].to_string());
+                String::new()
+            }
+        });
+
+        if let Some(name) = missing {
+            anyhow::bail!("Push tag '{template}' references undefined variable ${{{name}}}");
+        }
+
+        // 与 docker 的标签规则一致
+        let valid = Regex::new(r"^[A-Za-z0-9_][A-Za-z0-9_.-]{0,127}$").unwrap();
+        if !valid.is_match(&tag) {
+            anyhow::bail!("Push tag '{template}' renders to an invalid tag '{tag}'");
+        }
+        Ok(tag.to_string())
+    }
+
+    // 推送凭据: 配置中的 file / env 密钥优先, 否则读取 docker 配置文件
+    pub fn registry_auth(
+        push: &ConfKitImagePushConfig,
+        docker_config: &Path,
+    ) -> Result<Option<RegistryAuth>> {
+        let Some(credentials) = &push.credentials else {
+            return DockerConfig::load(docker_config)?.auth(&push.registry);
+        };
+
+        // 镜像不属于任何项目, 加密凭据只从 push.space 指定的 space 级存储中读取
+        let space = push.space.as_deref().unwrap_or_default();
+        if let Some(secret) = credentials
+            .iter()
+            .find(|secret| secret.from == ConfKitSecretSource::Encrypted && space.is_empty())
+        {
+            anyhow::bail!(
+                "Push credential '{}': from: encrypted requires push.space, the space whose encrypted secrets hold it",
+                secret.name
+            );
+        }
+
+        let mut resolved = SecretResolver::resolve_in_space(space, credentials)?;
+        match (resolved.remove("username"), resolved.remove("password")) {
+            (Some(username), Some(password)) => {
+                Ok(Some(RegistryAuth { username, password, server_address: push.registry.clone() }))
+            }
+            _ => anyhow::bail!(
+                "Push credentials for registry {} require 'username' and 'password'",
+                push.registry
+            ),
+        }
+    }
+
     // 移除镜像
     pub async fn remove(name: &str, tag: &str) -> Result<()> {
         // TODO: 检查镜像是否被使用，如果被使用，则提示用户是否强制移除
@@ -122,9 +495,78 @@ impl ImageBuilder {
         Ok(())
     }
 
+    // 按保留策略清理配置镜像的历史标签, name 为空时处理全部镜像
+    pub async fn prune(name: Option<&str>, policy: &PrunePolicy, dry_run: bool) -> Result<()> {
+        let config = ConfKitConfigLoader::get_config();
+        let mut names: Vec<&str> = vec![];
+        for image in &config.images {
+            if name.is_none_or(|name| name == image.name) && !names.contains(&image.name.as_str()) {
+                names.push(&image.name);
+            }
+        }
+        if let Some(name) = name.filter(|_| names.is_empty()) {
+            anyhow::bail!("Image {} not found in config", name);
+        }
+
+        // compose 配置无法读取时不清理, 避免移除构建容器正在使用的镜像
+        let mut in_use = vec![];
+        for service in ConfKitEngine::get_compose_services().await? {
+            if !service.container_name.is_empty() {
+                let container = ConfKitEngine::get_container_info(&service.container_name).await?;
+                if container.status != ContainerStatus::Unbuilt {
+                    in_use.push(container.image);
+                }
+            }
+            in_use.push(service.image);
+        }
+        let protected = prune::protected_references(&config.images, &in_use);
+
+        let now = chrono::Utc::now();
+        let (mut selected, mut failed) = (0, 0);
+        for name in names {
+            let tags = ConfKitEngine::list_image_tags(name).await?;
+            for info in policy.select(&tags, &protected, now) {
+                selected += 1;
+                if dry_run {
+                    tracing::info!(
+                        "Would remove {}:{} (created {}, {})",
+                        info.name,
+                        info.tag,
+                        info.created_at,
+                        info.size
+                    );
+                    continue;
+                }
+
+                match ConfKitEngine::remove_image(&info.name, &info.tag).await {
+                    Ok(()) => tracing::info!("Removed {}:{}", info.name, info.tag),
+                    Err(err) => {
+                        failed += 1;
+                        tracing::warn!("Failed to remove {}:{}: {}", info.name, info.tag, err);
+                    }
+                }
+            }
+        }
+
+        match (selected, dry_run) {
+            (0, _) => tracing::info!("No image tags to prune"),
+            (count, true) => tracing::info!("{} image tag(s) would be removed", count),
+            (count, false) => tracing::info!("Removed {} image tag(s)", count - failed),
+        }
+        if failed > 0 {
+            anyhow::bail!("Failed to remove {} image tag(s)", failed);
+        }
+        Ok(())
+    }
+
     pub async fn print_list() -> Result<()> {
         let images = Self::get_list().await?;
         BuilderImageFormatter::print_list(&images);
         Ok(())
     }
+
+    pub async fn print_tree() -> Result<()> {
+        let images = Self::get_list().await?;
+        BuilderImageFormatter::print_tree(&images)
+    }
 }
@@ -3,4 +3,6 @@
 //! Description: Builder core module
 
 pub mod container;
+pub mod graph;
 pub mod image;
+pub mod prune;
@@ -0,0 +1,125 @@
+//! Author: xiaoYown
+//! Created: 2026-10-19
+//! Description: Retention policy for local tags of configured images
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+
+use crate::types::config::{ConfKitImageConfig, EngineImageInfo};
+
+/// 镜像清理策略
+///
+/// 每个镜像按创建时间保留最新的 `keep` 个标签, 其余标签中创建时间早于 `older_than` 的被移除;
+/// 受保护的标签 (配置中的镜像, compose 服务使用的镜像) 始终保留。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrunePolicy {
+    pub older_than: Option<Duration>,
+    pub keep: usize,
+}
+
+impl PrunePolicy {
+    /// 至少需要指定 `older_than` 或 `keep` 之一, 避免误删全部历史标签
+    pub fn new(older_than: Option<&str>, keep: Option<usize>) -> Result<Self> {
+        if older_than.is_none() && keep.is_none() {
+            anyhow::bail!("Specify --older-than and/or --keep for image prune");
+        }
+
+        Ok(Self { older_than: older_than.map(parse_age).transpose()?, keep: keep.unwrap_or(0) })
+    }
+
+    /// 同一镜像的本地标签中需要移除的标签, 按创建时间从新到旧
+    ///
+    /// 创建时间无法解析的标签无法判断新旧, 一律保留。
+    pub fn select(
+        &self,
+        tags: &[EngineImageInfo],
+        protected: &HashSet<String>,
+        now: DateTime<Utc>,
+    ) -> Vec<EngineImageInfo> {
+        let mut dated: Vec<(DateTime<Utc>, &EngineImageInfo)> = tags
+            .iter()
+            .filter_map(|info| parse_created_at(&info.created_at).map(|created| (created, info)))
+            .collect();
+        dated.sort_by_key(|(created, _)| std::cmp::Reverse(*created));
+
+        dated
+            .into_iter()
+            .skip(self.keep)
+            .filter(|(_, info)| !protected.contains(&format!("{}:{}", info.name, info.tag)))
+            .filter(|(created, _)| self.older_than.is_none_or(|age| now - *created > age))
+            .map(|(_, info)| info.clone())
+            .collect()
+    }
+}
+
+/// 不可移除的镜像引用 (`name:tag`): 配置中的镜像, 以及 compose 服务与已有容器使用的镜像
+pub fn protected_references(images: &[ConfKitImageConfig], in_use: &[String]) -> HashSet<String> {
+    let configured = images.iter().map(|image| format!("{}:{}", image.name, image.tag));
+    let in_use = in_use.iter().filter(|image| !image.trim().is_empty()).map(|image| {
+        let image = image.trim();
+        let image = image.strip_prefix("docker.io/library/").unwrap_or(image);
+        let image = image.strip_prefix("docker.io/").unwrap_or(image);
+        // 最后一段路径中没有标签时为 latest
+        if image.rsplit('/').next().is_some_and(|name| name.contains(':')) {
+            image.to_string()
+        } else {
+            format!("{image}:latest")
+        }
+    });
+
+    configured.chain(in_use).collect()
+}
+
+/// 解析时长, 如 `90m` / `12h` / `30d` / `2w`
+pub fn parse_age(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid duration '{}', expected a number followed by s, m, h, d or w (eg: 30d)",
+            value
+        )
+    };
+
+    let unit = value.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = value[..value.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+    let duration = match unit {
+        's' => Duration::try_seconds(amount),
+        'm' => Duration::try_minutes(amount),
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => None,
+    };
+
+    duration.filter(|duration| *duration >= Duration::zero()).ok_or_else(invalid)
+}
+
+/// 解析引擎输出的创建时间
+///
+/// docker / podman 为 `2026-10-01 10:00:00 +0000 UTC` (末尾为时区名), nerdctl 可能为 RFC3339。
+pub fn parse_created_at(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+
+    let mut parts: Vec<&str> = value.split_whitespace().collect();
+    if parts.len() == 4 {
+        parts.pop();
+    }
+    match parts.as_slice() {
+        [date, time, offset] => {
+            DateTime::parse_from_str(&format!("{date} {time} {offset}"), "%Y-%m-%d %H:%M:%S%.f %z")
+                .ok()
+                .map(|time| time.with_timezone(&Utc))
+        }
+        [date, time] => {
+            NaiveDateTime::parse_from_str(&format!("{date} {time}"), "%Y-%m-%d %H:%M:%S%.f")
+                .ok()
+                .map(|time| time.and_utc())
+        }
+        _ => None,
+    }
+}
@@ -3,16 +3,23 @@
 //! 提供条件表达式的运行时求值能力，支持环境变量解析、类型转换和逻辑运算
 
 use anyhow::{anyhow, Result};
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
+use super::lint::regex_error_reason;
 use super::parser::parse_condition;
-use super::{ComparisonOperator, ConditionExpression, ConditionValue, LogicalOperator};
+use super::{
+    parse_version, ComparisonOperator, ConditionExpression, ConditionFunction, ConditionValue,
+    LogicalOperator, MessageLang,
+};
 
 /// 条件求值引擎
 #[derive(Debug)]
 pub struct ConditionEvaluator {
     /// 环境变量映射
     environment: HashMap<String, String>,
+    /// 带类型的变量, 优先于环境变量, 如 `steps.<name>.status`
+    values: HashMap<String, ConditionValue>,
     /// 最大递归深度
     max_recursion_depth: usize,
     /// 未定义变量的默认值
@@ -20,42 +27,121 @@ pub struct ConditionEvaluator {
 }
 
 /// 求值错误类型
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug)]
 pub enum EvaluationError {
-    #[error("递归深度超限")]
     RecursionLimit,
-    #[error("不支持的操作: {0}")]
-    UnsupportedOperation(String),
+    /// 正则表达式及错误原因
+    InvalidPattern(String, String),
+    InvalidVersion(String),
+    /// 函数参数个数不符, 仅出现在未经解析器构造的表达式中
+    ArgumentCount {
+        function: ConditionFunction,
+        expected: usize,
+        found: usize,
+    },
+    /// 逻辑运算缺少操作数, 仅出现在未经解析器构造的表达式中
+    MissingOperand {
+        operator: &'static str,
+        expected: usize,
+    },
+}
+
+impl EvaluationError {
+    /// 错误描述
+    pub fn message(&self, lang: MessageLang) -> String {
+        match (self, lang) {
+            (EvaluationError::RecursionLimit, MessageLang::En) => {
+                "recursion limit exceeded".to_string()
+            }
+            (EvaluationError::RecursionLimit, MessageLang::Zh) => "递归深度超限".to_string(),
+            (EvaluationError::InvalidPattern(pattern, reason), MessageLang::En) => {
+                format!("invalid regular expression '{pattern}': {reason}")
+            }
+            (EvaluationError::InvalidPattern(pattern, reason), MessageLang::Zh) => {
+                format!("无效的正则表达式 '{pattern}': {reason}")
+            }
+            (EvaluationError::InvalidVersion(version), MessageLang::En) => {
+                format!("invalid version '{version}'")
+            }
+            (EvaluationError::InvalidVersion(version), MessageLang::Zh) => {
+                format!("无效的版本号 '{version}'")
+            }
+            (EvaluationError::ArgumentCount { function, expected, found }, MessageLang::En) => {
+                format!("{function}() takes {expected} argument(s), {found} given")
+            }
+            (EvaluationError::ArgumentCount { function, expected, found }, MessageLang::Zh) => {
+                format!("{function}() 需要 {expected} 个参数, 实际为 {found} 个")
+            }
+            (EvaluationError::MissingOperand { operator, expected }, MessageLang::En) => {
+                format!("'{operator}' requires {expected} operand(s)")
+            }
+            (EvaluationError::MissingOperand { operator, expected }, MessageLang::Zh) => {
+                format!("'{operator}' 需要 {expected} 个操作数")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for EvaluationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message(MessageLang::from_env()))
+    }
 }
 
+impl std::error::Error for EvaluationError {}
+
 impl ConditionEvaluator {
     /// 创建新的求值器实例
     pub fn new(environment: HashMap<String, String>) -> Self {
         Self {
             environment,
+            values: HashMap::new(),
             max_recursion_depth: 100,
             default_for_undefined_var: ConditionValue::Null,
         }
     }
 
+    /// 追加带类型的变量
+    pub fn with_values(mut self, values: HashMap<String, ConditionValue>) -> Self {
+        self.values.extend(values);
+        self
+    }
+
     /// 求值条件表达式，返回布尔结果
     pub fn evaluate(&self, expr: &ConditionExpression) -> Result<bool> {
         let value = self.evaluate_to_value(expr, 0)?;
         Ok(value.is_truthy())
     }
 
-    /// 从字符串表达式求值
+    /// 从字符串表达式求值, 解析失败时按成立处理
+    #[allow(dead_code)]
     pub fn evaluate_string(&self, expr_str: &str) -> Result<bool> {
         match parse_condition(expr_str) {
             Ok(ast) => self.evaluate(&ast),
-            Err(parse_error) => self.handle_parse_error(expr_str, parse_error),
+            Err(parse_error) => self.handle_parse_error(parse_error),
         }
     }
 
-    /// 处理解析错误的降级策略  
-    fn handle_parse_error(&self, expr_str: &str, error: super::parser::ParseError) -> Result<bool> {
+    /// 从字符串表达式求值, 解析错误同样返回 Err, 由调用方决定降级策略
+    pub fn try_evaluate_string(&self, expr_str: &str) -> Result<bool> {
+        let ast = parse_condition(expr_str)?;
+        self.evaluate(&ast)
+    }
+
+    /// 处理解析错误的降级策略
+    #[allow(dead_code)]
+    fn handle_parse_error(&self, error: super::parser::ParseError) -> Result<bool> {
         // 默认策略：无条件执行（保守策略）
-        tracing::warn!("条件表达式解析失败，采用无条件执行策略: {} - {}", expr_str, error);
+        let lang = MessageLang::from_env();
+        match lang {
+            MessageLang::En => tracing::warn!(
+                "Failed to parse condition, treating it as true: {}",
+                error.render(lang)
+            ),
+            MessageLang::Zh => {
+                tracing::warn!("条件表达式解析失败，采用无条件执行策略: {}", error.render(lang))
+            }
+        }
         Ok(true)
     }
 
@@ -82,11 +168,105 @@ impl ConditionEvaluator {
                 right.as_ref().map(|v| &**v),
                 depth + 1,
             ),
+            ConditionExpression::List { items } => {
+                let values = items
+                    .iter()
+                    .map(|item| self.evaluate_to_value(item, depth + 1))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(ConditionValue::List(values))
+            }
+            ConditionExpression::Function { function, args } => {
+                let values = args
+                    .iter()
+                    .map(|arg| self.evaluate_to_value(arg, depth + 1))
+                    .collect::<Result<Vec<_>>>()?;
+                self.evaluate_function(function, &values)
+            }
+        }
+    }
+
+    /// 求值函数调用, 参数个数已在解析时校验
+    fn evaluate_function(
+        &self,
+        function: &ConditionFunction,
+        args: &[ConditionValue],
+    ) -> Result<ConditionValue> {
+        if args.len() != function.arity() {
+            return Err(anyhow!(EvaluationError::ArgumentCount {
+                function: *function,
+                expected: function.arity(),
+                found: args.len(),
+            }));
+        }
+
+        let value = match function {
+            ConditionFunction::Contains => {
+                ConditionValue::Boolean(self.contains_value(&args[0], &args[1]))
+            }
+            ConditionFunction::StartsWith => ConditionValue::Boolean(
+                args[0].to_string().starts_with(args[1].to_string().as_str()),
+            ),
+            ConditionFunction::EndsWith => {
+                ConditionValue::Boolean(args[0].to_string().ends_with(args[1].to_string().as_str()))
+            }
+            ConditionFunction::Matches => {
+                ConditionValue::Boolean(self.matches_pattern(&args[0], &args[1])?)
+            }
+            ConditionFunction::Len => {
+                let len = match &args[0] {
+                    ConditionValue::List(items) => items.len(),
+                    ConditionValue::Null => 0,
+                    value => value.to_string().chars().count(),
+                };
+                ConditionValue::Number(len as f64)
+            }
+            ConditionFunction::Lower => match &args[0] {
+                ConditionValue::Null => ConditionValue::Null,
+                value => ConditionValue::String(value.to_string().to_lowercase()),
+            },
+            ConditionFunction::Semver => match &args[0] {
+                ConditionValue::Version(version) => ConditionValue::Version(version.clone()),
+                ConditionValue::Null => ConditionValue::Null,
+                value => {
+                    let text = value.to_string();
+                    let version = parse_version(&text, true)
+                        .ok_or_else(|| anyhow!(EvaluationError::InvalidVersion(text)))?;
+                    ConditionValue::Version(version)
+                }
+            },
+        };
+        Ok(value)
+    }
+
+    /// 列表是否包含元素, 或字符串是否包含子串
+    fn contains_value(&self, haystack: &ConditionValue, needle: &ConditionValue) -> bool {
+        match haystack {
+            ConditionValue::List(items) => items.iter().any(|item| {
+                self.compare_values(item, needle, &ComparisonOperator::Equal).unwrap_or(false)
+            }),
+            ConditionValue::Null => false,
+            value => value.to_string().contains(needle.to_string().as_str()),
+        }
+    }
+
+    /// 正则匹配, 空值不匹配任何模式
+    fn matches_pattern(&self, value: &ConditionValue, pattern: &ConditionValue) -> Result<bool> {
+        let pattern = pattern.to_string();
+        let regex = regex::Regex::new(&pattern).map_err(|e| {
+            anyhow!(EvaluationError::InvalidPattern(pattern.clone(), regex_error_reason(&e)))
+        })?;
+        match value {
+            ConditionValue::Null => Ok(false),
+            value => Ok(regex.is_match(&value.to_string())),
         }
     }
 
     /// 求值变量表达式
     fn evaluate_variable(&self, name: &str) -> Result<ConditionValue> {
+        if let Some(value) = self.values.get(name) {
+            return Ok(value.clone());
+        }
+
         match self.get_variable_value(name) {
             Some(value) => {
                 let condition_value = ConditionValue::from_string(&value);
@@ -112,7 +292,12 @@ impl ConditionEvaluator {
         let left_val = self.evaluate_to_value(left, depth)?;
         let right_val = self.evaluate_to_value(right, depth)?;
 
-        let result = self.compare_values(&left_val, &right_val, operator)?;
+        let result = match operator {
+            ComparisonOperator::In => self.contains_value(&right_val, &left_val),
+            ComparisonOperator::NotIn => !self.contains_value(&right_val, &left_val),
+            ComparisonOperator::Matches => self.matches_pattern(&left_val, &right_val)?,
+            _ => self.compare_values(&left_val, &right_val, operator)?,
+        };
 
         Ok(ConditionValue::Boolean(result))
     }
@@ -150,9 +335,7 @@ impl ConditionEvaluator {
 
                     Ok(ConditionValue::Boolean(result))
                 } else {
-                    Err(anyhow!(EvaluationError::UnsupportedOperation(
-                        "逻辑与运算需要两个操作数".to_string()
-                    )))
+                    Err(anyhow!(EvaluationError::MissingOperand { operator: "&&", expected: 2 }))
                 }
             }
             LogicalOperator::Or => {
@@ -179,9 +362,7 @@ impl ConditionEvaluator {
 
                     Ok(ConditionValue::Boolean(result))
                 } else {
-                    Err(anyhow!(EvaluationError::UnsupportedOperation(
-                        "逻辑或运算需要两个操作数".to_string()
-                    )))
+                    Err(anyhow!(EvaluationError::MissingOperand { operator: "||", expected: 2 }))
                 }
             }
             LogicalOperator::Not => {
@@ -196,9 +377,7 @@ impl ConditionEvaluator {
 
                     Ok(ConditionValue::Boolean(result))
                 } else {
-                    Err(anyhow!(EvaluationError::UnsupportedOperation(
-                        "逻辑非运算需要一个操作数".to_string()
-                    )))
+                    Err(anyhow!(EvaluationError::MissingOperand { operator: "!", expected: 1 }))
                 }
             }
         }
@@ -212,9 +391,29 @@ impl ConditionEvaluator {
         operator: &ComparisonOperator,
     ) -> Result<bool> {
         match (left, right) {
-            // 同类型比较
+            // 版本比较: 任一侧为 semver() 结果时另一侧按版本解析
+            (ConditionValue::Version(l), ConditionValue::Version(r)) => {
+                Ok(self.compare_versions(l, r, operator))
+            }
+            (ConditionValue::Version(l), r) if !matches!(r, ConditionValue::Null) => {
+                let text = r.to_string();
+                let r = parse_version(&text, true)
+                    .ok_or_else(|| anyhow!(EvaluationError::InvalidVersion(text)))?;
+                Ok(self.compare_versions(l, &r, operator))
+            }
+            (l, ConditionValue::Version(r)) if !matches!(l, ConditionValue::Null) => {
+                let text = l.to_string();
+                let l = parse_version(&text, true)
+                    .ok_or_else(|| anyhow!(EvaluationError::InvalidVersion(text)))?;
+                Ok(self.compare_versions(&l, r, operator))
+            }
+
+            // 同类型比较, 两侧都是完整的语义化版本时按版本比较
             (ConditionValue::String(l), ConditionValue::String(r)) => {
-                Ok(self.compare_strings(l, r, operator))
+                match (parse_version(l, false), parse_version(r, false)) {
+                    (Some(lv), Some(rv)) => Ok(self.compare_versions(&lv, &rv, operator)),
+                    _ => Ok(self.compare_strings(l, r, operator)),
+                }
             }
             (ConditionValue::Number(l), ConditionValue::Number(r)) => {
                 Ok(self.compare_numbers(*l, *r, operator))
@@ -246,38 +445,48 @@ impl ConditionEvaluator {
 
     /// 字符串比较
     fn compare_strings(&self, left: &str, right: &str, operator: &ComparisonOperator) -> bool {
-        match operator {
-            ComparisonOperator::Equal => left == right,
-            ComparisonOperator::NotEqual => left != right,
-            ComparisonOperator::GreaterThan => left > right,
-            ComparisonOperator::LessThan => left < right,
-            ComparisonOperator::GreaterThanOrEqual => left >= right,
-            ComparisonOperator::LessThanOrEqual => left <= right,
-        }
+        self.apply_ordering(left.cmp(right), operator)
     }
 
     /// 数字比较
     fn compare_numbers(&self, left: f64, right: f64, operator: &ComparisonOperator) -> bool {
-        match operator {
-            ComparisonOperator::Equal => (left - right).abs() < f64::EPSILON,
-            ComparisonOperator::NotEqual => (left - right).abs() >= f64::EPSILON,
-            ComparisonOperator::GreaterThan => left > right,
-            ComparisonOperator::LessThan => left < right,
-            ComparisonOperator::GreaterThanOrEqual => left >= right,
-            ComparisonOperator::LessThanOrEqual => left <= right,
+        if (left - right).abs() < f64::EPSILON {
+            return self.apply_ordering(Ordering::Equal, operator);
         }
+        match left.partial_cmp(&right) {
+            Some(ordering) => self.apply_ordering(ordering, operator),
+            // NaN 仅满足不等于
+            None => matches!(operator, ComparisonOperator::NotEqual),
+        }
+    }
+
+    /// 版本比较, 按 semver 优先级规则 (预发布版本低于正式版本, 忽略 build 元数据)
+    fn compare_versions(
+        &self,
+        left: &semver::Version,
+        right: &semver::Version,
+        operator: &ComparisonOperator,
+    ) -> bool {
+        self.apply_ordering(left.cmp_precedence(right), operator)
     }
 
-    /// 布尔值比较
+    /// 布尔值比较, false < true
     fn compare_booleans(&self, left: bool, right: bool, operator: &ComparisonOperator) -> bool {
+        self.apply_ordering(left.cmp(&right), operator)
+    }
+
+    /// 按比较结果判断运算符是否成立, in / not in / matches 不经过此处
+    fn apply_ordering(&self, ordering: Ordering, operator: &ComparisonOperator) -> bool {
         match operator {
-            ComparisonOperator::Equal => left == right,
-            ComparisonOperator::NotEqual => left != right,
-            // 布尔值的大小比较: false < true
-            ComparisonOperator::GreaterThan => left && !right,
-            ComparisonOperator::LessThan => !left && right,
-            ComparisonOperator::GreaterThanOrEqual => left >= right,
-            ComparisonOperator::LessThanOrEqual => left <= right,
+            ComparisonOperator::Equal => ordering == Ordering::Equal,
+            ComparisonOperator::NotEqual => ordering != Ordering::Equal,
+            ComparisonOperator::GreaterThan => ordering == Ordering::Greater,
+            ComparisonOperator::LessThan => ordering == Ordering::Less,
+            ComparisonOperator::GreaterThanOrEqual => ordering != Ordering::Less,
+            ComparisonOperator::LessThanOrEqual => ordering != Ordering::Greater,
+            ComparisonOperator::In | ComparisonOperator::NotIn | ComparisonOperator::Matches => {
+                false
+            }
         }
     }
 
@@ -287,6 +496,7 @@ impl ConditionEvaluator {
             ConditionValue::Number(n) => Some(*n),
             ConditionValue::String(s) => s.parse().ok(),
             ConditionValue::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
+            ConditionValue::Version(_) | ConditionValue::List(_) => None,
             ConditionValue::Null => Some(0.0),
         }
     }
@@ -0,0 +1,83 @@
+//! 条件表达式静态检查
+//!
+//! 不求值, 仅检查可在解析后确定的错误: matches 的正则字面量、semver() 的版本号字面量
+
+use super::{
+    parse_version, ComparisonOperator, ConditionExpression, ConditionFunction, ConditionValue,
+};
+
+/// 检查表达式中的字面量参数, 返回错误描述
+pub fn lint_condition(expr: &ConditionExpression) -> Vec<String> {
+    let mut issues = vec![];
+    walk(expr, &mut issues);
+    issues
+}
+
+fn walk(expr: &ConditionExpression, issues: &mut Vec<String>) {
+    match expr {
+        ConditionExpression::Variable { .. } | ConditionExpression::Literal { .. } => {}
+        ConditionExpression::Comparison { left, operator, right } => {
+            if *operator == ComparisonOperator::Matches {
+                check_pattern(right, issues);
+            }
+            walk(left, issues);
+            walk(right, issues);
+        }
+        ConditionExpression::Logical { left, right, .. } => {
+            for operand in [left, right].into_iter().flatten() {
+                walk(operand, issues);
+            }
+        }
+        ConditionExpression::List { items } => {
+            for item in items {
+                walk(item, issues);
+            }
+        }
+        ConditionExpression::Function { function, args } => {
+            match function {
+                ConditionFunction::Matches => check_pattern(&args[1], issues),
+                ConditionFunction::Semver => check_version(&args[0], issues),
+                _ => {}
+            }
+            for arg in args {
+                walk(arg, issues);
+            }
+        }
+    }
+}
+
+/// 字面量参数的字符串形式, 变量等运行时才能确定的值返回 None
+fn literal_text(expr: &ConditionExpression) -> Option<String> {
+    match expr {
+        ConditionExpression::Literal { value: value @ ConditionValue::String(_) }
+        | ConditionExpression::Literal { value: value @ ConditionValue::Number(_) } => {
+            Some(value.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn check_pattern(expr: &ConditionExpression, issues: &mut Vec<String>) {
+    let Some(pattern) = literal_text(expr) else {
+        return;
+    };
+    if let Err(e) = regex::Regex::new(&pattern) {
+        issues.push(format!("invalid regular expression '{pattern}': {}", regex_error_reason(&e)));
+    }
+}
+
+/// 正则错误原因; regex 的语法错误为带插入符的多行描述, 最后一行为原因
+pub fn regex_error_reason(error: &regex::Error) -> String {
+    let message = error.to_string();
+    let reason = message.lines().last().unwrap_or_default();
+    reason.trim_start_matches("error: ").to_string()
+}
+
+fn check_version(expr: &ConditionExpression, issues: &mut Vec<String>) {
+    let Some(version) = literal_text(expr) else {
+        return;
+    };
+    if parse_version(&version, true).is_none() {
+        issues.push(format!("invalid version '{version}' in semver()"));
+    }
+}
@@ -3,6 +3,7 @@
 //! Description: Condition expression module for conditional step execution
 
 pub mod evaluator;
+pub mod lint;
 pub mod parser;
 
 // Re-export types from the centralized types module
@@ -5,55 +5,192 @@
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while, take_while1},
-    character::complete::{char, digit1, multispace0},
-    combinator::{map, opt, recognize},
-    sequence::{delimited, preceded, tuple},
+    character::complete::{alpha1, alphanumeric0, char, digit1, multispace0, multispace1},
+    combinator::{cut, map, not, opt, recognize},
+    error::ErrorKind,
+    multi::separated_list0,
+    sequence::{delimited, pair, preceded, terminated, tuple},
     IResult,
 };
 
-use super::{ComparisonOperator, ConditionExpression, ConditionValue, LogicalOperator};
+use super::{
+    ComparisonOperator, ConditionExpression, ConditionFunction, ConditionValue, LogicalOperator,
+    MessageLang,
+};
+
+/// 解析错误的具体原因
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    /// 空表达式
+    Empty,
+    /// 意外的内容
+    UnexpectedToken(String),
+    /// 表达式意外结束
+    UnexpectedEnd,
+    /// 缺少闭合符号 `)`、`]`、`}`
+    Expected(char),
+    /// 字符串缺少结束引号
+    UnterminatedString,
+    /// 未知函数
+    UnknownFunction(String),
+    /// 函数参数个数不符
+    ArgumentCount { function: ConditionFunction, expected: usize, found: usize },
+}
+
+/// 解析错误, 列号从 1 开始按字符计数
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{}", self.message(MessageLang::from_env()))]
+pub struct ParseError {
+    /// 原始表达式
+    pub input: String,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// 错误描述, 含列号
+    pub fn message(&self, lang: MessageLang) -> String {
+        match lang {
+            MessageLang::En => {
+                let reason = match &self.kind {
+                    ParseErrorKind::Empty => "empty condition".to_string(),
+                    ParseErrorKind::UnexpectedToken(token) => format!("unexpected '{token}'"),
+                    ParseErrorKind::UnexpectedEnd => "unexpected end of condition".to_string(),
+                    ParseErrorKind::Expected(c) => format!("expected '{c}'"),
+                    ParseErrorKind::UnterminatedString => "unterminated string".to_string(),
+                    ParseErrorKind::UnknownFunction(name) => format!("unknown function '{name}'"),
+                    ParseErrorKind::ArgumentCount { function, expected, found } => {
+                        format!("{function}() takes {expected} argument(s), {found} given")
+                    }
+                };
+                format!("{reason} at column {}", self.column)
+            }
+            MessageLang::Zh => {
+                let reason = match &self.kind {
+                    ParseErrorKind::Empty => "条件表达式为空".to_string(),
+                    ParseErrorKind::UnexpectedToken(token) => format!("意外的内容 '{token}'"),
+                    ParseErrorKind::UnexpectedEnd => "表达式不完整".to_string(),
+                    ParseErrorKind::Expected(c) => format!("缺少 '{c}'"),
+                    ParseErrorKind::UnterminatedString => "字符串缺少结束引号".to_string(),
+                    ParseErrorKind::UnknownFunction(name) => format!("未知函数 '{name}'"),
+                    ParseErrorKind::ArgumentCount { function, expected, found } => {
+                        format!("{function}() 需要 {expected} 个参数, 实际为 {found} 个")
+                    }
+                };
+                format!("第 {} 列: {reason}", self.column)
+            }
+        }
+    }
+
+    /// 带原表达式和插入符 `^` 的多行描述
+    pub fn render(&self, lang: MessageLang) -> String {
+        format!(
+            "{}\n  {}\n  {}^",
+            self.message(lang),
+            self.input,
+            " ".repeat(self.column.saturating_sub(1))
+        )
+    }
+}
+
+/// nom 内部错误, 记录出错位置及已确定的原因
+#[derive(Debug)]
+struct SyntaxError<'a> {
+    input: &'a str,
+    kind: Option<ParseErrorKind>,
+}
+
+impl<'a> nom::error::ParseError<&'a str> for SyntaxError<'a> {
+    fn from_error_kind(input: &'a str, _kind: ErrorKind) -> Self {
+        Self { input, kind: None }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
 
-/// 解析错误类型
-#[derive(Debug, thiserror::Error)]
-pub enum ParseError {
-    #[error("语法错误: {0}")]
-    SyntaxError(String),
+    /// 所有分支都失败时保留解析得最远的错误
+    fn or(self, other: Self) -> Self {
+        if other.input.len() < self.input.len() {
+            other
+        } else {
+            self
+        }
+    }
 }
 
+type PResult<'a, T> = IResult<&'a str, T, SyntaxError<'a>>;
+
 /// 条件表达式解析器主入口
 pub fn parse_condition(input: &str) -> Result<ConditionExpression, ParseError> {
-    match condition_expression(input.trim()) {
+    let error = |remaining: &str, kind: Option<ParseErrorKind>| {
+        let remaining = remaining.trim_start();
+        let offset = input.len() - remaining.len();
+        ParseError {
+            input: input.to_string(),
+            column: input[..offset].chars().count() + 1,
+            kind: kind.unwrap_or_else(|| unexpected(remaining)),
+        }
+    };
+
+    if input.trim().is_empty() {
+        return Err(ParseError {
+            input: input.to_string(),
+            column: 1,
+            kind: ParseErrorKind::Empty,
+        });
+    }
+
+    match condition_expression(input) {
         Ok((remaining, expr)) => {
-            if !remaining.trim().is_empty() {
-                Err(ParseError::SyntaxError(format!(
-                    "表达式解析完成后发现意外的内容: '{}'",
-                    remaining.trim()
-                )))
-            } else {
+            if remaining.trim().is_empty() {
                 Ok(expr)
+            } else {
+                Err(error(remaining, None))
             }
         }
-        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(ParseError::SyntaxError(
-            format!("语法错误: 无法解析表达式 '{}' (在位置附近: '{}')", input, e.input),
-        )),
-        Err(nom::Err::Incomplete(_)) => {
-            Err(ParseError::SyntaxError("表达式不完整，可能缺少结束符号".to_string()))
-        }
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(error(e.input, e.kind)),
+        Err(nom::Err::Incomplete(_)) => Err(error("", Some(ParseErrorKind::UnexpectedEnd))),
+    }
+}
+
+/// 出错位置的内容: 标识符整体或单个字符
+fn unexpected(rest: &str) -> ParseErrorKind {
+    let word: String = rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+    if !word.is_empty() {
+        return ParseErrorKind::UnexpectedToken(word);
+    }
+    match rest.chars().next() {
+        Some(c) => ParseErrorKind::UnexpectedToken(c.to_string()),
+        None => ParseErrorKind::UnexpectedEnd,
+    }
+}
+
+/// 直接报错, 不再尝试其他分支
+fn failure<T>(input: &str, kind: ParseErrorKind) -> PResult<'_, T> {
+    Err(nom::Err::Failure(SyntaxError { input, kind: Some(kind) }))
+}
+
+/// 必须出现的闭合符号
+fn closing<'a>(c: char) -> impl FnMut(&'a str) -> PResult<'a, char> {
+    move |input| match char::<_, SyntaxError>(c)(input) {
+        Ok(result) => Ok(result),
+        Err(_) => failure(input, ParseErrorKind::Expected(c)),
     }
 }
 
 /// 解析完整的条件表达式
-fn condition_expression(input: &str) -> IResult<&str, ConditionExpression> {
+fn condition_expression(input: &str) -> PResult<'_, ConditionExpression> {
     logical_or_expression(input)
 }
 
 /// 解析逻辑或表达式 (优先级最低)
-fn logical_or_expression(input: &str) -> IResult<&str, ConditionExpression> {
+fn logical_or_expression(input: &str) -> PResult<'_, ConditionExpression> {
     let (input, first) = logical_and_expression(input)?;
 
     let (input, rest) = nom::multi::many0(preceded(
         delimited(multispace0, tag("||"), multispace0),
-        logical_and_expression,
+        cut(logical_and_expression),
     ))(input)?;
 
     if rest.is_empty() {
@@ -72,12 +209,12 @@ fn logical_or_expression(input: &str) -> IResult<&str, ConditionExpression> {
 }
 
 /// 解析逻辑与表达式
-fn logical_and_expression(input: &str) -> IResult<&str, ConditionExpression> {
+fn logical_and_expression(input: &str) -> PResult<'_, ConditionExpression> {
     let (input, first) = comparison_expression(input)?;
 
     let (input, rest) = nom::multi::many0(preceded(
         delimited(multispace0, tag("&&"), multispace0),
-        comparison_expression,
+        cut(comparison_expression),
     ))(input)?;
 
     if rest.is_empty() {
@@ -96,45 +233,44 @@ fn logical_and_expression(input: &str) -> IResult<&str, ConditionExpression> {
 }
 
 /// 解析比较表达式 (优先级最高)
-fn comparison_expression(input: &str) -> IResult<&str, ConditionExpression> {
+fn comparison_expression(input: &str) -> PResult<'_, ConditionExpression> {
     let (input, left) = primary_expression(input)?;
-    let (input, _) = multispace0(input)?;
+    let (rest, _) = multispace0(input)?;
 
     // 尝试解析比较运算符
-    if let Ok((input, op)) = comparison_operator(input) {
-        let (input, _) = multispace0(input)?;
-        let (input, right) = primary_expression(input)?;
-
-        let comparison_op = match op.as_str() {
-            "==" => ComparisonOperator::Equal,
-            "!=" => ComparisonOperator::NotEqual,
-            ">" => ComparisonOperator::GreaterThan,
-            "<" => ComparisonOperator::LessThan,
-            ">=" => ComparisonOperator::GreaterThanOrEqual,
-            "<=" => ComparisonOperator::LessThanOrEqual,
-            _ => {
-                return Err(nom::Err::Error(nom::error::Error::new(
-                    input,
-                    nom::error::ErrorKind::Alt,
-                )))
-            }
-        };
-
-        Ok((
-            input,
-            ConditionExpression::Comparison {
-                left: Box::new(left),
-                operator: comparison_op,
-                right: Box::new(right),
-            },
-        ))
-    } else {
-        Ok((input, left))
-    }
+    let (rest, op) = opt(comparison_operator)(rest)?;
+    let Some(op) = op else {
+        return Ok((input, left));
+    };
+
+    let (input, _) = multispace0(rest)?;
+    let (input, right) = cut(primary_expression)(input)?;
+
+    let comparison_op = match op.as_str() {
+        "==" => ComparisonOperator::Equal,
+        "!=" => ComparisonOperator::NotEqual,
+        ">" => ComparisonOperator::GreaterThan,
+        "<" => ComparisonOperator::LessThan,
+        ">=" => ComparisonOperator::GreaterThanOrEqual,
+        "<=" => ComparisonOperator::LessThanOrEqual,
+        "in" => ComparisonOperator::In,
+        "not in" => ComparisonOperator::NotIn,
+        "matches" => ComparisonOperator::Matches,
+        _ => return failure(rest, ParseErrorKind::UnexpectedToken(op)),
+    };
+
+    Ok((
+        input,
+        ConditionExpression::Comparison {
+            left: Box::new(left),
+            operator: comparison_op,
+            right: Box::new(right),
+        },
+    ))
 }
 
 /// 解析主表达式 (变量、字面量、括号表达式、逻辑非)
-fn primary_expression(input: &str) -> IResult<&str, ConditionExpression> {
+fn primary_expression(input: &str) -> PResult<'_, ConditionExpression> {
     let (input, _) = multispace0(input)?;
 
     alt((
@@ -146,10 +282,10 @@ fn primary_expression(input: &str) -> IResult<&str, ConditionExpression> {
 }
 
 /// 解析逻辑非表达式
-fn unary_not_expression(input: &str) -> IResult<&str, ConditionExpression> {
+fn unary_not_expression(input: &str) -> PResult<'_, ConditionExpression> {
     let (input, _) = char('!')(input)?;
     let (input, _) = multispace0(input)?;
-    let (input, expr) = atom_expression(input)?;
+    let (input, expr) = cut(atom_expression)(input)?;
 
     Ok((
         input,
@@ -161,31 +297,83 @@ fn unary_not_expression(input: &str) -> IResult<&str, ConditionExpression> {
     ))
 }
 
-/// 解析原子表达式 (变量、字面量、括号表达式)
-fn atom_expression(input: &str) -> IResult<&str, ConditionExpression> {
+/// 解析原子表达式 (变量、函数调用、列表、字面量、括号表达式)
+fn atom_expression(input: &str) -> PResult<'_, ConditionExpression> {
     let (input, _) = multispace0(input)?;
 
     alt((
         // 括号表达式
-        delimited(char('('), delimited(multispace0, condition_expression, multispace0), char(')')),
+        delimited(
+            char('('),
+            delimited(multispace0, condition_expression, multispace0),
+            closing(')'),
+        ),
         // 变量
         map(variable, |var| ConditionExpression::Variable { name: var }),
+        // 函数调用
+        function_call,
+        // 列表字面量
+        map(list_literal, |items| ConditionExpression::List { items }),
         // 字面量
         map(literal_value, |val| ConditionExpression::Literal { value: val }),
     ))(input)
 }
 
-/// 解析变量 ${VAR}
-fn variable(input: &str) -> IResult<&str, String> {
+/// 解析函数调用 name(arg, ...), 函数名未知或参数个数不符时解析失败
+fn function_call(input: &str) -> PResult<'_, ConditionExpression> {
+    let start = input;
+    let (input, name) = recognize(pair(alpha1, alphanumeric0))(input)?;
+    // 不是函数调用时从起始位置回溯, 由其他分支报告错误
+    let Ok((input, _)) = preceded(multispace0, char::<_, SyntaxError>('('))(input) else {
+        return Err(nom::Err::Error(SyntaxError { input: start, kind: None }));
+    };
+    let (input, args) = terminated(
+        separated_list0(char(','), delimited(multispace0, condition_expression, multispace0)),
+        preceded(multispace0, closing(')')),
+    )(input)?;
+
+    let Some(function) = ConditionFunction::from_name(name) else {
+        return failure(start, ParseErrorKind::UnknownFunction(name.to_string()));
+    };
+    if function.arity() != args.len() {
+        return failure(
+            start,
+            ParseErrorKind::ArgumentCount {
+                function,
+                expected: function.arity(),
+                found: args.len(),
+            },
+        );
+    }
+
+    Ok((input, ConditionExpression::Function { function, args }))
+}
+
+/// 解析列表字面量 [a, b, ...]
+fn list_literal(input: &str) -> PResult<'_, Vec<ConditionExpression>> {
     delimited(
-        tag("${"),
-        map(take_while1(|c: char| c.is_alphanumeric() || c == '_'), |s: &str| s.to_string()),
-        char('}'),
+        char('['),
+        separated_list0(char(','), delimited(multispace0, condition_expression, multispace0)),
+        preceded(multispace0, closing(']')),
     )(input)
 }
 
+/// 解析变量 ${VAR}, 支持 `steps.<name>.status` 形式的点分路径
+fn variable(input: &str) -> PResult<'_, String> {
+    let (input, _) = tag("${")(input)?;
+    let name = take_while1::<_, _, SyntaxError>(|c: char| {
+        c.is_alphanumeric() || c == '_' || c == '.' || c == '-'
+    })(input);
+    let Ok((input, name)) = name else {
+        return failure(input, unexpected(input));
+    };
+    let (input, _) = closing('}')(input)?;
+
+    Ok((input, name.to_string()))
+}
+
 /// 解析字面量值
-fn literal_value(input: &str) -> IResult<&str, ConditionValue> {
+fn literal_value(input: &str) -> PResult<'_, ConditionValue> {
     alt((
         map(boolean_literal, ConditionValue::Boolean),
         map(number_literal, ConditionValue::Number),
@@ -194,29 +382,29 @@ fn literal_value(input: &str) -> IResult<&str, ConditionValue> {
 }
 
 /// 解析布尔字面量
-fn boolean_literal(input: &str) -> IResult<&str, bool> {
+fn boolean_literal(input: &str) -> PResult<'_, bool> {
     alt((map(tag("true"), |_| true), map(tag("false"), |_| false)))(input)
 }
 
 /// 解析数字字面量
-fn number_literal(input: &str) -> IResult<&str, f64> {
+fn number_literal(input: &str) -> PResult<'_, f64> {
     map(recognize(tuple((opt(char('-')), digit1, opt(tuple((char('.'), digit1)))))), |s: &str| {
         s.parse().unwrap_or(0.0)
     })(input)
 }
 
-/// 解析字符串字面量
-fn string_literal(input: &str) -> IResult<&str, String> {
-    alt((
-        // 双引号字符串
-        delimited(char('"'), map(take_while(|c| c != '"'), |s: &str| s.to_string()), char('"')),
-        // 单引号字符串
-        delimited(char('\''), map(take_while(|c| c != '\''), |s: &str| s.to_string()), char('\'')),
-    ))(input)
+/// 解析字符串字面量 (单引号或双引号)
+fn string_literal(input: &str) -> PResult<'_, String> {
+    let (rest, quote) = alt((char('"'), char('\'')))(input)?;
+    let (rest, value) = take_while(|c| c != quote)(rest)?;
+    match char::<_, SyntaxError>(quote)(rest) {
+        Ok((rest, _)) => Ok((rest, value.to_string())),
+        Err(_) => failure(input, ParseErrorKind::UnterminatedString),
+    }
 }
 
 /// 解析比较运算符
-fn comparison_operator(input: &str) -> IResult<&str, String> {
+fn comparison_operator(input: &str) -> PResult<'_, String> {
     alt((
         map(tag("=="), |s: &str| s.to_string()),
         map(tag("!="), |s: &str| s.to_string()),
@@ -224,5 +412,13 @@ fn comparison_operator(input: &str) -> IResult<&str, String> {
         map(tag("<="), |s: &str| s.to_string()),
         map(tag(">"), |s: &str| s.to_string()),
         map(tag("<"), |s: &str| s.to_string()),
+        map(keyword("in"), |_| "in".to_string()),
+        map(tuple((keyword("not"), multispace1, keyword("in"))), |_| "not in".to_string()),
+        map(keyword("matches"), |_| "matches".to_string()),
     ))(input)
 }
+
+/// 关键字运算符, 其后不能紧跟标识符字符
+fn keyword<'a>(word: &'static str) -> impl FnMut(&'a str) -> PResult<'a, &'a str> {
+    terminated(tag(word), not(take_while1(|c: char| c.is_alphanumeric() || c == '_')))
+}
@@ -0,0 +1,313 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Environment diagnostics for `confkit doctor`
+
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+
+use crate::engine::ConfKitEngine;
+use crate::infra::config::ConfKitConfigLoader;
+use crate::infra::db::TaskDb;
+use crate::shared::constants::{
+    HOST_ARTIFACTS_ROOT_DIR, HOST_CACHE_DIR, HOST_LOG_DIR, HOST_TEMP_DIR, HOST_WORKSPACE_DIR,
+    TASK_DB_FILE,
+};
+use crate::types::config::{ContainerStatus, Engine};
+
+/// 可用磁盘空间低于该值时报错 (1 GiB)
+const DISK_FAIL_BYTES: u64 = 1024 * 1024 * 1024;
+/// 可用磁盘空间低于该值时警告 (5 GiB)
+const DISK_WARN_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+
+/// 检查结果状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Pass => write!(f, "pass"),
+            CheckStatus::Warn => write!(f, "warn"),
+            CheckStatus::Fail => write!(f, "fail"),
+        }
+    }
+}
+
+/// 单项检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl DoctorCheck {
+    pub fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self { name: name.into(), status: CheckStatus::Pass, detail: detail.into() }
+    }
+
+    pub fn warn(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self { name: name.into(), status: CheckStatus::Warn, detail: detail.into() }
+    }
+
+    pub fn fail(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self { name: name.into(), status: CheckStatus::Fail, detail: detail.into() }
+    }
+}
+
+/// 诊断报告
+#[derive(Debug, Default, Serialize)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    pub fn push(&mut self, check: DoctorCheck) {
+        self.checks.push(check);
+    }
+
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|check| check.status == status).count()
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.count(CheckStatus::Fail) > 0
+    }
+}
+
+pub struct Doctor;
+
+impl Doctor {
+    /// 执行全部检查
+    ///
+    /// 不依赖启动流程中的引擎检测, 配置或引擎不可用时跳过相关检查。
+    pub async fn run() -> DoctorReport {
+        let mut report = DoctorReport::default();
+
+        report.push(Self::check_command("git", "git", &["--version"]));
+        report.push(Self::check_disk_space(Path::new(".")));
+
+        if !ConfKitConfigLoader::is_config_file_exists().await {
+            report.push(DoctorCheck::fail(
+                "config",
+                "no .confkit.yml/.toml/.json in current directory (run `confkit init`)",
+            ));
+            return report;
+        }
+        if let Err(e) = ConfKitConfigLoader::set_config().await {
+            report.push(DoctorCheck::fail("config", e.to_string()));
+            return report;
+        }
+        let path = ConfKitConfigLoader::config_file_path().unwrap_or_default();
+        report.push(DoctorCheck::pass("config", format!("{path} loaded")));
+
+        for check in Self::check_volumes(Path::new(".")) {
+            report.push(check);
+        }
+        report.push(Self::check_task_db(&Path::new(HOST_LOG_DIR).join(TASK_DB_FILE)));
+
+        let config = ConfKitConfigLoader::get_config();
+        let engine = config.engine;
+        if engine == Engine::None {
+            report.push(DoctorCheck::pass(
+                "engine",
+                "none (host-only mode, container steps need fallback: host)",
+            ));
+            return report;
+        }
+        let binary = engine.to_string();
+
+        let engine_check = Self::check_command("engine", &binary, &["--version"]);
+        let engine_installed = engine_check.status == CheckStatus::Pass;
+        report.push(engine_check);
+        if let Err(e) = ConfKitEngine::set_mode(&engine, config.engine_mode) {
+            report.push(DoctorCheck::fail("engine mode", e.to_string()));
+            return report;
+        }
+        if !engine_installed {
+            report.push(DoctorCheck::warn("engine daemon", "skipped: engine not installed"));
+            return report;
+        }
+        // api 模式下 socket 不存在等错误, 与启动时 set_engine 的报错一致
+        if let Err(e) = ConfKitEngine::set_engine(engine).await {
+            report.push(DoctorCheck::fail("engine daemon", e.to_string()));
+            report.push(DoctorCheck::warn("builders", "skipped: engine daemon not reachable"));
+            report.push(DoctorCheck::warn("images", "skipped: engine daemon not reachable"));
+            return report;
+        }
+
+        report.push(Self::check_command("compose", &binary, &["compose", "version"]));
+
+        if let Err(e) = ConfKitEngine::ensure_running().await {
+            report.push(DoctorCheck::fail("engine daemon", e.to_string()));
+            report.push(DoctorCheck::warn("builders", "skipped: engine daemon not reachable"));
+            report.push(DoctorCheck::warn("images", "skipped: engine daemon not reachable"));
+            return report;
+        }
+        report.push(DoctorCheck::pass("engine daemon", format!("{binary} daemon is reachable")));
+
+        for check in Self::check_builders().await {
+            report.push(check);
+        }
+        for check in Self::check_images().await {
+            report.push(check);
+        }
+
+        report
+    }
+
+    /// 检查命令是否可用, 通过时以输出首行作为详情
+    pub fn check_command(name: &str, program: &str, args: &[&str]) -> DoctorCheck {
+        match Command::new(program).args(args).output() {
+            Ok(output) if output.status.success() => {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let version = stdout.lines().next().unwrap_or_default().trim().to_string();
+                DoctorCheck::pass(name, version)
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let message = stderr.lines().next().unwrap_or_default().trim();
+                DoctorCheck::fail(
+                    name,
+                    format!("`{} {}` failed: {}", program, args.join(" "), message),
+                )
+            }
+            Err(_) => DoctorCheck::fail(name, format!("`{program}` not found in PATH")),
+        }
+    }
+
+    /// 检查 volumes 目录是否可写
+    pub fn check_volumes(root: &Path) -> Vec<DoctorCheck> {
+        let dirs = [
+            HOST_WORKSPACE_DIR,
+            HOST_ARTIFACTS_ROOT_DIR,
+            HOST_LOG_DIR,
+            HOST_CACHE_DIR,
+            HOST_TEMP_DIR,
+        ];
+
+        dirs.iter()
+            .map(|dir| {
+                let path = root.join(dir);
+                if !path.is_dir() {
+                    return DoctorCheck::warn(*dir, "missing (created on next run)");
+                }
+
+                let probe = path.join(".confkit-doctor");
+                match std::fs::write(&probe, b"") {
+                    Ok(_) => {
+                        let _ = std::fs::remove_file(&probe);
+                        DoctorCheck::pass(*dir, "writable")
+                    }
+                    Err(e) => DoctorCheck::fail(*dir, format!("not writable: {e}")),
+                }
+            })
+            .collect()
+    }
+
+    /// 检查任务数据库完整性
+    pub fn check_task_db(path: &Path) -> DoctorCheck {
+        if !path.exists() {
+            return DoctorCheck::warn(TASK_DB_FILE, "not created yet");
+        }
+
+        match TaskDb::integrity_check(path) {
+            Ok(result) if result == "ok" => DoctorCheck::pass(TASK_DB_FILE, "integrity ok"),
+            Ok(result) => DoctorCheck::fail(TASK_DB_FILE, format!("integrity check: {result}")),
+            Err(e) => DoctorCheck::fail(TASK_DB_FILE, format!("cannot open: {e}")),
+        }
+    }
+
+    /// 检查可用磁盘空间
+    pub fn check_disk_space(path: &Path) -> DoctorCheck {
+        let output = Command::new("df").arg("-Pk").arg(path).output();
+        let available = output
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| parse_df_available(&String::from_utf8_lossy(&output.stdout)));
+
+        match available {
+            Some(bytes) => {
+                let detail = format!("{:.1} GiB available", bytes as f64 / 1024f64.powi(3));
+                if bytes < DISK_FAIL_BYTES {
+                    DoctorCheck::fail("disk space", detail)
+                } else if bytes < DISK_WARN_BYTES {
+                    DoctorCheck::warn("disk space", detail)
+                } else {
+                    DoctorCheck::pass("disk space", detail)
+                }
+            }
+            None => DoctorCheck::warn("disk space", "unable to determine available space"),
+        }
+    }
+
+    /// 检查 compose 中的构建器容器是否存在且运行中
+    async fn check_builders() -> Vec<DoctorCheck> {
+        let mut services = match ConfKitEngine::get_compose_services().await {
+            Ok(services) => services,
+            Err(e) => return vec![DoctorCheck::fail("builders", e.to_string())],
+        };
+        if services.is_empty() {
+            return vec![DoctorCheck::warn("builders", "no services in compose file")];
+        }
+        services.sort_by(|a, b| a.service_name.cmp(&b.service_name));
+
+        let mut checks = vec![];
+        for service in services {
+            let name = format!("builder {}", service.container_name);
+            let check = match ConfKitEngine::get_container_info(&service.container_name).await {
+                Ok(info) => match info.status {
+                    ContainerStatus::Up => DoctorCheck::pass(name, "up"),
+                    ContainerStatus::Unbuilt => DoctorCheck::fail(
+                        name,
+                        format!(
+                            "not created (confkit builder create -n {})",
+                            service.container_name
+                        ),
+                    ),
+                    status => DoctorCheck::warn(name, format!("{status}")),
+                },
+                Err(e) => DoctorCheck::fail(name, e.to_string()),
+            };
+            checks.push(check);
+        }
+        checks
+    }
+
+    /// 检查配置中的镜像是否已构建
+    async fn check_images() -> Vec<DoctorCheck> {
+        let images = ConfKitConfigLoader::get_config().images;
+        if images.is_empty() {
+            return vec![DoctorCheck::warn("images", "no images configured")];
+        }
+
+        let mut checks = vec![];
+        for image in images {
+            let name = format!("image {}:{}", image.name, image.tag);
+            let check = match ConfKitEngine::check_image_exists(&image.name, &image.tag).await {
+                Ok(true) => DoctorCheck::pass(name, "built"),
+                Ok(false) => DoctorCheck::fail(
+                    name,
+                    format!("not built (confkit image create -n {} -t {})", image.name, image.tag),
+                ),
+                Err(e) => DoctorCheck::fail(name, e.to_string()),
+            };
+            checks.push(check);
+        }
+        checks
+    }
+}
+
+/// 解析 `df -Pk` 输出中的可用空间 (字节)
+pub fn parse_df_available(output: &str) -> Option<u64> {
+    let line = output.lines().nth(1)?;
+    let available_kb: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available_kb * 1024)
+}
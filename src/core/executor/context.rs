@@ -7,10 +7,12 @@ use std::collections::HashMap;
 use tokio::process::Command;
 
 use crate::{
+    engine::ConfKitEngine,
     formatter::path::PathFormatter,
     infra::{
         config::ConfKitConfigLoader,
         git::{GitClient, GitInfo},
+        secret::{SecretReferences, SecretResolver},
     },
     shared::constants::{
         CONTAINER_ARTIFACTS_ROOT_DIR, CONTAINER_WORKSPACE_DIR, HOST_ARTIFACTS_ROOT_DIR,
@@ -19,6 +21,26 @@ use crate::{
     types::config::ConfKitProjectConfig,
 };
 
+use super::environment::{EnvironmentLayers, EnvironmentSource};
+
+/// 预览环境变量时使用的任务 ID 占位符
+const PREVIEW_TASK_ID: &str = "<task_id>";
+
+/// 项目密钥解析结果
+struct ProjectSecrets {
+    /// 以环境变量注入的密钥 (secrets)
+    variables: HashMap<String, String>,
+    /// 替换密钥引用后的项目环境变量 (environment)
+    references: SecretReferences,
+}
+
+impl ProjectSecrets {
+    /// 全部密钥值, 用于日志脱敏
+    fn values(&self) -> Vec<String> {
+        self.variables.values().chain(self.references.values.iter()).cloned().collect()
+    }
+}
+
 /// 执行上下文
 #[derive(Debug, Clone)]
 pub struct ExecutionContext {
@@ -29,12 +51,16 @@ pub struct ExecutionContext {
     pub environment: HashMap<String, String>,
     /// Git 信息
     pub git_info: Option<GitInfo>,
+    /// 密钥值, 用于日志脱敏
+    pub secret_values: Vec<String>,
     /// 是否清理工作空间
     pub clean_workspace: bool,
     /// 主机工作空间目录
     pub host_workspace_dir: String,
     /// 容器工作空间目录
     pub container_workspace_dir: String,
+    /// 未配置容器引擎 (engine: none), 步骤只能在宿主机执行
+    pub host_only: bool,
     // /// 主机产物目录
     // pub host_artifacts_dir: String,
     // /// 容器产物目录
@@ -50,28 +76,25 @@ impl ExecutionContext {
         project_config: &ConfKitProjectConfig,
         environment_from_args: HashMap<String, String>,
     ) -> Result<Self> {
-        let task_path_identify = PathFormatter::get_task_path(&space_name, &project_name, &task_id);
-
-        let host_workspace_dir = format!("{HOST_WORKSPACE_DIR}/{task_path_identify}");
-        let container_workspace_dir = format!("{CONTAINER_WORKSPACE_DIR}/{task_path_identify}");
-        let host_artifacts_dir = HOST_ARTIFACTS_ROOT_DIR.to_string();
-        let container_artifacts_dir = CONTAINER_ARTIFACTS_ROOT_DIR.to_string();
-
         let git_client = GitClient::new(&space_name, &project_name).await?;
 
-        let environment = Self::build_environment(BuildEnvironmentParams {
+        let secrets = Self::resolve_secrets(&space_name, &project_name, project_config)?;
+        let secret_values = secrets.values();
+
+        let layers = Self::build_environment_layers(
+            &task_id,
+            &space_name,
+            &project_name,
+            project_config,
+            &git_client.git_info,
+            secrets,
             environment_from_args,
-            task_id: &task_id,
-            space_name: &space_name,
-            project_name: &project_name,
-            git_info: &git_client.git_info,
-            task_path_identify: &task_path_identify,
-            host_workspace_dir: &host_workspace_dir,
-            container_workspace_dir: &container_workspace_dir,
-            host_artifacts_dir: &host_artifacts_dir,
-            container_artifacts_dir: &container_artifacts_dir,
-        })
-        .await?;
+        );
+        let environment = layers.to_map();
+
+        let task_path_identify = PathFormatter::get_task_path(&space_name, &project_name, &task_id);
+        let host_workspace_dir = format!("{HOST_WORKSPACE_DIR}/{task_path_identify}");
+        let container_workspace_dir = format!("{CONTAINER_WORKSPACE_DIR}/{task_path_identify}");
 
         let clean_workspace = if let Some(cleaner) = &project_config.cleaner {
             cleaner.workspace.unwrap_or(true)
@@ -79,6 +102,8 @@ impl ExecutionContext {
             true
         };
 
+        let host_only = ConfKitEngine::is_host_only().await?;
+
         Ok(Self {
             task_id: task_id.clone(),
             space_name,
@@ -86,9 +111,11 @@ impl ExecutionContext {
             project_config: project_config.clone(),
             environment,
             git_info: git_client.git_info,
+            secret_values,
             clean_workspace,
             host_workspace_dir,
             container_workspace_dir,
+            host_only,
             // host_artifacts_dir,
             // container_artifacts_dir,
         })
@@ -108,87 +135,130 @@ impl ExecutionContext {
     pub fn resolve_working_dir(&self, working_dir: &str) -> String {
         self.resolve_variables(working_dir)
     }
-}
 
-/// 构建环境变量的参数结构体
-struct BuildEnvironmentParams<'a> {
-    environment_from_args: HashMap<String, String>,
-    task_id: &'a str,
-    space_name: &'a str,
-    project_name: &'a str,
-    git_info: &'a Option<GitInfo>,
-    task_path_identify: &'a str,
-    host_workspace_dir: &'a str,
-    container_workspace_dir: &'a str,
-    host_artifacts_dir: &'a str,
-    container_artifacts_dir: &'a str,
+    /// 将容器工作空间下的路径映射为宿主机路径, 用于在宿主机执行容器步骤
+    pub fn host_working_dir(&self, working_dir: &str) -> String {
+        match working_dir.strip_prefix(&self.container_workspace_dir) {
+            Some(rest) => format!("{}{rest}", self.host_workspace_dir),
+            None => working_dir.to_string(),
+        }
+    }
 }
 
 impl ExecutionContext {
-    /// 构建环境变量
-    async fn build_environment(
-        params: BuildEnvironmentParams<'_>,
-    ) -> Result<HashMap<String, String>> {
-        let mut env = HashMap::new();
-        let (_, env_from_conf, env_from_file) =
-            ConfKitConfigLoader::load_project_env(params.space_name, params.project_name).await?;
-
-        // 项目文件环境变量
-        for (key, value) in env_from_file {
-            env.insert(key, value);
-        }
-
-        // 基础环境变量
-        env.insert("TASK_ID".to_string(), params.task_id.to_string());
-        env.insert("PROJECT_NAME".to_string(), params.project_name.to_string());
-        env.insert("SPACE_NAME".to_string(), params.space_name.to_string());
+    /// 预览环境变量分层结果 (不创建任务), 用于 `confkit env show`
+    pub async fn preview_environment(
+        space_name: &str,
+        project_name: &str,
+        project_config: &ConfKitProjectConfig,
+        environment_from_args: HashMap<String, String>,
+    ) -> Result<EnvironmentLayers> {
+        let git_client = GitClient::new(space_name, project_name).await?;
+        let secrets = Self::resolve_secrets(space_name, project_name, project_config)?;
 
-        // 任务工作目录标识
-        env.insert("TASK_WORKSPACE_DIR".to_string(), params.task_path_identify.to_string());
+        Ok(Self::build_environment_layers(
+            PREVIEW_TASK_ID,
+            space_name,
+            project_name,
+            project_config,
+            &git_client.git_info,
+            secrets,
+            environment_from_args,
+        ))
+    }
 
-        // 目录环境变量
+    /// 解析项目密钥及项目环境变量中的 `${secret:NAME}` 引用
+    fn resolve_secrets(
+        space_name: &str,
+        project_name: &str,
+        project_config: &ConfKitProjectConfig,
+    ) -> Result<ProjectSecrets> {
+        let variables = match &project_config.secrets {
+            Some(secrets) => SecretResolver::resolve(space_name, project_name, secrets)?,
+            None => HashMap::new(),
+        };
+        let references = match &project_config.environment {
+            Some(environment) => {
+                SecretResolver::resolve_references(space_name, project_name, environment)?
+            }
+            None => SecretReferences::default(),
+        };
 
-        // 主机 volumes 根目录
-        env.insert("HOST_VOLUMES_DIR".to_string(), HOST_VOLUMES_DIR.to_string());
+        Ok(ProjectSecrets { variables, references })
+    }
 
-        // 主机工作空间目录
-        env.insert("HOST_WORKSPACE_DIR".to_string(), params.host_workspace_dir.to_string());
+    /// 构建环境变量
+    ///
+    /// 优先级从低到高: 环境变量文件 < 内置变量 < Git 变量 < 项目环境变量 < 密钥 < 参数环境变量
+    fn build_environment_layers(
+        task_id: &str,
+        space_name: &str,
+        project_name: &str,
+        project_config: &ConfKitProjectConfig,
+        git_info: &Option<GitInfo>,
+        secrets: ProjectSecrets,
+        environment_from_args: HashMap<String, String>,
+    ) -> EnvironmentLayers {
+        let mut layers = EnvironmentLayers::new();
 
-        // 主机产物目录
-        env.insert("HOST_ARTIFACTS_ROOT_DIR".to_string(), params.host_artifacts_dir.to_string());
+        let task_path_identify = PathFormatter::get_task_path(space_name, project_name, task_id);
+        let host_workspace_dir = format!("{HOST_WORKSPACE_DIR}/{task_path_identify}");
+        let container_workspace_dir = format!("{CONTAINER_WORKSPACE_DIR}/{task_path_identify}");
 
-        // 容器工作空间目录
-        env.insert(
-            "CONTAINER_WORKSPACE_DIR".to_string(),
-            params.container_workspace_dir.to_string(),
-        );
+        // 项目文件环境变量
+        for (path, vars) in ConfKitConfigLoader::load_environment_files(project_config) {
+            layers.extend(&vars, EnvironmentSource::File { path });
+        }
 
-        // 容器产物目录
-        env.insert(
-            "CONTAINER_ARTIFACTS_ROOT_DIR".to_string(),
-            params.container_artifacts_dir.to_string(),
-        );
+        // 基础环境变量
+        let builtin = [
+            ("TASK_ID", task_id),
+            ("PROJECT_NAME", project_name),
+            ("SPACE_NAME", space_name),
+            // 任务工作目录标识
+            ("TASK_WORKSPACE_DIR", task_path_identify.as_str()),
+            // 主机 volumes 根目录
+            ("HOST_VOLUMES_DIR", HOST_VOLUMES_DIR),
+            // 主机工作空间目录
+            ("HOST_WORKSPACE_DIR", host_workspace_dir.as_str()),
+            // 主机产物目录
+            ("HOST_ARTIFACTS_ROOT_DIR", HOST_ARTIFACTS_ROOT_DIR),
+            // 容器工作空间目录
+            ("CONTAINER_WORKSPACE_DIR", container_workspace_dir.as_str()),
+            // 容器产物目录
+            ("CONTAINER_ARTIFACTS_ROOT_DIR", CONTAINER_ARTIFACTS_ROOT_DIR),
+        ];
+        for (key, value) in builtin {
+            layers.insert(key, value, EnvironmentSource::Builtin);
+        }
 
         // Git 相关变量
-        if let Some(git_info) = params.git_info {
-            env.insert("GIT_REPO".to_string(), git_info.repo_url.clone());
-            env.insert("GIT_BRANCH".to_string(), git_info.branch.clone());
-            env.insert("GIT_HASH".to_string(), git_info.commit_hash.clone());
-            env.insert("GIT_HASH_SHORT".to_string(), git_info.commit_hash_short.clone());
-            env.insert("PROJECT_VERSION".to_string(), git_info.project_version.clone());
+        if let Some(git_info) = git_info {
+            let git = [
+                ("GIT_REPO", &git_info.repo_url),
+                ("GIT_BRANCH", &git_info.branch),
+                ("GIT_HASH", &git_info.commit_hash),
+                ("GIT_HASH_SHORT", &git_info.commit_hash_short),
+                ("PROJECT_VERSION", &git_info.project_version),
+            ];
+            for (key, value) in git {
+                layers.insert(key, value, EnvironmentSource::Git);
+            }
         }
 
         // 项目环境变量
-        for (key, value) in env_from_conf {
-            env.insert(key.clone(), value.clone());
+        layers.extend(&secrets.references.environment, EnvironmentSource::Project);
+        for key in &secrets.references.keys {
+            layers.mark_sensitive(key);
         }
 
+        // 密钥
+        layers.extend(&secrets.variables, EnvironmentSource::Secret);
+
         // 参数环境变量
-        for (key, value) in params.environment_from_args {
-            env.insert(key.clone(), value.clone());
-        }
+        layers.extend(&environment_from_args, EnvironmentSource::Args);
 
-        Ok(env)
+        layers
     }
 }
 
@@ -0,0 +1,142 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Environment layers with provenance tracking
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::utils::mask::{is_sensitive_key, mask_value};
+
+/// 环境变量来源层, 按优先级从低到高排列
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "layer", rename_all = "snake_case")]
+pub enum EnvironmentSource {
+    /// 环境变量文件 (environment_files)
+    File { path: String },
+    /// 内置变量 (TASK_ID, 目录变量等)
+    Builtin,
+    /// Git 变量 (source 配置)
+    Git,
+    /// 项目环境变量 (environment)
+    Project,
+    /// 密钥 (secrets)
+    Secret,
+    /// 参数环境变量 (-e / environment_from_args)
+    Args,
+}
+
+impl fmt::Display for EnvironmentSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvironmentSource::File { path } => write!(f, "file({path})"),
+            EnvironmentSource::Builtin => write!(f, "builtin"),
+            EnvironmentSource::Git => write!(f, "git"),
+            EnvironmentSource::Project => write!(f, "project"),
+            EnvironmentSource::Secret => write!(f, "secret"),
+            EnvironmentSource::Args => write!(f, "args"),
+        }
+    }
+}
+
+/// 被覆盖的变量值
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentOverride {
+    pub value: String,
+    pub source: EnvironmentSource,
+}
+
+/// 最终生效的变量及其来源
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentEntry {
+    pub key: String,
+    pub value: String,
+    pub source: EnvironmentSource,
+    /// 被当前值覆盖的历史值 (按覆盖顺序)
+    pub overridden: Vec<EnvironmentOverride>,
+    /// 是否含有密钥引用 (`${secret:NAME}`)
+    #[serde(skip)]
+    pub sensitive: bool,
+}
+
+/// 分层环境变量, 记录每个变量的来源与覆盖关系
+#[derive(Debug, Clone, Default)]
+pub struct EnvironmentLayers {
+    entries: BTreeMap<String, EnvironmentEntry>,
+}
+
+impl EnvironmentLayers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 写入变量, 已存在时记录被覆盖的值
+    pub fn insert(&mut self, key: &str, value: &str, source: EnvironmentSource) {
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                let previous = EnvironmentOverride {
+                    value: std::mem::replace(&mut entry.value, value.to_string()),
+                    source: std::mem::replace(&mut entry.source, source),
+                };
+                entry.overridden.push(previous);
+            }
+            None => {
+                self.entries.insert(
+                    key.to_string(),
+                    EnvironmentEntry {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                        source,
+                        overridden: vec![],
+                        sensitive: false,
+                    },
+                );
+            }
+        }
+    }
+
+    /// 批量写入同一来源的变量
+    pub fn extend(&mut self, vars: &HashMap<String, String>, source: EnvironmentSource) {
+        // 排序后写入, 保证同层变量的处理顺序稳定
+        let mut keys: Vec<&String> = vars.keys().collect();
+        keys.sort();
+        for key in keys {
+            self.insert(key, &vars[key], source.clone());
+        }
+    }
+
+    /// 标记变量含有密钥, 展示时脱敏
+    pub fn mark_sensitive(&mut self, key: &str) {
+        if let Some(entry) = self.entries.get_mut(key) {
+            entry.sensitive = true;
+        }
+    }
+
+    /// 按变量名排序的变量列表
+    pub fn entries(&self) -> impl Iterator<Item = &EnvironmentEntry> {
+        self.entries.values()
+    }
+
+    /// 最终生效的环境变量
+    pub fn to_map(&self) -> HashMap<String, String> {
+        self.entries.values().map(|entry| (entry.key.clone(), entry.value.clone())).collect()
+    }
+
+    /// 返回敏感值已脱敏的副本, 用于展示
+    ///
+    /// 变量名疑似敏感、含有密钥引用, 或任意一层来自密钥时, 所有层的值都会脱敏。
+    pub fn masked(&self) -> Self {
+        let mut entries = self.entries.clone();
+        for entry in entries.values_mut() {
+            let from_secret = entry.source == EnvironmentSource::Secret
+                || entry.overridden.iter().any(|o| o.source == EnvironmentSource::Secret);
+            if from_secret || entry.sensitive || is_sensitive_key(&entry.key) {
+                entry.value = mask_value(&entry.value);
+                for overridden in entry.overridden.iter_mut() {
+                    overridden.value = mask_value(&overridden.value);
+                }
+            }
+        }
+        Self { entries }
+    }
+}
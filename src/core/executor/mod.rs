@@ -4,6 +4,7 @@
 
 pub mod command_executor;
 pub mod context;
+pub mod environment;
 pub mod runner;
 pub mod step_executor;
 pub mod task;
@@ -25,11 +25,10 @@ impl Runner {
         environment_from_args: HashMap<String, String>,
     ) -> Result<Self> {
         // 获取项目配置
-        let project_config =
-            ConfKitConfigLoader::get_project_config(space_name, project_name).await?;
+        let project = ConfKitConfigLoader::get_indexed_project(space_name, project_name).await?;
 
-        let project_config = match project_config {
-            Some(config) => config,
+        let (project_path, project_config) = match project {
+            Some(project) => (project.path, project.config),
             None => {
                 tracing::error!("Project '{project_name}' not found in space '{space_name}'");
                 return Err(anyhow::anyhow!("Project not found"));
@@ -48,7 +47,11 @@ impl Runner {
             &project_config,
             environment_from_args,
         )
-        .await?;
+        .await
+        .map_err(|e| anyhow::anyhow!("{}: {}", project_path.display(), e))?;
+
+        // 日志中脱敏密钥值
+        task.logger().add_secrets(&context.secret_values);
 
         // 设置上下文和项目配置
         task.context = Some(context);
@@ -4,32 +4,42 @@
 
 use anyhow::Result;
 use chrono::Utc;
+use std::collections::HashMap;
 use std::time::Instant;
 
 use super::command_executor::CommandExecutor;
 use super::context::ExecutionContext;
 use super::types::{StepResult, StepStatus};
 use crate::core::condition::evaluator::ConditionEvaluator;
+use crate::core::condition::parser::ParseError;
+use crate::core::condition::{ConditionValue, MessageLang};
 use crate::infra::logger::TaskLogger;
-use crate::types::config::ConfKitStepConfig;
+use crate::types::config::{ConditionErrorPolicy, ConfKitStepConfig, StepFallback};
 
 /// 步骤执行器
 pub struct StepExecutor {
     context: ExecutionContext,
     task_logger: TaskLogger,
+    /// 项目级的条件出错处理方式, 步骤未配置时使用
+    on_condition_error: ConditionErrorPolicy,
 }
 
 impl StepExecutor {
-    pub fn new(context: ExecutionContext, task_logger: TaskLogger) -> Self {
-        Self { context, task_logger }
+    pub fn new(
+        context: ExecutionContext,
+        task_logger: TaskLogger,
+        on_condition_error: ConditionErrorPolicy,
+    ) -> Self {
+        Self { context, task_logger, on_condition_error }
     }
 
-    /// 执行单个步骤
+    /// 执行单个步骤, previous 为已执行步骤的结果, 供条件表达式引用
     pub async fn execute_step(
         &self,
         step: &ConfKitStepConfig,
         step_number: usize,
         total_steps: usize,
+        previous: &[StepResult],
     ) -> Result<StepResult> {
         let mut result = StepResult {
             name: step.name.clone(),
@@ -46,44 +56,97 @@ impl StepExecutor {
 
         // 检查步骤条件
         if let Some(condition) = &step.condition {
-            let evaluator = ConditionEvaluator::new(self.context.environment.clone());
-            match evaluator.evaluate_string(condition) {
+            let evaluator = ConditionEvaluator::new(self.context.environment.clone())
+                .with_values(Self::condition_values(previous));
+            let policy = step.on_condition_error.unwrap_or(self.on_condition_error);
+
+            let skip_reason = match evaluator.try_evaluate_string(condition) {
                 Ok(true) => {
                     self.task_logger.info(&format!(
                         "[Step {}/{}] Condition satisfied: {}",
                         step_number, total_steps, condition
                     ))?;
+                    None
                 }
-                Ok(false) => {
-                    result.status = StepStatus::Skipped;
-                    result.exit_code = Some(0);
-                    result.output = String::new();
-                    result.error = None;
-                    result.finished_at = Some(Utc::now());
-                    result.duration_ms = Some(start_time.elapsed().as_millis() as u64);
-
-                    // 跳过步骤并记录结果
-                    self.log_step_result(
-                        &result,
-                        step_number,
-                        total_steps,
-                        Some(&format!("condition {condition}")),
-                    )?;
-                    return Ok(result);
-                }
+                Ok(false) => Some(format!("condition {condition}")),
                 Err(e) => {
-                    self.task_logger.warn(&format!(
-                        "[Step {}/{}] Failed to evaluate condition '{}': {}. Executing step anyway.",
-                        step_number, total_steps, condition, e
-                    ))?;
+                    let error = Self::describe_condition_error(&e);
+                    match policy {
+                        ConditionErrorPolicy::Run => {
+                            self.task_logger.warn(&format!(
+                                "[Step {}/{}] Failed to evaluate condition '{}': {}. Executing step anyway.",
+                                step_number, total_steps, condition, error
+                            ))?;
+                            None
+                        }
+                        ConditionErrorPolicy::Skip => {
+                            self.task_logger.warn(&format!(
+                                "[Step {}/{}] Failed to evaluate condition '{}': {}",
+                                step_number, total_steps, condition, error
+                            ))?;
+                            Some(format!("condition error (on_condition_error: {policy})"))
+                        }
+                        ConditionErrorPolicy::Fail => {
+                            result.status = StepStatus::Failed;
+                            result.exit_code = Some(1);
+                            result.error = Some(format!(
+                                "Failed to evaluate condition '{condition}': {error}"
+                            ));
+                            result.finished_at = Some(Utc::now());
+                            result.duration_ms = Some(start_time.elapsed().as_millis() as u64);
+                            self.log_step_result(&result, step_number, total_steps, None)?;
+                            return Ok(result);
+                        }
+                    }
                 }
+            };
+
+            if let Some(reason) = skip_reason {
+                result.status = StepStatus::Skipped;
+                result.exit_code = Some(0);
+                result.output = String::new();
+                result.error = None;
+                result.finished_at = Some(Utc::now());
+                result.duration_ms = Some(start_time.elapsed().as_millis() as u64);
+
+                // 跳过步骤并记录结果
+                self.log_step_result(&result, step_number, total_steps, Some(&reason))?;
+                return Ok(result);
             }
         }
 
+        // engine: none 时, 容器步骤按 fallback 在宿主机执行, 未配置则失败
+        let container = match (&step.container, self.context.host_only) {
+            (Some(container), true) => match step.fallback {
+                Some(StepFallback::Host) => {
+                    self.task_logger.warn(&format!(
+                        "[Step {}/{}] No container engine (engine: none), running on host instead of container '{}'",
+                        step_number, total_steps, container
+                    ))?;
+                    None
+                }
+                None => {
+                    result.status = StepStatus::Failed;
+                    result.exit_code = Some(1);
+                    result.error = Some(format!(
+                        "Step requires container '{container}' but no container engine is configured (engine: none). Set `fallback: host` to run it on the host"
+                    ));
+                    result.finished_at = Some(Utc::now());
+                    result.duration_ms = Some(start_time.elapsed().as_millis() as u64);
+                    self.log_step_result(&result, step_number, total_steps, None)?;
+                    return Ok(result);
+                }
+            },
+            (container, _) => container.as_deref(),
+        };
+
         let working_dir = match &step.working_dir {
+            Some(working_dir) if step.container.is_some() && container.is_none() => {
+                self.context.host_working_dir(&self.context.resolve_working_dir(working_dir))
+            }
             Some(working_dir) => self.context.resolve_working_dir(working_dir),
             None => {
-                if step.container.is_some() {
+                if container.is_some() {
                     self.context.container_workspace_dir.clone()
                 } else {
                     self.context.host_workspace_dir.clone()
@@ -92,7 +155,7 @@ impl StepExecutor {
         };
 
         // 记录步骤详情
-        self.log_step_details(step_number, total_steps, step, &working_dir).await?;
+        self.log_step_details(step_number, total_steps, step, container, &working_dir).await?;
 
         // commands 长度为 0 时，直接跳过
         if step.commands.is_empty() {
@@ -105,7 +168,7 @@ impl StepExecutor {
         }
         // 执行命令，在 step 级别应用超时
         let execution_future = async {
-            if let Some(container) = &step.container {
+            if let Some(container) = container {
                 CommandExecutor::execute_in_container(
                     &self.context,
                     container,
@@ -178,17 +241,64 @@ impl StepExecutor {
         Ok(result)
     }
 
+    /// 已执行步骤的结果转换为条件变量
+    ///
+    /// - `steps.<name>.status`: success / failed / skipped
+    /// - `steps.<name>.exit_code`, `steps.<name>.duration_ms`
+    /// - `task.failed`: 是否已有步骤失败, `task.failed_steps`: 失败步骤数
+    ///
+    /// 名称中字母、数字、`_`、`-` 以外的字符可写作 `_`, 如 `steps.Run_tests.status`。
+    pub fn condition_values(previous: &[StepResult]) -> HashMap<String, ConditionValue> {
+        let mut values = HashMap::new();
+
+        for result in previous {
+            let mut fields = vec![("status", ConditionValue::String(result.status.to_string()))];
+            if let Some(exit_code) = result.exit_code {
+                fields.push(("exit_code", ConditionValue::Number(exit_code as f64)));
+            }
+            if let Some(duration_ms) = result.duration_ms {
+                fields.push(("duration_ms", ConditionValue::Number(duration_ms as f64)));
+            }
+
+            let key = Self::step_key(&result.name);
+            for (field, value) in fields {
+                values.insert(format!("steps.{}.{field}", result.name), value.clone());
+                values.insert(format!("steps.{key}.{field}"), value);
+            }
+        }
+
+        let failed = previous.iter().filter(|r| r.status == StepStatus::Failed).count();
+        values.insert("task.failed".to_string(), ConditionValue::Boolean(failed > 0));
+        values.insert("task.failed_steps".to_string(), ConditionValue::Number(failed as f64));
+        values
+    }
+
+    /// 条件出错的描述, 解析错误附带原表达式和出错位置
+    fn describe_condition_error(error: &anyhow::Error) -> String {
+        match error.downcast_ref::<ParseError>() {
+            Some(parse_error) => parse_error.render(MessageLang::from_env()),
+            None => error.to_string(),
+        }
+    }
+
+    /// 条件表达式中引用步骤的名称, 字母、数字、`_`、`-` 以外的字符替换为 `_`
+    pub fn step_key(name: &str) -> String {
+        name.chars()
+            .map(|c| if c.is_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+            .collect()
+    }
+
     /// 记录步骤详情
     async fn log_step_details(
         &self,
         step_number: usize,
         total_steps: usize,
         step: &ConfKitStepConfig,
+        container: Option<&str>,
         working_dir: &str,
     ) -> Result<()> {
         self.task_logger.info(&format!("[Step {}/{}] Details:", step_number, total_steps))?;
-        self.task_logger
-            .info(&format!("  - Container: {}", step.container.as_deref().unwrap_or("Host")))?;
+        self.task_logger.info(&format!("  - Container: {}", container.unwrap_or("Host")))?;
         self.task_logger.info(&format!("  - Working Directory: {working_dir}"))?;
         self.task_logger.info(&format!("  - Command Count: {}", step.commands.len()))?;
 
@@ -155,7 +155,8 @@ impl Task {
         ))?;
 
         // 创建步骤执行器
-        let executor = StepExecutor::new(context.clone(), self.logger());
+        let on_condition_error = project_config.on_condition_error.unwrap_or_default();
+        let executor = StepExecutor::new(context.clone(), self.logger(), on_condition_error);
 
         for (index, step) in project_config.steps.iter().enumerate() {
             let step_number = index + 1;
@@ -163,7 +164,8 @@ impl Task {
 
             self.info(&format!("[Step {}/{}] Executing: {}", step_number, total_steps, step.name))?;
 
-            let result = executor.execute_step(step, step_number, total_steps).await?;
+            let result =
+                executor.execute_step(step, step_number, total_steps, &self.step_results).await?;
 
             self.step_results.push(result.clone());
             self.update_metadata(db)?;
@@ -28,6 +28,17 @@ pub enum StepStatus {
     Skipped,
 }
 
+impl std::fmt::Display for StepStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StepStatus::Running => write!(f, "running"),
+            StepStatus::Success => write!(f, "success"),
+            StepStatus::Failed => write!(f, "failed"),
+            StepStatus::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
 /// 任务元数据
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskMetadata {
@@ -0,0 +1,138 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Project scaffolding for `confkit init`
+
+mod templates;
+
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::shared::constants::{
+    CONFKIT_CONFIG_FILE, HOST_ARTIFACTS_ROOT_DIR, HOST_CACHE_DIR, HOST_LOG_DIR, HOST_TEMP_DIR,
+    HOST_WORKSPACE_DIR,
+};
+use crate::types::config::Engine;
+
+/// 生成的 compose 文件
+pub const INIT_COMPOSE_FILE: &str = "docker-compose.yml";
+/// 镜像构建上下文目录
+pub const INIT_CONTEXT_DIR: &str = "volumes/context";
+
+/// 初始化选项
+#[derive(Debug, Clone)]
+pub struct InitOptions {
+    pub engine: Engine,
+    pub space_name: String,
+    pub project_name: String,
+    pub git_repo: String,
+    pub git_branch: String,
+    /// 构建器名称 (镜像名 / 容器名 / compose 服务名)
+    pub builder_name: String,
+    pub base_image: String,
+    pub base_image_tag: String,
+}
+
+impl Default for InitOptions {
+    fn default() -> Self {
+        Self {
+            engine: Engine::Docker,
+            space_name: "hello".to_string(),
+            project_name: "hello-app".to_string(),
+            git_repo: "https://github.com/example/hello-app.git".to_string(),
+            git_branch: "main".to_string(),
+            builder_name: "hello-builder".to_string(),
+            base_image: "alpine".to_string(),
+            base_image_tag: "3.18".to_string(),
+        }
+    }
+}
+
+impl InitOptions {
+    /// 构建器 Dockerfile 路径
+    pub fn dockerfile_path(&self) -> String {
+        format!(".confkit/images/Dockerfile.{}.{}", self.base_image, self.base_image_tag)
+    }
+
+    /// space 目录
+    pub fn space_path(&self) -> String {
+        format!(".confkit/spaces/{}", self.space_name)
+    }
+}
+
+pub struct ProjectScaffold;
+
+impl ProjectScaffold {
+    /// 检测宿主机已安装的容器引擎, 按 docker, podman, nerdctl 的顺序
+    pub fn detect_engines() -> Vec<Engine> {
+        [Engine::Docker, Engine::Podman, Engine::Nerdctl]
+            .into_iter()
+            .filter(|engine| {
+                Command::new(engine.to_string())
+                    .arg("--version")
+                    .output()
+                    .map(|output| output.status.success())
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// 待生成的文件 (相对路径, 内容)
+    pub fn files(options: &InitOptions) -> Vec<(String, String)> {
+        vec![
+            (CONFKIT_CONFIG_FILE.to_string(), templates::confkit_config(options)),
+            (INIT_COMPOSE_FILE.to_string(), templates::compose(options)),
+            (options.dockerfile_path(), templates::dockerfile(options)),
+            (
+                format!("{}/{}.yml", options.space_path(), options.project_name),
+                templates::project(options),
+            ),
+        ]
+    }
+
+    /// 在 root 目录下生成项目结构, 返回写入的文件
+    ///
+    /// 任一文件已存在且未指定 force 时不写入任何文件。
+    pub fn generate(root: &Path, options: &InitOptions, force: bool) -> Result<Vec<PathBuf>> {
+        let files = Self::files(options);
+
+        if !force {
+            let existing: Vec<&str> = files
+                .iter()
+                .filter(|(path, _)| root.join(path).exists())
+                .map(|(path, _)| path.as_str())
+                .collect();
+            if !existing.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Refusing to overwrite existing files (use --force): {}",
+                    existing.join(", ")
+                ));
+            }
+        }
+
+        let dirs = [
+            HOST_WORKSPACE_DIR,
+            HOST_ARTIFACTS_ROOT_DIR,
+            HOST_LOG_DIR,
+            HOST_CACHE_DIR,
+            HOST_TEMP_DIR,
+            INIT_CONTEXT_DIR,
+        ];
+        for dir in dirs {
+            fs::create_dir_all(root.join(dir))?;
+        }
+
+        let mut written = vec![];
+        for (path, content) in files {
+            let path = root.join(path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, content)?;
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+}
@@ -0,0 +1,125 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: File templates for `confkit init`
+
+use super::{InitOptions, INIT_COMPOSE_FILE, INIT_CONTEXT_DIR};
+use crate::shared::constants::{
+    CONTAINER_ARTIFACTS_ROOT_DIR, CONTAINER_WORKSPACE_DIR, HOST_ARTIFACTS_ROOT_DIR,
+    HOST_WORKSPACE_DIR,
+};
+
+pub fn confkit_config(options: &InitOptions) -> String {
+    let engine = &options.engine;
+
+    format!(
+        r#"# yaml-language-server: $schema=https://raw.githubusercontent.com/confkit/engine/main/schemas/confkit.schema.json
+version: 1.0.0
+
+# Container engine: docker/podman/nerdctl/none
+engine: {engine}
+
+engine_compose:
+  # Container group (default: confkit)
+  project: confkit
+  # Compose file of builder containers
+  file: ./{compose}
+
+# Space list
+spaces:
+  - name: {space}
+    description: "{space} space"
+    # Project execution config files
+    path: {space_path}
+
+# Image management list
+images:
+    # Target image name
+  - name: {builder}
+    # Base image (auto pull)
+    base_image: {base_image}
+    # Base image tag (shared by target image)
+    tag: "{tag}"
+    context: {context}
+    # Dockerfile path
+    engine_file: ./{dockerfile}
+"#,
+        compose = INIT_COMPOSE_FILE,
+        space = options.space_name,
+        space_path = options.space_path(),
+        builder = options.builder_name,
+        base_image = options.base_image,
+        tag = options.base_image_tag,
+        context = INIT_CONTEXT_DIR,
+        dockerfile = options.dockerfile_path(),
+    )
+}
+
+pub fn compose(options: &InitOptions) -> String {
+    format!(
+        r#"services:
+  {builder}:
+    image: {builder}:{tag}
+    container_name: {builder}
+    working_dir: {container_workspace}
+    volumes:
+      - ./{host_workspace}:{container_workspace}
+      - ./{host_artifacts}:{container_artifacts}
+    # Keep the builder running so steps can exec into it
+    command: ["tail", "-f", "/dev/null"]
+"#,
+        builder = options.builder_name,
+        tag = options.base_image_tag,
+        host_workspace = HOST_WORKSPACE_DIR,
+        container_workspace = CONTAINER_WORKSPACE_DIR,
+        host_artifacts = HOST_ARTIFACTS_ROOT_DIR,
+        container_artifacts = CONTAINER_ARTIFACTS_ROOT_DIR,
+    )
+}
+
+pub fn dockerfile(options: &InitOptions) -> String {
+    format!(
+        r#"FROM {base_image}:{tag}
+
+RUN apk add --no-cache bash git
+
+WORKDIR {container_workspace}
+"#,
+        base_image = options.base_image,
+        tag = options.base_image_tag,
+        container_workspace = CONTAINER_WORKSPACE_DIR,
+    )
+}
+
+pub fn project(options: &InitOptions) -> String {
+    format!(
+        r#"# yaml-language-server: $schema=https://raw.githubusercontent.com/confkit/engine/main/schemas/project.schema.json
+name: "{project}"
+description: "{project} project"
+
+source:
+  git_repo: "{git_repo}"
+  git_branch: "{git_branch}"
+
+environment:
+  APP_NAME: "{project}"
+
+steps:
+  - name: "Show Source"
+    container: "{builder}"
+    working_dir: "${{CONTAINER_WORKSPACE_DIR}}"
+    commands:
+      - "echo 'Building ${{APP_NAME}} (${{GIT_BRANCH}}@${{GIT_HASH_SHORT}})'"
+      - "ls -la"
+
+  - name: "Collect Artifacts"
+    working_dir: "${{HOST_WORKSPACE_DIR}}"
+    commands:
+      - "mkdir -p ../../artifacts/${{TASK_WORKSPACE_DIR}}"
+    continue_on_error: true
+"#,
+        project = options.project_name,
+        git_repo = options.git_repo,
+        git_branch = options.git_branch,
+        builder = options.builder_name,
+    )
+}
@@ -19,7 +19,7 @@ use anyhow::Result;
 use std::collections::HashMap;
 
 use crate::{
-    core::condition::evaluator::ConditionEvaluator,
+    core::condition::{evaluator::ConditionEvaluator, parser::ParseError, MessageLang},
     types::config::{ConfKitEnvironmentInteractiveConfig, ConfKitInteractiveType},
 };
 
@@ -43,11 +43,18 @@ pub async fn process_interactive_environments(
             let mut merged_env = env.clone();
             merged_env.extend(env_vars.clone());
 
+            // 条件无法解析时报错, 而不是按成立处理继续询问
             let evaluator = ConditionEvaluator::new(merged_env);
-            match evaluator.evaluate_string(condition) {
+            match evaluator.try_evaluate_string(condition) {
                 Ok(true) => {}
                 Ok(false) => continue,
-                Err(e) => return Err(e),
+                Err(e) => {
+                    let reason = match e.downcast_ref::<ParseError>() {
+                        Some(parse_error) => parse_error.render(MessageLang::from_env()),
+                        None => e.to_string(),
+                    };
+                    anyhow::bail!("Invalid condition for '{}': {}", config.name, reason);
+                }
             }
         }
 
@@ -5,6 +5,8 @@
 use anyhow::Result;
 use inquire::Select;
 
+use crate::engine::ConfKitEngine;
+
 use super::{
     menu::InteractiveMenu,
     ui::{InteractiveMainUI, InteractiveUI},
@@ -12,7 +14,7 @@ use super::{
 
 impl InteractiveMenu {
     pub async fn main(&mut self) -> Result<bool> {
-        let options = vec![
+        let mut options = vec![
             InteractiveMainUI::Run,
             InteractiveMainUI::Builder,
             InteractiveMainUI::Image,
@@ -21,6 +23,13 @@ impl InteractiveMenu {
             InteractiveMainUI::Quit,
         ];
 
+        // engine: none 时没有构建器与镜像可管理
+        if ConfKitEngine::is_host_only().await? {
+            options.retain(|option| {
+                !matches!(option, InteractiveMainUI::Builder | InteractiveMainUI::Image)
+            });
+        }
+
         let selection = Select::new("Please select an option:", options)
             .with_help_message("Use ↑↓ to navigate, Enter to confirm")
             .prompt();
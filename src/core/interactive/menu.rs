@@ -6,6 +6,7 @@ use anyhow::Result;
 use inquire::Select;
 
 use crate::core::interactive::ui::InteractiveYesNoUI;
+use crate::infra::config::ConfKitConfigLoader;
 
 use super::ui::InteractiveUI;
 
@@ -24,6 +25,10 @@ impl InteractiveMenu {
         loop {
             match &self.ui {
                 InteractiveUI::Main => {
+                    // 回到主菜单时重新加载项目配置, 使运行期间的修改生效
+                    if let Err(e) = ConfKitConfigLoader::reload_projects() {
+                        tracing::warn!("Failed to reload project configs: {}", e);
+                    }
                     if !self.main().await? {
                         break;
                     }
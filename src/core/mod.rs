@@ -5,6 +5,9 @@
 pub mod builder;
 pub mod clean;
 pub mod condition;
+pub mod doctor;
 pub mod executor;
+pub mod init;
 pub mod interactive;
 pub mod logger;
+pub mod validator;
@@ -0,0 +1,40 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Config file formats detected by extension
+
+use std::fmt;
+use std::path::Path;
+
+/// 配置文件格式, 按扩展名识别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFileFormat {
+    Yaml,
+    Toml,
+    Json,
+}
+
+impl ConfigFileFormat {
+    /// 支持的扩展名
+    pub const EXTENSIONS: &'static [&'static str] = &["yml", "yaml", "toml", "json"];
+
+    /// 根据扩展名识别格式, 不支持的扩展名返回 None
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "yml" | "yaml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ConfigFileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigFileFormat::Yaml => write!(f, "yaml"),
+            ConfigFileFormat::Toml => write!(f, "toml"),
+            ConfigFileFormat::Json => write!(f, "json"),
+        }
+    }
+}
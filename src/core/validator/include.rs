@@ -0,0 +1,241 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Resolve `include:` of .confkit.yml and merge included spaces/images
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{ConfigFileFormat, ConfigValidator, Diagnostic, YamlLocator};
+use crate::types::config::{
+    ConfKitConfig, ConfKitConfigInclude, ConfKitImageConfig, ConfKitSpaceConfig,
+};
+
+/// 配置项来源: 所在文件及其在该文件列表中的下标
+#[derive(Debug, Clone)]
+pub struct ConfigOrigin {
+    pub file: String,
+    pub index: usize,
+}
+
+/// 合并 include 后的主配置
+pub struct LoadedConfig {
+    /// 主配置文件路径
+    pub path: String,
+    pub config: ConfKitConfig,
+    /// 与 config.spaces 一一对应
+    pub space_origins: Vec<ConfigOrigin>,
+    /// 与 config.images 一一对应
+    pub image_origins: Vec<ConfigOrigin>,
+    locators: HashMap<String, YamlLocator>,
+}
+
+impl LoadedConfig {
+    /// 主配置文件中的位置
+    pub fn locate(&self, path: &[&str]) -> Option<(usize, usize)> {
+        self.locate_in(&self.path, path)
+    }
+
+    /// 第 index 个 space 的字段所在文件及位置
+    pub fn locate_space(&self, index: usize, field: &str) -> (&str, Option<(usize, usize)>) {
+        let origin = &self.space_origins[index];
+        (&origin.file, self.locate_in(&origin.file, &["spaces", &origin.index.to_string(), field]))
+    }
+
+    /// 第 index 个镜像的字段所在文件及位置
+    pub fn locate_image(&self, index: usize, field: &str) -> (&str, Option<(usize, usize)>) {
+        let origin = &self.image_origins[index];
+        (&origin.file, self.locate_in(&origin.file, &["images", &origin.index.to_string(), field]))
+    }
+
+    fn locate_in(&self, file: &str, path: &[&str]) -> Option<(usize, usize)> {
+        let path: Vec<String> = path.iter().map(|s| s.to_string()).collect();
+        self.locators.get(file)?.locate(&path)
+    }
+
+    /// 合并一个文件中的 spaces / images, 重复的 space 名称或镜像 name:tag 报错并跳过
+    fn merge(
+        &mut self,
+        file: &str,
+        spaces: Vec<ConfKitSpaceConfig>,
+        images: Vec<ConfKitImageConfig>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        for (index, space) in spaces.into_iter().enumerate() {
+            let first = self.config.spaces.iter().position(|s| s.name == space.name);
+            match first {
+                Some(first) if !space.name.is_empty() => {
+                    let (first_file, first_position) = self.locate_space(first, "name");
+                    let message = format!(
+                        "duplicate space name '{}' (first defined in {})",
+                        space.name,
+                        format_location(first_file, first_position)
+                    );
+                    let position = self.locate_in(file, &["spaces", &index.to_string(), "name"]);
+                    diagnostics.push(Diagnostic::error(file, position, message));
+                }
+                _ => {
+                    self.config.spaces.push(space);
+                    self.space_origins.push(ConfigOrigin { file: file.to_string(), index });
+                }
+            }
+        }
+
+        for (index, image) in images.into_iter().enumerate() {
+            let first =
+                self.config.images.iter().position(|i| i.name == image.name && i.tag == image.tag);
+            match first {
+                Some(first) if !image.name.is_empty() => {
+                    let (first_file, first_position) = self.locate_image(first, "name");
+                    let message = format!(
+                        "duplicate image '{}:{}' (first defined in {})",
+                        image.name,
+                        image.tag,
+                        format_location(first_file, first_position)
+                    );
+                    let position = self.locate_in(file, &["images", &index.to_string(), "name"]);
+                    diagnostics.push(Diagnostic::error(file, position, message));
+                }
+                _ => {
+                    self.config.images.push(image);
+                    self.image_origins.push(ConfigOrigin { file: file.to_string(), index });
+                }
+            }
+        }
+    }
+}
+
+impl ConfigValidator {
+    /// 加载主配置并合并 include 引入的文件
+    ///
+    /// 返回 (合并结果, 诊断); 主配置解析失败时结果为 None, 冲突以 error 级诊断返回。
+    pub fn load_config(path: &str) -> (Option<LoadedConfig>, Vec<Diagnostic>) {
+        let mut diagnostics = vec![];
+
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                diagnostics.push(Diagnostic::error(
+                    path,
+                    None,
+                    format!("failed to read file: {e}"),
+                ));
+                return (None, diagnostics);
+            }
+        };
+        let format = ConfigFileFormat::from_path(path).unwrap_or(ConfigFileFormat::Yaml);
+        let locator = Self::locator(format, &content);
+        let (config, parsed) = Self::parse_with::<ConfKitConfig>(path, &content, format, &locator);
+        diagnostics.extend(parsed);
+        let Some(mut config) = config else {
+            return (None, diagnostics);
+        };
+
+        let spaces = std::mem::take(&mut config.spaces);
+        let images = std::mem::take(&mut config.images);
+        let includes = config.include.clone().unwrap_or_default();
+
+        let mut loaded = LoadedConfig {
+            path: path.to_string(),
+            config,
+            space_origins: vec![],
+            image_origins: vec![],
+            locators: HashMap::from([(path.to_string(), locator)]),
+        };
+        loaded.merge(path, spaces, images, &mut diagnostics);
+
+        let base = Path::new(path).parent().unwrap_or(Path::new(""));
+        let mut seen: HashSet<PathBuf> = fs::canonicalize(path).into_iter().collect();
+
+        for (i, pattern) in includes.iter().enumerate() {
+            let position = loaded.locate(&["include", &i.to_string()]);
+            let files = match resolve_include(base, pattern) {
+                Ok(files) => files,
+                Err(e) => {
+                    diagnostics.push(Diagnostic::error(
+                        path,
+                        position,
+                        format!("invalid include pattern '{pattern}': {e}"),
+                    ));
+                    continue;
+                }
+            };
+
+            if files.is_empty() {
+                if is_glob(pattern) {
+                    diagnostics.push(Diagnostic::warning(
+                        path,
+                        position,
+                        format!("include pattern '{pattern}' matched no files"),
+                    ));
+                } else {
+                    diagnostics.push(Diagnostic::error(
+                        path,
+                        position,
+                        format!("included file does not exist: {pattern}"),
+                    ));
+                }
+                continue;
+            }
+
+            for file in files {
+                // 同一文件 (含主配置自身) 只合并一次
+                if !seen.insert(fs::canonicalize(&file).unwrap_or_else(|_| file.clone())) {
+                    continue;
+                }
+                Self::merge_include(&mut loaded, &file.to_string_lossy(), &mut diagnostics);
+            }
+        }
+
+        (Some(loaded), diagnostics)
+    }
+
+    fn merge_include(loaded: &mut LoadedConfig, file: &str, diagnostics: &mut Vec<Diagnostic>) {
+        let content = match fs::read_to_string(file) {
+            Ok(content) => content,
+            Err(e) => {
+                diagnostics.push(Diagnostic::error(
+                    file,
+                    None,
+                    format!("failed to read file: {e}"),
+                ));
+                return;
+            }
+        };
+        let format = ConfigFileFormat::from_path(file).unwrap_or(ConfigFileFormat::Yaml);
+        let locator = Self::locator(format, &content);
+        let (include, parsed) =
+            Self::parse_with::<ConfKitConfigInclude>(file, &content, format, &locator);
+        diagnostics.extend(parsed);
+        loaded.locators.insert(file.to_string(), locator);
+
+        if let Some(include) = include {
+            loaded.merge(file, include.spaces, include.images, diagnostics);
+        }
+    }
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+/// 解析 include 条目为文件列表 (按路径排序), 相对路径基于主配置所在目录
+fn resolve_include(base: &Path, pattern: &str) -> Result<Vec<PathBuf>, String> {
+    let full = base.join(pattern);
+
+    if !is_glob(pattern) {
+        return Ok(if full.is_file() { vec![full] } else { vec![] });
+    }
+
+    let entries = glob::glob(&full.to_string_lossy()).map_err(|e| e.to_string())?;
+    let mut files: Vec<PathBuf> = entries.flatten().filter(|path| path.is_file()).collect();
+    files.sort();
+    Ok(files)
+}
+
+fn format_location(file: &str, position: Option<(usize, usize)>) -> String {
+    match position {
+        Some((line, _)) => format!("{file}:{line}"),
+        None => file.to_string(),
+    }
+}
@@ -0,0 +1,139 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: YAML key path to line/column locator
+
+use std::collections::HashMap;
+
+/// YAML 路径定位器
+///
+/// 按缩进扫描块格式 YAML, 记录每个键与序列项的行列号。路径以 `.` 连接,
+/// 序列项使用下标, 例如 `steps.0.container`。不支持 flow 格式 (`{}` / `[]`)
+/// 内部的定位, 此时回退到最近的父路径。
+#[derive(Debug, Default)]
+pub struct YamlLocator {
+    positions: HashMap<String, (usize, usize)>,
+}
+
+/// 扫描栈中的节点
+struct Node {
+    indent: usize,
+    segment: String,
+    is_item: bool,
+}
+
+impl YamlLocator {
+    pub fn new(content: &str) -> Self {
+        let mut positions = HashMap::new();
+        let mut stack: Vec<Node> = vec![];
+        let mut item_counters: HashMap<String, usize> = HashMap::new();
+        // 块标量 (| / >) 所属键的缩进, 其内容行需跳过
+        let mut block_indent: Option<usize> = None;
+
+        for (index, raw_line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let trimmed = raw_line.trim_start();
+            let mut indent = raw_line.len() - trimmed.len();
+
+            if let Some(block) = block_indent {
+                if trimmed.is_empty() || indent > block {
+                    continue;
+                }
+                block_indent = None;
+            }
+
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("---") {
+                continue;
+            }
+
+            let mut content = trimmed;
+
+            // 序列项, 同一行可能继续跟随键 (`- name: x`) 或嵌套序列 (`- - x`)
+            while content == "-" || content.starts_with("- ") {
+                while stack
+                    .last()
+                    .is_some_and(|n| n.indent > indent || (n.indent == indent && n.is_item))
+                {
+                    stack.pop();
+                }
+
+                let parent = join(&stack);
+                let counter = item_counters.entry(parent.clone()).or_insert(0);
+                let segment = counter.to_string();
+                *counter += 1;
+
+                stack.push(Node { indent, segment, is_item: true });
+                positions.entry(join(&stack)).or_insert((line_number, indent + 1));
+
+                let rest = content[1..].trim_start();
+                indent += content.len() - rest.len();
+                content = rest;
+            }
+
+            let Some((key, value)) = split_key(content) else {
+                continue;
+            };
+
+            while stack.last().is_some_and(|n| n.indent >= indent) {
+                stack.pop();
+            }
+            stack.push(Node { indent, segment: key, is_item: false });
+
+            let path = join(&stack);
+            // 新的映射键出现时, 重置其下序列项的计数
+            item_counters.remove(&path);
+            positions.entry(path).or_insert((line_number, indent + 1));
+
+            let value = value.trim_start();
+            if value.starts_with('|') || value.starts_with('>') {
+                block_indent = Some(indent);
+            }
+        }
+
+        Self { positions }
+    }
+
+    /// 查找路径的行列号, 找不到时逐级回退到父路径
+    pub fn locate(&self, path: &[String]) -> Option<(usize, usize)> {
+        let mut len = path.len();
+        while len > 0 {
+            if let Some(position) = self.positions.get(&path[..len].join(".")) {
+                return Some(*position);
+            }
+            len -= 1;
+        }
+        None
+    }
+}
+
+fn join(stack: &[Node]) -> String {
+    stack.iter().map(|n| n.segment.as_str()).collect::<Vec<_>>().join(".")
+}
+
+/// 拆分 `key: value`, 支持带引号的键
+fn split_key(content: &str) -> Option<(String, &str)> {
+    if let Some(quote @ ('"' | '\'')) = content.chars().next() {
+        let end = content[1..].find(quote)? + 1;
+        let rest = content[end + 1..].trim_start();
+        let value = rest.strip_prefix(':')?;
+        if !(value.is_empty() || value.starts_with(char::is_whitespace)) {
+            return None;
+        }
+        return Some((content[1..end].to_string(), value));
+    }
+
+    // 值中的 `: ` 不影响键的判断, 以第一个 `:` (其后为空白或行尾) 为准
+    let mut search_from = 0;
+    while let Some(offset) = content[search_from..].find(':') {
+        let position = search_from + offset;
+        let value = &content[position + 1..];
+        if value.is_empty() || value.starts_with(char::is_whitespace) {
+            let key = content[..position].trim_end();
+            if key.is_empty() || key.starts_with(['{', '[']) {
+                return None;
+            }
+            return Some((key.to_string(), value));
+        }
+        search_from = position + 1;
+    }
+    None
+}
@@ -0,0 +1,746 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Configuration validator with file:line:col diagnostics
+
+pub mod diagnostic;
+pub mod format;
+pub mod include;
+pub mod locator;
+
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::core::builder::graph::ImageGraph;
+use crate::core::condition::lint::lint_condition;
+use crate::core::condition::parser::parse_condition;
+use crate::core::condition::MessageLang;
+use crate::core::executor::step_executor::StepExecutor;
+use crate::infra::config::ConfKitConfigLoader;
+use crate::types::config::{
+    ConfKitProjectConfig, ConfKitSecretSource, Engine, EngineComposeConfig, EngineMode,
+};
+use crate::utils::fs::get_files_with_extensions_in_dir;
+
+pub use diagnostic::{Diagnostic, Severity, ValidationReport};
+pub use format::ConfigFileFormat;
+pub use include::LoadedConfig;
+pub use locator::YamlLocator;
+
+/// 执行时注入的内置变量, 与 ExecutionContext 保持一致
+const BUILTIN_VARIABLES: &[&str] = &[
+    "TASK_ID",
+    "PROJECT_NAME",
+    "SPACE_NAME",
+    "TASK_WORKSPACE_DIR",
+    "HOST_VOLUMES_DIR",
+    "HOST_WORKSPACE_DIR",
+    "HOST_ARTIFACTS_ROOT_DIR",
+    "CONTAINER_WORKSPACE_DIR",
+    "CONTAINER_ARTIFACTS_ROOT_DIR",
+];
+
+/// 配置了 source 时注入的 Git 变量
+const GIT_VARIABLES: &[&str] =
+    &["GIT_REPO", "GIT_BRANCH", "GIT_HASH", "GIT_HASH_SHORT", "PROJECT_VERSION"];
+
+/// 条件表达式中 `steps.<name>.<field>` 可用的字段
+const STEP_CONDITION_FIELDS: &[&str] = &["status", "exit_code", "duration_ms"];
+
+/// 条件表达式中 `task.<field>` 可用的字段
+const TASK_CONDITION_FIELDS: &[&str] = &["failed", "failed_steps"];
+
+/// 已解析的 YAML 文件
+struct ParsedFile<T> {
+    path: String,
+    locator: YamlLocator,
+    value: T,
+}
+
+impl<T> ParsedFile<T> {
+    fn locate(&self, path: &[&str]) -> Option<(usize, usize)> {
+        let path: Vec<String> = path.iter().map(|s| s.to_string()).collect();
+        self.locator.locate(&path)
+    }
+}
+
+/// compose 文件中定义的构建器容器
+#[derive(Default)]
+struct ComposeServices {
+    /// 服务名与 container_name
+    containers: HashSet<String>,
+    loaded: bool,
+    /// engine: none, 不使用 compose 文件
+    host_only: bool,
+}
+
+pub struct ConfigValidator;
+
+impl ConfigValidator {
+    /// 校验主配置文件及其引入的文件、compose 文件、项目配置
+    pub fn validate_file(config_path: &str) -> ValidationReport {
+        let mut report = ValidationReport::default();
+
+        let (config, diagnostics) = Self::load_config(config_path);
+        for diagnostic in diagnostics {
+            report.push(diagnostic);
+        }
+        let Some(config) = config else {
+            return report;
+        };
+
+        Self::check_spaces(&config, &mut report);
+        let services = Self::check_compose(&config, &mut report);
+        Self::check_images(&config, &mut report);
+
+        for space in &config.config.spaces {
+            if Path::new(&space.path).is_dir() {
+                Self::check_projects(&space.name, &space.path, &services, &mut report);
+            }
+        }
+
+        report
+    }
+
+    /// 按扩展名 (yaml/toml/json) 反序列化配置并收集未知字段, 无法识别的扩展名按 YAML 处理
+    ///
+    /// 返回 (解析结果, 未知字段诊断), 解析失败时诊断中包含错误位置。
+    pub fn parse_config<T: DeserializeOwned>(
+        file: &str,
+        content: &str,
+    ) -> (Option<T>, Vec<Diagnostic>) {
+        let format = ConfigFileFormat::from_path(file).unwrap_or(ConfigFileFormat::Yaml);
+        Self::parse_with(file, content, format, &Self::locator(format, content))
+    }
+
+    /// 键路径定位器, 仅 YAML 可定位键与序列项, TOML / JSON 只报告解析错误的位置
+    fn locator(format: ConfigFileFormat, content: &str) -> YamlLocator {
+        match format {
+            ConfigFileFormat::Yaml => YamlLocator::new(content),
+            ConfigFileFormat::Toml | ConfigFileFormat::Json => YamlLocator::default(),
+        }
+    }
+
+    fn parse_with<T: DeserializeOwned>(
+        file: &str,
+        content: &str,
+        format: ConfigFileFormat,
+        locator: &YamlLocator,
+    ) -> (Option<T>, Vec<Diagnostic>) {
+        let mut unknown: Vec<Vec<String>> = vec![];
+        let mut on_unknown = |path: serde_ignored::Path| unknown.push(path_segments(&path));
+
+        // 失败时返回 (错误信息, 位置)
+        let result: Result<T, (String, Option<(usize, usize)>)> = match format {
+            ConfigFileFormat::Yaml => {
+                let deserializer = serde_yaml::Deserializer::from_str(content);
+                serde_ignored::deserialize(deserializer, &mut on_unknown).map_err(|e| {
+                    let position = e.location().map(|l| (l.line(), l.column()));
+                    (strip_location(&e.to_string()), position)
+                })
+            }
+            ConfigFileFormat::Json => {
+                let mut deserializer = serde_json::Deserializer::from_str(content);
+                serde_ignored::deserialize(&mut deserializer, &mut on_unknown)
+                    .and_then(|value| deserializer.end().map(|_| value))
+                    .map_err(|e| {
+                        let position = (e.line() > 0).then(|| (e.line(), e.column()));
+                        (strip_location(&e.to_string()), position)
+                    })
+            }
+            ConfigFileFormat::Toml => toml::Deserializer::parse(content)
+                .and_then(|deserializer| serde_ignored::deserialize(deserializer, &mut on_unknown))
+                .map_err(|e| {
+                    let position = e.span().map(|span| offset_position(content, span.start));
+                    (e.message().to_string(), position)
+                }),
+        };
+
+        match result {
+            Ok(value) => {
+                let diagnostics = unknown
+                    .iter()
+                    // JSON 配置可通过 `$schema` 关联编辑器的 JSON Schema
+                    .filter(|path| path.as_slice() != ["$schema"])
+                    .map(|path| {
+                        Diagnostic::warning(
+                            file,
+                            locator.locate(path),
+                            format!("unknown field `{}`", path.join(".")),
+                        )
+                    })
+                    .collect();
+                (Some(value), diagnostics)
+            }
+            Err((message, position)) => (None, vec![Diagnostic::error(file, position, message)]),
+        }
+    }
+
+    /// 读取并解析文件, 格式按扩展名识别
+    fn parse_file<T: DeserializeOwned>(
+        path: &str,
+        report: &mut ValidationReport,
+    ) -> Option<ParsedFile<T>> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                report.push(Diagnostic::error(path, None, format!("failed to read file: {e}")));
+                return None;
+            }
+        };
+
+        let format = ConfigFileFormat::from_path(path).unwrap_or(ConfigFileFormat::Yaml);
+        let locator = Self::locator(format, &content);
+        let (value, diagnostics) = Self::parse_with::<T>(path, &content, format, &locator);
+        for diagnostic in diagnostics {
+            report.push(diagnostic);
+        }
+
+        value.map(|value| ParsedFile { path: path.to_string(), locator, value })
+    }
+
+    /// 重复的 space 名称在合并 include 时已报告
+    fn check_spaces(config: &LoadedConfig, report: &mut ValidationReport) {
+        if config.config.spaces.is_empty() {
+            report.push(Diagnostic::error(
+                &config.path,
+                config.locate(&["spaces"]),
+                "no spaces defined",
+            ));
+        }
+
+        for (i, space) in config.config.spaces.iter().enumerate() {
+            if space.name.is_empty() {
+                let (file, position) = config.locate_space(i, "name");
+                report.push(Diagnostic::error(file, position, "space name is empty"));
+            }
+
+            if !Path::new(&space.path).is_dir() {
+                let (file, position) = config.locate_space(i, "path");
+                report.push(Diagnostic::error(
+                    file,
+                    position,
+                    format!("space '{}' path does not exist: {}", space.name, space.path),
+                ));
+            }
+        }
+    }
+
+    fn check_compose(config: &LoadedConfig, report: &mut ValidationReport) -> ComposeServices {
+        let mut services = ComposeServices::default();
+        let compose_file = &config.config.engine_compose.file;
+
+        if config.config.engine == Engine::None {
+            services.host_only = true;
+            return services;
+        }
+
+        if config.config.engine == Engine::Nerdctl && config.config.engine_mode == EngineMode::Api {
+            report.push(Diagnostic::error(
+                &config.path,
+                config.locate(&["engine_mode"]),
+                "engine_mode: api is not supported by engine: nerdctl",
+            ));
+        }
+
+        let podman_api = matches!(config.config.engine, Engine::Podman)
+            && config.config.engine_mode == EngineMode::Api;
+        if config.config.engine_compose.pod.is_some() && !podman_api {
+            report.push(Diagnostic::warning(
+                &config.path,
+                config.locate(&["engine_compose", "pod"]),
+                "engine_compose.pod only applies to engine: podman with engine_mode: api",
+            ));
+        }
+
+        if !Path::new(compose_file).is_file() {
+            report.push(Diagnostic::error(
+                &config.path,
+                config.locate(&["engine_compose", "file"]),
+                format!("compose file does not exist: {compose_file}"),
+            ));
+            return services;
+        }
+
+        let Some(compose) = Self::parse_file::<EngineComposeConfig>(compose_file, report) else {
+            return services;
+        };
+
+        // confkit 管理的镜像: name:tag
+        let images: HashSet<String> = config
+            .config
+            .images
+            .iter()
+            .map(|image| format!("{}:{}", image.name, image.tag))
+            .collect();
+
+        let mut service_names: Vec<&String> = compose.value.services.keys().collect();
+        service_names.sort();
+        for name in service_names {
+            let service = &compose.value.services[name];
+            services.containers.insert(name.clone());
+            if !service.container_name.is_empty() {
+                services.containers.insert(service.container_name.clone());
+            }
+
+            if !service.image.is_empty() && !images.contains(&service.image) {
+                report.push(Diagnostic::warning(
+                    compose_file,
+                    compose.locate(&["services", name, "image"]),
+                    format!(
+                        "image '{}' of service '{}' is not defined in images of {}",
+                        service.image, name, config.path
+                    ),
+                ));
+            }
+        }
+
+        services.loaded = true;
+        services
+    }
+
+    /// 重复的镜像 name:tag 在合并 include 时已报告
+    fn check_images(config: &LoadedConfig, report: &mut ValidationReport) {
+        for (i, image) in config.config.images.iter().enumerate() {
+            let reference = format!("{}:{}", image.name, image.tag);
+
+            if image.name.is_empty() {
+                let (file, position) = config.locate_image(i, "name");
+                report.push(Diagnostic::error(file, position, "image name is empty"));
+            }
+
+            if !Path::new(&image.context).is_dir() {
+                let (file, position) = config.locate_image(i, "context");
+                report.push(Diagnostic::error(
+                    file,
+                    position,
+                    format!("image '{}' context does not exist: {}", reference, image.context),
+                ));
+            }
+
+            if !Path::new(&image.engine_file).is_file() {
+                let (file, position) = config.locate_image(i, "engine_file");
+                report.push(Diagnostic::error(
+                    file,
+                    position,
+                    format!(
+                        "image '{}' engine file does not exist: {}",
+                        reference, image.engine_file
+                    ),
+                ));
+            }
+
+            for secret in image.secrets.iter().flatten() {
+                let message = match (&secret.from, &secret.path) {
+                    (ConfKitSecretSource::Encrypted, _) => format!(
+                        "image '{}' build secret '{}': from: encrypted is not supported for images",
+                        reference, secret.name
+                    ),
+                    (ConfKitSecretSource::File, None) => format!(
+                        "image '{}' build secret '{}' (from: file) requires a path",
+                        reference, secret.name
+                    ),
+                    _ => continue,
+                };
+                let (file, position) = config.locate_image(i, "secrets");
+                report.push(Diagnostic::error(file, position, message));
+            }
+
+            if let Some(push) = &image.push {
+                let mut messages = vec![];
+                if push.registry.trim().is_empty() {
+                    messages.push(format!("image '{reference}' push registry is empty"));
+                }
+                if let Some(space) = &push.space {
+                    if !config.config.spaces.iter().any(|s| s.name == *space) {
+                        messages.push(format!(
+                            "image '{reference}' push space '{space}' is not defined"
+                        ));
+                    }
+                }
+                if let Some(credentials) = &push.credentials {
+                    for secret in credentials {
+                        if secret.from == ConfKitSecretSource::Encrypted && push.space.is_none() {
+                            messages.push(format!(
+                                "image '{}' push credential '{}': from: encrypted requires push.space",
+                                reference, secret.name
+                            ));
+                        }
+                    }
+                    let has = |name: &str| credentials.iter().any(|secret| secret.name == name);
+                    if !has("username") || !has("password") {
+                        messages.push(format!(
+                            "image '{reference}' push credentials require 'username' and 'password'"
+                        ));
+                    }
+                }
+                for message in messages {
+                    let (file, position) = config.locate_image(i, "push");
+                    report.push(Diagnostic::error(file, position, message));
+                }
+            }
+
+            // 接口构建不经过 BuildKit
+            if config.config.engine_mode == EngineMode::Api {
+                let platforms = image.platforms.as_ref().map_or(0, Vec::len);
+                let secrets = image.secrets.as_ref().map_or(0, Vec::len);
+                if platforms > 1 || secrets > 0 {
+                    let (file, position) = config.locate_image(i, "name");
+                    report.push(Diagnostic::error(
+                        file,
+                        position,
+                        format!(
+                            "image '{reference}' uses build secrets or multiple platforms, which require engine_mode: cli"
+                        ),
+                    ));
+                }
+            }
+        }
+
+        // 本地基础镜像的循环依赖无法确定构建顺序
+        let graph = ImageGraph::from_configs(&config.config.images);
+        if let Some(cycle) = graph.find_cycle() {
+            let (file, position) = config.locate_image(cycle[0], "base_image");
+            report.push(Diagnostic::error(
+                file,
+                position,
+                format!("image dependency cycle: {}", graph.describe(&cycle)),
+            ));
+        }
+    }
+
+    fn check_projects(
+        space_name: &str,
+        space_path: &str,
+        services: &ComposeServices,
+        report: &mut ValidationReport,
+    ) {
+        let mut files =
+            match get_files_with_extensions_in_dir(space_path, ConfigFileFormat::EXTENSIONS) {
+                Ok(files) => files,
+                Err(e) => {
+                    report.push(Diagnostic::error(
+                        space_path,
+                        None,
+                        format!("failed to list project configs: {e}"),
+                    ));
+                    return;
+                }
+            };
+        files.sort();
+
+        if files.is_empty() {
+            report.push(Diagnostic::warning(
+                space_path,
+                None,
+                format!("space '{space_name}' has no project configs"),
+            ));
+        }
+
+        // 项目名 -> 首次定义的文件
+        let mut names: HashMap<String, String> = HashMap::new();
+
+        for file_name in files {
+            let path = Path::new(space_path).join(&file_name).to_string_lossy().to_string();
+            let Some(project) = Self::parse_file::<ConfKitProjectConfig>(&path, report) else {
+                continue;
+            };
+
+            if let Some(first) = names.get(&project.value.name) {
+                report.push(Diagnostic::error(
+                    &path,
+                    project.locate(&["name"]),
+                    format!(
+                        "duplicate project name '{}' in space '{}' (first defined in {})",
+                        project.value.name, space_name, first
+                    ),
+                ));
+            } else {
+                names.insert(project.value.name.clone(), path.clone());
+            }
+
+            Self::check_project(&project, services, report);
+        }
+    }
+
+    fn check_project(
+        project: &ParsedFile<ConfKitProjectConfig>,
+        services: &ComposeServices,
+        report: &mut ValidationReport,
+    ) {
+        let file = project.path.as_str();
+        let config = &project.value;
+
+        if config.steps.is_empty() {
+            report.push(Diagnostic::warning(
+                file,
+                project.locate(&["steps"]),
+                format!("project '{}' has no steps", config.name),
+            ));
+        }
+
+        for (i, env_file) in config.environment_files.iter().flatten().enumerate() {
+            if !Path::new(&env_file.path).is_file() {
+                report.push(Diagnostic::warning(
+                    file,
+                    project.locate(&["environment_files", &i.to_string(), "path"]),
+                    format!("environment file does not exist: {}", env_file.path),
+                ));
+            }
+        }
+
+        for (i, secret) in config.secrets.iter().flatten().enumerate() {
+            let index = i.to_string();
+            if secret.from == ConfKitSecretSource::File {
+                match &secret.path {
+                    None => report.push(Diagnostic::error(
+                        file,
+                        project.locate(&["secrets", &index]),
+                        format!("secret '{}' requires 'path' for from: file", secret.name),
+                    )),
+                    Some(path) if !Path::new(path).is_file() => report.push(Diagnostic::warning(
+                        file,
+                        project.locate(&["secrets", &index, "path"]),
+                        format!("secret file does not exist: {path}"),
+                    )),
+                    Some(_) => {}
+                }
+            }
+        }
+
+        for (i, arg) in config.environment_from_args.iter().flatten().enumerate() {
+            if let Some(condition) = &arg.condition {
+                let position =
+                    project.locate(&["environment_from_args", &i.to_string(), "condition"]);
+                for message in Self::lint_condition(condition) {
+                    report.push(Diagnostic::error(file, position, message));
+                }
+            }
+        }
+
+        let defined = Self::defined_variables(config);
+        let mut check_references = |path: &[&str], value: &str| {
+            for name in variable_references(value) {
+                if !defined.contains(name) {
+                    report.push(Diagnostic::warning(
+                        file,
+                        project.locate(path),
+                        format!("variable '${{{name}}}' is not defined"),
+                    ));
+                }
+            }
+        };
+
+        let mut environment: Vec<(&String, &String)> =
+            config.environment.iter().flatten().collect();
+        environment.sort();
+        for (key, value) in environment {
+            check_references(&["environment", key], value);
+        }
+
+        for (i, step) in config.steps.iter().enumerate() {
+            let index = i.to_string();
+            if let Some(working_dir) = &step.working_dir {
+                check_references(&["steps", &index, "working_dir"], working_dir);
+            }
+            for (j, command) in step.commands.iter().enumerate() {
+                check_references(&["steps", &index, "commands", &j.to_string()], command);
+            }
+            if let Some(condition) = &step.condition {
+                check_references(&["steps", &index, "condition"], condition);
+            }
+        }
+
+        for (i, arg) in config.environment_from_args.iter().flatten().enumerate() {
+            if let Some(condition) = &arg.condition {
+                check_references(
+                    &["environment_from_args", &i.to_string(), "condition"],
+                    condition,
+                );
+            }
+        }
+
+        for (i, step) in config.steps.iter().enumerate() {
+            let index = i.to_string();
+
+            if let Some(container) = &step.container {
+                if services.host_only && step.fallback.is_none() {
+                    report.push(Diagnostic::warning(
+                        file,
+                        project.locate(&["steps", &index, "container"]),
+                        format!(
+                            "step '{}' uses container '{}' but engine is none; it will fail unless `fallback: host` is set",
+                            step.name, container
+                        ),
+                    ));
+                }
+                if services.loaded && !services.containers.contains(container) {
+                    report.push(Diagnostic::error(
+                        file,
+                        project.locate(&["steps", &index, "container"]),
+                        format!(
+                            "step '{}' uses unknown container '{}' (not a compose service)",
+                            step.name, container
+                        ),
+                    ));
+                }
+            }
+
+            if let Some(condition) = &step.condition {
+                let position = project.locate(&["steps", &index, "condition"]);
+                let errors = Self::lint_condition(condition);
+                if !errors.is_empty() {
+                    for message in errors {
+                        report.push(Diagnostic::error(file, position, message));
+                    }
+                    continue;
+                }
+                for message in Self::check_step_references(config, i, condition) {
+                    report.push(Diagnostic::warning(file, position, message));
+                }
+            }
+        }
+    }
+
+    /// 解析并静态检查条件表达式, 解析错误附带出错列号和插入符
+    fn lint_condition(condition: &str) -> Vec<String> {
+        match parse_condition(condition) {
+            Ok(expr) => lint_condition(&expr)
+                .into_iter()
+                .map(|issue| format!("invalid condition: {issue}"))
+                .collect(),
+            Err(e) => vec![format!("invalid condition: {}", e.render(MessageLang::from_env()))],
+        }
+    }
+
+    /// 检查条件中的 `steps.<name>.<field>` / `task.<field>` 引用, 步骤只能引用之前的步骤
+    fn check_step_references(
+        config: &ConfKitProjectConfig,
+        index: usize,
+        condition: &str,
+    ) -> Vec<String> {
+        let mut messages = vec![];
+        let matches_step =
+            |name: &str, key: &str| name == key || StepExecutor::step_key(name) == key;
+
+        for reference in dotted_references(condition) {
+            if let Some(field) = reference.strip_prefix("task.") {
+                if !TASK_CONDITION_FIELDS.contains(&field) {
+                    messages.push(format!("unknown task field '${{{reference}}}'"));
+                }
+                continue;
+            }
+
+            let Some((key, field)) =
+                reference.strip_prefix("steps.").and_then(|rest| rest.rsplit_once('.'))
+            else {
+                continue;
+            };
+            if !STEP_CONDITION_FIELDS.contains(&field) {
+                messages.push(format!("unknown step field '${{{reference}}}'"));
+            } else if !config.steps[..index].iter().any(|s| matches_step(&s.name, key)) {
+                let reason = if config.steps[index..].iter().any(|s| matches_step(&s.name, key)) {
+                    "a step that has not run yet"
+                } else {
+                    "an unknown step"
+                };
+                messages.push(format!("'${{{reference}}}' refers to {reason}"));
+            }
+        }
+        messages
+    }
+
+    /// 项目执行时可用的变量名
+    fn defined_variables(config: &ConfKitProjectConfig) -> HashSet<String> {
+        let mut defined: HashSet<String> =
+            BUILTIN_VARIABLES.iter().map(|name| name.to_string()).collect();
+
+        if config.source.is_some() {
+            defined.extend(GIT_VARIABLES.iter().map(|name| name.to_string()));
+        }
+        for (_, vars) in ConfKitConfigLoader::load_environment_files(config) {
+            defined.extend(vars.into_keys());
+        }
+        defined.extend(config.environment.iter().flatten().map(|(key, _)| key.clone()));
+        defined.extend(config.secrets.iter().flatten().map(|secret| secret.name.clone()));
+        defined.extend(config.environment_from_args.iter().flatten().map(|arg| arg.name.clone()));
+
+        defined
+    }
+}
+
+/// 提取 `${NAME}` 形式的变量引用, 忽略密钥引用与 shell 参数展开 (`${VAR:-x}`)
+fn variable_references(value: &str) -> Vec<&str> {
+    let mut names = vec![];
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        let name = &after[..end];
+        if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            names.push(name);
+        }
+        rest = &after[end + 1..];
+    }
+    names
+}
+
+/// 提取 `${a.b}` 形式的点分引用, 如 `${steps.build.status}`
+fn dotted_references(value: &str) -> Vec<&str> {
+    let mut names = vec![];
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        let name = &after[..end];
+        if name.contains('.') {
+            names.push(name);
+        }
+        rest = &after[end + 1..];
+    }
+    names
+}
+
+fn path_segments(path: &serde_ignored::Path) -> Vec<String> {
+    let mut segments = vec![];
+    let mut current = path;
+    loop {
+        match current {
+            serde_ignored::Path::Root => break,
+            serde_ignored::Path::Seq { parent, index } => {
+                segments.push(index.to_string());
+                current = parent;
+            }
+            serde_ignored::Path::Map { parent, key } => {
+                segments.push(key.clone());
+                current = parent;
+            }
+            serde_ignored::Path::Some { parent }
+            | serde_ignored::Path::NewtypeStruct { parent }
+            | serde_ignored::Path::NewtypeVariant { parent } => current = parent,
+        }
+    }
+    segments.reverse();
+    segments
+}
+
+/// 字节偏移转换为行列号 (从 1 开始)
+fn offset_position(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+    (line, column)
+}
+
+/// 去除 serde_yaml / serde_json 错误信息中的位置后缀, 位置已单独输出
+fn strip_location(message: &str) -> String {
+    match message.find(" at line ") {
+        Some(index) => message[..index].to_string(),
+        None => message.to_string(),
+    }
+}
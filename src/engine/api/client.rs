@@ -0,0 +1,355 @@
+//! Author: xiaoYown
+//! Created: 2026-10-19
+//! Description: Minimal HTTP client for docker-compatible engine APIs (unix socket / tcp)
+
+use std::fmt;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::client::conn::http1;
+use hyper::header::{CONTENT_TYPE, HOST};
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpStream, UnixStream};
+
+/// docker 默认 socket
+pub const DEFAULT_DOCKER_SOCKET: &str = "/var/run/docker.sock";
+
+/// 引擎 API 地址
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiEndpoint {
+    /// unix socket 路径
+    Unix(PathBuf),
+    /// host:port
+    Tcp(String),
+}
+
+impl ApiEndpoint {
+    /// 解析 `unix:///path`, `tcp://host:port` 或 socket 路径
+    pub fn parse(host: &str) -> Result<Self> {
+        if let Some(path) = host.strip_prefix("unix://") {
+            return Ok(ApiEndpoint::Unix(PathBuf::from(path)));
+        }
+        if let Some(address) = host.strip_prefix("tcp://").or_else(|| host.strip_prefix("http://"))
+        {
+            let address = address.trim_end_matches('/');
+            if address.is_empty() {
+                anyhow::bail!("Invalid engine host '{host}': missing address");
+            }
+            return Ok(ApiEndpoint::Tcp(address.to_string()));
+        }
+        if host.starts_with('/') {
+            return Ok(ApiEndpoint::Unix(PathBuf::from(host)));
+        }
+
+        anyhow::bail!("Unsupported engine host '{host}': expected unix:// or tcp://")
+    }
+
+    /// `DOCKER_HOST`, 未设置时使用默认 socket
+    pub fn from_env() -> Result<Self> {
+        match std::env::var("DOCKER_HOST") {
+            Ok(host) if !host.trim().is_empty() => Self::parse(host.trim()),
+            _ => Ok(ApiEndpoint::Unix(PathBuf::from(DEFAULT_DOCKER_SOCKET))),
+        }
+    }
+}
+
+impl fmt::Display for ApiEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiEndpoint::Unix(path) => write!(f, "unix://{}", path.display()),
+            ApiEndpoint::Tcp(address) => write!(f, "tcp://{address}"),
+        }
+    }
+}
+
+/// 请求体
+pub enum ApiBody {
+    Empty,
+    Json(serde_json::Value),
+    Tar(Vec<u8>),
+}
+
+/// 接口返回的错误信息
+#[derive(Debug, Deserialize)]
+struct ApiErrorMessage {
+    message: String,
+}
+
+/// 引擎 API 客户端, 每个请求使用独立连接
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    endpoint: ApiEndpoint,
+}
+
+impl ApiClient {
+    pub fn new(endpoint: ApiEndpoint) -> Self {
+        Self { endpoint }
+    }
+
+    pub fn endpoint(&self) -> &ApiEndpoint {
+        &self.endpoint
+    }
+
+    async fn connect(&self) -> Result<http1::SendRequest<Full<Bytes>>> {
+        match &self.endpoint {
+            ApiEndpoint::Unix(path) => handshake(UnixStream::connect(path).await?).await,
+            ApiEndpoint::Tcp(address) => handshake(TcpStream::connect(address).await?).await,
+        }
+    }
+
+    /// 发送请求, 返回未读取的响应
+    pub async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: ApiBody,
+    ) -> Result<Response<Incoming>> {
+        self.request_with_headers(method, path, body, &[]).await
+    }
+
+    /// 发送带附加请求头的请求 (如推送镜像时的 `X-Registry-Auth`)
+    pub async fn request_with_headers(
+        &self,
+        method: Method,
+        path: &str,
+        body: ApiBody,
+        headers: &[(&str, String)],
+    ) -> Result<Response<Incoming>> {
+        let mut sender = self
+            .connect()
+            .await
+            .with_context(|| format!("Cannot connect to engine API at {}", self.endpoint))?;
+
+        let mut builder = Request::builder().method(method).uri(path).header(HOST, "localhost");
+        for (name, value) in headers {
+            builder = builder.header(*name, value);
+        }
+        let request = match body {
+            ApiBody::Empty => builder.body(Full::new(Bytes::new()))?,
+            ApiBody::Json(value) => builder
+                .header(CONTENT_TYPE, "application/json")
+                .body(Full::new(Bytes::from(serde_json::to_vec(&value)?)))?,
+            ApiBody::Tar(archive) => builder
+                .header(CONTENT_TYPE, "application/x-tar")
+                .body(Full::new(Bytes::from(archive)))?,
+        };
+
+        Ok(sender.send_request(request).await?)
+    }
+
+    /// 发送请求并读取完整响应体
+    pub async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: ApiBody,
+    ) -> Result<(StatusCode, Bytes)> {
+        let response = self.request(method, path, body).await?;
+        let status = response.status();
+        let body = response.into_body().collect().await?.to_bytes();
+        Ok((status, body))
+    }
+
+    /// GET 并解析 JSON, 404 时返回 None
+    pub async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        let (status, body) = self.send(Method::GET, path, ApiBody::Empty).await?;
+        if status == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(api_error(status, &body));
+        }
+        Ok(Some(serde_json::from_slice(&body)?))
+    }
+
+    /// 发送请求, 非 2xx 时返回接口中的错误信息
+    pub async fn expect_success(&self, method: Method, path: &str, body: ApiBody) -> Result<Bytes> {
+        let (status, body) = self.send(method, path, body).await?;
+        if !status.is_success() {
+            return Err(api_error(status, &body));
+        }
+        Ok(body)
+    }
+
+    /// 逐块读取响应体
+    pub async fn read_chunks(
+        response: Response<Incoming>,
+        mut on_chunk: impl FnMut(&[u8]) -> Result<()>,
+    ) -> Result<()> {
+        let mut body = response.into_body();
+        while let Some(frame) = body.frame().await {
+            if let Some(data) = frame?.data_ref() {
+                on_chunk(data)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 读取以换行分隔的 JSON 流 (build / pull 进度)
+    pub async fn read_json_stream<T: DeserializeOwned>(
+        response: Response<Incoming>,
+        mut on_message: impl FnMut(T) -> Result<()>,
+    ) -> Result<()> {
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.into_body().collect().await?.to_bytes();
+            return Err(api_error(status, &body));
+        }
+
+        let mut lines = LineBuffer::default();
+        Self::read_chunks(response, |chunk| {
+            for line in lines.push(chunk) {
+                parse_json_line(&line, &mut on_message)?;
+            }
+            Ok(())
+        })
+        .await?;
+
+        match lines.finish() {
+            Some(line) => parse_json_line(&line, &mut on_message),
+            None => Ok(()),
+        }
+    }
+}
+
+async fn handshake<S>(stream: S) -> Result<http1::SendRequest<Full<Bytes>>>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (sender, connection) = http1::handshake(TokioIo::new(stream)).await?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            tracing::debug!("Engine API connection closed: {}", e);
+        }
+    });
+    Ok(sender)
+}
+
+fn parse_json_line<T: DeserializeOwned>(
+    line: &[u8],
+    on_message: &mut impl FnMut(T) -> Result<()>,
+) -> Result<()> {
+    let line = String::from_utf8_lossy(line);
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(());
+    }
+    let message = serde_json::from_str(line)
+        .with_context(|| format!("Invalid JSON in engine API stream: {line}"))?;
+    on_message(message)
+}
+
+/// 由非 2xx 响应构造错误, 优先使用响应中的 message
+pub fn api_error(status: StatusCode, body: &[u8]) -> anyhow::Error {
+    let message = serde_json::from_slice::<ApiErrorMessage>(body)
+        .map(|error| error.message)
+        .unwrap_or_else(|_| String::from_utf8_lossy(body).trim().to_string());
+    anyhow::anyhow!("Engine API error ({}): {}", status.as_u16(), message)
+}
+
+/// 将字节流按行切分, 不完整的行保留到下一块
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    pub fn push(&mut self, data: &[u8]) -> Vec<Vec<u8>> {
+        self.pending.extend_from_slice(data);
+
+        let mut lines = vec![];
+        while let Some(index) = self.pending.iter().position(|byte| *byte == b'\n') {
+            let mut line: Vec<u8> = self.pending.drain(..=index).collect();
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// 剩余的最后一行 (无换行结尾)
+    pub fn finish(&mut self) -> Option<Vec<u8>> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        Some(std::mem::take(&mut self.pending))
+    }
+}
+
+/// exec 输出流类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// 解析 attach / exec 的多路复用输出
+///
+/// 每帧 8 字节头: [类型, 0, 0, 0, 长度(大端 u32)], 类型 1 为 stdout, 2 为 stderr。
+#[derive(Debug, Default)]
+pub struct StreamDemuxer {
+    buffer: Vec<u8>,
+    stdout: LineBuffer,
+    stderr: LineBuffer,
+}
+
+impl StreamDemuxer {
+    /// 追加数据, 返回已完整的输出行
+    pub fn push(&mut self, data: &[u8]) -> Vec<(OutputStream, String)> {
+        self.buffer.extend_from_slice(data);
+
+        let mut lines = vec![];
+        while self.buffer.len() >= 8 {
+            let size = u32::from_be_bytes([
+                self.buffer[4],
+                self.buffer[5],
+                self.buffer[6],
+                self.buffer[7],
+            ]) as usize;
+            if self.buffer.len() < 8 + size {
+                break;
+            }
+
+            let kind = self.buffer[0];
+            let payload: Vec<u8> = self.buffer.drain(..8 + size).skip(8).collect();
+            let (stream, buffer) = match kind {
+                2 => (OutputStream::Stderr, &mut self.stderr),
+                _ => (OutputStream::Stdout, &mut self.stdout),
+            };
+            for line in buffer.push(&payload) {
+                lines.push((stream, String::from_utf8_lossy(&line).to_string()));
+            }
+        }
+        lines
+    }
+
+    /// 输出结束时剩余的不完整行
+    pub fn finish(&mut self) -> Vec<(OutputStream, String)> {
+        let mut lines = vec![];
+        if let Some(line) = self.stdout.finish() {
+            lines.push((OutputStream::Stdout, String::from_utf8_lossy(&line).to_string()));
+        }
+        if let Some(line) = self.stderr.finish() {
+            lines.push((OutputStream::Stderr, String::from_utf8_lossy(&line).to_string()));
+        }
+        lines
+    }
+}
+
+/// 构造查询字符串
+pub fn query(path: &str, pairs: &[(&str, &str)]) -> String {
+    let query = url::form_urlencoded::Serializer::new(String::new()).extend_pairs(pairs).finish();
+    if query.is_empty() {
+        path.to_string()
+    } else {
+        format!("{path}?{query}")
+    }
+}
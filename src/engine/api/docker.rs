@@ -0,0 +1,640 @@
+//! Author: xiaoYown
+//! Created: 2026-10-19
+//! Description: Docker engine implementation over the Docker Engine HTTP API
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE, Engine as _};
+use chrono::{DateTime, Utc};
+use hyper::{Method, StatusCode};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    engine::{
+        api::client::{api_error, query, ApiBody, ApiClient, ApiEndpoint, StreamDemuxer},
+        cli::CliEngine,
+        traits::ContainerEngine,
+        ConfKitEngine,
+    },
+    infra::logger::TaskLogger,
+    types::config::{
+        ContainerStatus, EngineContainerInfo, EngineImageInfo, ImageBuildOptions, ImageStatus,
+        RegistryAuth,
+    },
+    utils::dockerignore::context_files,
+};
+
+/// 构建上下文外的 Dockerfile 在归档中的名称
+const EXTERNAL_DOCKERFILE: &str = ".confkit.Dockerfile";
+
+/// build / pull 进度消息
+#[derive(Debug, Default, Deserialize)]
+pub struct ProgressMessage {
+    #[serde(default)]
+    pub stream: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub progress: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ImageInspect {
+    id: String,
+    #[serde(default)]
+    created: String,
+    #[serde(default)]
+    size: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ImageSummary {
+    id: String,
+    #[serde(default)]
+    repo_tags: Option<Vec<String>>,
+    #[serde(default)]
+    created: i64,
+    #[serde(default)]
+    size: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerInspect {
+    id: String,
+    #[serde(default)]
+    created: String,
+    #[serde(default)]
+    size_rw: Option<u64>,
+    #[serde(default)]
+    size_root_fs: Option<u64>,
+    config: ContainerInspectConfig,
+    state: ContainerInspectState,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerInspectConfig {
+    #[serde(default)]
+    image: String,
+    #[serde(default)]
+    working_dir: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerInspectState {
+    #[serde(default)]
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ExecCreated {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ExecInspect {
+    #[serde(default)]
+    exit_code: Option<i32>,
+}
+
+/// 通过 Docker Engine API 操作镜像与容器
+///
+/// compose 创建容器仍调用 `docker compose`, 其余操作不依赖命令行输出格式。
+pub struct DockerApiEngine {
+    client: ApiClient,
+    compose: CliEngine,
+}
+
+impl DockerApiEngine {
+    pub fn new(client: ApiClient) -> Self {
+        Self::with_compose(client, CliEngine::docker())
+    }
+
+    /// 指定创建容器时使用的 compose 命令行, 供兼容 Docker API 的引擎复用
+    pub fn with_compose(client: ApiClient, compose: CliEngine) -> Self {
+        Self { client, compose }
+    }
+
+    /// 使用 `DOCKER_HOST` 或默认 socket
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(ApiClient::new(ApiEndpoint::from_env()?)))
+    }
+
+    /// 读取 build / pull / push 的 JSON 进度流, 遇到 error 消息时返回错误
+    async fn stream_progress(
+        &self,
+        method: Method,
+        path: &str,
+        body: ApiBody,
+        headers: &[(&str, String)],
+    ) -> Result<()> {
+        let response = self.client.request_with_headers(method, path, body, headers).await?;
+        ApiClient::read_json_stream(response, |message: ProgressMessage| {
+            log_progress(&message);
+            match message.error {
+                Some(error) => Err(anyhow::anyhow!(error.trim().to_string())),
+                None => Ok(()),
+            }
+        })
+        .await
+    }
+
+    /// 对容器执行无请求体的操作 (start / stop / restart)
+    async fn container_action(&self, name: &str, action: &str) -> Result<()> {
+        let path = format!("/containers/{name}/{action}");
+        let (status, body) = self.client.send(Method::POST, &path, ApiBody::Empty).await?;
+
+        // 304: 容器已处于目标状态
+        if status.is_success() || status == StatusCode::NOT_MODIFIED {
+            return Ok(());
+        }
+        Err(api_error(status, &body))
+    }
+}
+
+#[async_trait]
+impl ContainerEngine for DockerApiEngine {
+    // ================================================ Engine Basic ================================================
+
+    async fn check_engine(&self) -> Result<()> {
+        if let ApiEndpoint::Unix(path) = self.client.endpoint() {
+            if !path.exists() {
+                return Err(anyhow::anyhow!("Docker API socket not found: {}", path.display()));
+            }
+        }
+        Ok(())
+    }
+
+    async fn ensure_running(&self) -> Result<()> {
+        self.client.expect_success(Method::GET, "/_ping", ApiBody::Empty).await.map_err(|e| {
+            anyhow::anyhow!("Docker daemon is not running. Please start Docker first. ({e})")
+        })?;
+        Ok(())
+    }
+
+    // ================================================ Image ================================================
+
+    async fn check_image_exists(&self, image: &str, tag: &str) -> Result<bool> {
+        let inspect: Option<serde_json::Value> =
+            self.client.get_json(&format!("/images/{image}:{tag}/json")).await?;
+        Ok(inspect.is_some())
+    }
+
+    async fn get_image_info(&self, image: &str, tag: &str) -> Result<EngineImageInfo> {
+        let inspect: Option<ImageInspect> =
+            self.client.get_json(&format!("/images/{image}:{tag}/json")).await?;
+
+        Ok(match inspect {
+            Some(inspect) => EngineImageInfo {
+                id: short_id(&inspect.id),
+                name: image.to_string(),
+                tag: tag.to_string(),
+                created_at: format_created(&inspect.created),
+                size: format_size(inspect.size),
+                status: ImageStatus::Built,
+            },
+            None => EngineImageInfo {
+                id: "".to_string(),
+                name: image.to_string(),
+                tag: tag.to_string(),
+                created_at: "".to_string(),
+                size: "".to_string(),
+                status: ImageStatus::Unbuilt,
+            },
+        })
+    }
+
+    async fn list_image_tags(&self, image: &str) -> Result<Vec<EngineImageInfo>> {
+        let filters = json!({ "reference": [image] }).to_string();
+        let summaries: Vec<ImageSummary> = self
+            .client
+            .get_json(&query("/images/json", &[("filters", &filters)]))
+            .await?
+            .unwrap_or_default();
+
+        let mut tags = vec![];
+        for summary in summaries {
+            for repo_tag in summary.repo_tags.unwrap_or_default() {
+                match repo_tag.rsplit_once(':') {
+                    Some((name, tag)) if name == image && tag != "<none>" => {
+                        tags.push(EngineImageInfo {
+                            id: short_id(&summary.id),
+                            name: image.to_string(),
+                            tag: tag.to_string(),
+                            created_at: format_timestamp(summary.created),
+                            size: format_size(summary.size),
+                            status: ImageStatus::Built,
+                        })
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(tags)
+    }
+
+    async fn get_image_labels(&self, image: &str, tag: &str) -> Result<BTreeMap<String, String>> {
+        let inspect: Option<serde_json::Value> =
+            self.client.get_json(&format!("/images/{image}:{tag}/json")).await?;
+
+        Ok(inspect
+            .and_then(|inspect| serde_json::from_value(inspect["Config"]["Labels"].clone()).ok())
+            .unwrap_or_default())
+    }
+
+    async fn pull_image(&self, image: &str, tag: &str) -> Result<()> {
+        let path = query("/images/create", &[("fromImage", image), ("tag", tag)]);
+        self.stream_progress(Method::POST, &path, ApiBody::Empty, &[]).await
+    }
+
+    async fn build_image(
+        &self,
+        name: &str,
+        tag: &str,
+        dockerfile: &str,
+        context: Option<&str>,
+        options: &ImageBuildOptions,
+    ) -> Result<()> {
+        let context = PathBuf::from(context.unwrap_or("."));
+        let (archive, dockerfile) = build_context_archive(&context, Path::new(dockerfile))?;
+
+        let path = build_query(&format!("{name}:{tag}"), &dockerfile, options)?;
+        self.stream_progress(Method::POST, &path, ApiBody::Tar(archive), &[]).await
+    }
+
+    async fn tag_image(
+        &self,
+        image: &str,
+        tag: &str,
+        repository: &str,
+        target_tag: &str,
+    ) -> Result<()> {
+        let path = query(
+            &format!("/images/{image}:{tag}/tag"),
+            &[("repo", repository), ("tag", target_tag)],
+        );
+        self.client.expect_success(Method::POST, &path, ApiBody::Empty).await?;
+        Ok(())
+    }
+
+    async fn push_image(
+        &self,
+        repository: &str,
+        tag: &str,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<()> {
+        let path = query(&format!("/images/{repository}/push"), &[("tag", tag)]);
+        let headers = [("X-Registry-Auth", registry_auth_header(auth)?)];
+        self.stream_progress(Method::POST, &path, ApiBody::Empty, &headers).await
+    }
+
+    async fn remove_image(&self, image: &str, tag: &str) -> Result<()> {
+        let body = self
+            .client
+            .expect_success(Method::DELETE, &format!("/images/{image}:{tag}"), ApiBody::Empty)
+            .await?;
+
+        let deleted: Vec<HashMap<String, String>> =
+            serde_json::from_slice(&body).unwrap_or_default();
+        for entry in deleted {
+            for (action, id) in entry {
+                tracing::info!("{}: {}", action, id);
+            }
+        }
+        Ok(())
+    }
+
+    // ================================================ Container ================================================
+
+    async fn check_container_exists(&self, name: &str) -> Result<bool> {
+        let inspect: Option<serde_json::Value> =
+            self.client.get_json(&format!("/containers/{name}/json")).await?;
+        Ok(inspect.is_some())
+    }
+
+    async fn create_container(&self, name: &str) -> Result<()> {
+        self.compose.create_container(name).await
+    }
+
+    async fn remove_container(&self, name: &str) -> Result<()> {
+        self.client
+            .expect_success(Method::DELETE, &format!("/containers/{name}"), ApiBody::Empty)
+            .await?;
+        tracing::info!("{}", name);
+        Ok(())
+    }
+
+    async fn start_container(&self, name: &str) -> Result<()> {
+        self.container_action(name, "start").await
+    }
+
+    async fn stop_container(&self, name: &str) -> Result<()> {
+        self.container_action(name, "stop").await
+    }
+
+    async fn restart_container(&self, name: &str) -> Result<()> {
+        tracing::info!(" --------- Restarting container: {} ---------", name);
+
+        self.container_action(name, "restart").await
+    }
+
+    async fn get_container_info(&self, name: &str) -> Result<EngineContainerInfo> {
+        let inspect: Option<ContainerInspect> = self
+            .client
+            .get_json(&query(&format!("/containers/{name}/json"), &[("size", "1")]))
+            .await?;
+        let service = ConfKitEngine::get_compose_service_config_by_container_name(name).await?;
+
+        let Some(inspect) = inspect else {
+            return Ok(EngineContainerInfo {
+                id: "".to_string(),
+                name: name.to_string(),
+                image: service.as_ref().map(|s| s.image.clone()).unwrap_or_default(),
+                created_at: "".to_string(),
+                size: "".to_string(),
+                working_dir: service.and_then(|s| s.working_dir),
+                status: ContainerStatus::Unbuilt,
+            });
+        };
+
+        let working_dir = match service.and_then(|s| s.working_dir) {
+            Some(dir) => Some(dir),
+            None if !inspect.config.working_dir.is_empty() => Some(inspect.config.working_dir),
+            None => None,
+        };
+        let size = match (inspect.size_rw, inspect.size_root_fs) {
+            (Some(rw), Some(root)) => {
+                format!("{} (virtual {})", format_size(rw), format_size(root))
+            }
+            (Some(rw), None) => format_size(rw),
+            _ => "".to_string(),
+        };
+
+        Ok(EngineContainerInfo {
+            id: short_id(&inspect.id),
+            name: name.to_string(),
+            image: inspect.config.image,
+            created_at: format_created(&inspect.created),
+            size,
+            working_dir,
+            status: parse_state(&inspect.state.status),
+        })
+    }
+
+    async fn execute_in_container(
+        &self,
+        container: &str,
+        shell: &str,
+        working_dir: &str,
+        cmd: &str,
+        environment: &HashMap<String, String>,
+        task_logger: &TaskLogger,
+    ) -> Result<i32> {
+        let env: Vec<String> =
+            environment.iter().map(|(key, value)| format!("{key}={value}")).collect();
+        let body = self
+            .client
+            .expect_success(
+                Method::POST,
+                &format!("/containers/{container}/exec"),
+                ApiBody::Json(json!({
+                    "AttachStdout": true,
+                    "AttachStderr": true,
+                    "Tty": false,
+                    "Env": env,
+                    "WorkingDir": working_dir,
+                    "Cmd": [shell, "-c", cmd],
+                })),
+            )
+            .await?;
+        let exec: ExecCreated = serde_json::from_slice(&body)?;
+
+        let response = self
+            .client
+            .request(
+                Method::POST,
+                &format!("/exec/{}/start", exec.id),
+                ApiBody::Json(json!({ "Detach": false, "Tty": false })),
+            )
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let mut body = vec![];
+            ApiClient::read_chunks(response, |chunk| {
+                body.extend_from_slice(chunk);
+                Ok(())
+            })
+            .await?;
+            return Err(api_error(status, &body));
+        }
+
+        let mut demuxer = StreamDemuxer::default();
+        ApiClient::read_chunks(response, |chunk| {
+            for (_, line) in demuxer.push(chunk) {
+                let _ = task_logger.info(&format!("    | {}", line));
+            }
+            Ok(())
+        })
+        .await?;
+        for (_, line) in demuxer.finish() {
+            let _ = task_logger.info(&format!("    | {}", line));
+        }
+
+        let inspect: ExecInspect = self
+            .client
+            .get_json(&format!("/exec/{}/json", exec.id))
+            .await?
+            .context("exec instance disappeared before its exit code was read")?;
+        inspect.exit_code.context("exec finished without an exit code")
+    }
+}
+
+/// 输出 build / pull 进度, 带进度条的下载状态仅在 debug 级别输出
+pub fn log_progress(message: &ProgressMessage) {
+    if let Some(stream) = &message.stream {
+        for line in stream.lines().filter(|line| !line.trim().is_empty()) {
+            tracing::info!("● {}", line);
+        }
+    }
+
+    if let Some(status) = &message.status {
+        let status = match &message.id {
+            Some(id) => format!("{id}: {status}"),
+            None => status.clone(),
+        };
+        match &message.progress {
+            Some(progress) => tracing::debug!("● {} {}", status, progress),
+            None => tracing::info!("● {}", status),
+        }
+    }
+
+    if let Some(error) = &message.error {
+        tracing::error!("✗ {}", error.trim());
+    }
+}
+
+/// 打包构建上下文 (遵循 .dockerignore), 返回 (tar 数据, 归档内的 Dockerfile 路径)
+///
+/// Dockerfile 在上下文之外时以固定名称加入归档。
+pub fn build_context_archive(context: &Path, dockerfile: &Path) -> Result<(Vec<u8>, String)> {
+    let mut builder = tar::Builder::new(vec![]);
+    builder.follow_symlinks(false);
+
+    let files = context_files(context)?;
+    for file in &files {
+        builder.append_path_with_name(context.join(file), file)?;
+    }
+
+    let inside = dockerfile
+        .canonicalize()
+        .ok()
+        .zip(context.canonicalize().ok())
+        .and_then(|(file, root)| file.strip_prefix(root).ok().map(Path::to_path_buf));
+    let dockerfile_name = match inside {
+        Some(relative) if !relative.as_os_str().is_empty() => {
+            // .dockerignore 排除了 Dockerfile 时仍需发送
+            if !files.contains(&relative) {
+                builder.append_path_with_name(dockerfile, &relative)?;
+            }
+            relative.to_string_lossy().replace('\\', "/")
+        }
+        _ => {
+            let content = std::fs::read(dockerfile)
+                .with_context(|| format!("Failed to read {}", dockerfile.display()))?;
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, EXTERNAL_DOCKERFILE, content.as_slice())?;
+            EXTERNAL_DOCKERFILE.to_string()
+        }
+    };
+
+    Ok((builder.into_inner()?, dockerfile_name))
+}
+
+/// `/build` 请求路径
+///
+/// 接口构建不经过 BuildKit 会话, 不支持构建密钥与多平台构建。
+pub fn build_query(image: &str, dockerfile: &str, options: &ImageBuildOptions) -> Result<String> {
+    if !options.secrets.is_empty() {
+        anyhow::bail!("Build secrets require BuildKit, use engine_mode: cli to build {image}");
+    }
+    if options.platforms.len() > 1 {
+        anyhow::bail!("Multi-platform builds require engine_mode: cli to build {image}");
+    }
+
+    let build_args = serde_json::to_string(&options.build_args)?;
+    let labels = serde_json::to_string(&options.labels)?;
+    let mut pairs = vec![("t", image), ("dockerfile", dockerfile), ("rm", "1")];
+    if !options.build_args.is_empty() {
+        pairs.push(("buildargs", &build_args));
+    }
+    if !options.labels.is_empty() {
+        pairs.push(("labels", &labels));
+    }
+    if let Some(target) = &options.target {
+        pairs.push(("target", target));
+    }
+    if let Some(platform) = options.platforms.first() {
+        pairs.push(("platform", platform));
+    }
+    if options.no_cache {
+        pairs.push(("nocache", "1"));
+    }
+    if options.pull {
+        pairs.push(("pull", "1"));
+    }
+
+    Ok(query("/build", &pairs))
+}
+
+/// 推送镜像时的 `X-Registry-Auth` 请求头: base64url 编码的 JSON, 无凭据时为 `{}`
+pub fn registry_auth_header(auth: Option<&RegistryAuth>) -> Result<String> {
+    let value = match auth {
+        Some(auth) => json!({
+            "username": auth.username,
+            "password": auth.password,
+            "serveraddress": auth.server_address,
+        }),
+        None => json!({}),
+    };
+    Ok(URL_SAFE.encode(serde_json::to_vec(&value)?))
+}
+
+/// 容器状态 (inspect 中的 State.Status)
+pub fn parse_state(status: &str) -> ContainerStatus {
+    match status {
+        "running" => ContainerStatus::Up,
+        "exited" => ContainerStatus::Exited,
+        "created" => ContainerStatus::Created,
+        "paused" => ContainerStatus::Paused,
+        "restarting" => ContainerStatus::Restarting,
+        "dead" => ContainerStatus::Dead,
+        "removing" => ContainerStatus::Removing,
+        _ => ContainerStatus::Unbuilt,
+    }
+}
+
+/// 与命令行一致的 12 位短 ID
+fn short_id(id: &str) -> String {
+    let id = id.strip_prefix("sha256:").unwrap_or(id);
+    id.chars().take(12).collect()
+}
+
+/// RFC3339 时间转换为命令行的展示格式 (UTC)
+fn format_created(created: &str) -> String {
+    match DateTime::parse_from_rfc3339(created) {
+        Ok(time) => time.with_timezone(&Utc).format("%Y-%m-%d %H:%M:%S %z UTC").to_string(),
+        Err(_) => created.to_string(),
+    }
+}
+
+/// unix 时间戳转换为命令行的展示格式 (UTC)
+fn format_timestamp(created: i64) -> String {
+    match DateTime::from_timestamp(created, 0) {
+        Some(time) => time.format("%Y-%m-%d %H:%M:%S %z UTC").to_string(),
+        None => "".to_string(),
+    }
+}
+
+/// 与命令行一致的十进制大小, 保留 3 位有效数字, 如 "120MB" / "1.23GB"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "kB", "MB", "GB", "TB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+
+    let digits = if value >= 100.0 {
+        0
+    } else if value >= 10.0 {
+        1
+    } else {
+        2
+    };
+    let number = format!("{value:.digits$}");
+    let number = if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        number
+    };
+    format!("{number}{}", UNITS[unit])
+}
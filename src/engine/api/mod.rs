@@ -0,0 +1,7 @@
+//! Author: xiaoYown
+//! Created: 2026-10-19
+//! Description: Engine implementations over docker-compatible HTTP APIs
+
+pub mod client;
+pub mod docker;
+pub mod podman;
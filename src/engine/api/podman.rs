@@ -0,0 +1,488 @@
+//! Author: xiaoYown
+//! Created: 2026-10-19
+//! Description: Podman engine implementation over the podman system service socket
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use hyper::{Method, StatusCode};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{
+    engine::{
+        api::client::{api_error, query, ApiBody, ApiClient, ApiEndpoint},
+        api::docker::DockerApiEngine,
+        cli::CliEngine,
+        traits::ContainerEngine,
+        ConfKitEngine,
+    },
+    infra::config::ConfKitConfigLoader,
+    infra::logger::TaskLogger,
+    types::config::{
+        EngineContainerInfo, EngineImageInfo, EngineServiceConfig, ImageBuildOptions, RegistryAuth,
+    },
+};
+
+/// root 运行时的 podman socket
+pub const ROOTFUL_PODMAN_SOCKET: &str = "/run/podman/podman.sock";
+
+/// 查找 podman socket
+///
+/// 优先使用 `CONTAINER_HOST`; rootless 时为 `$XDG_RUNTIME_DIR/podman/podman.sock`
+/// (未设置时为 `/run/user/<uid>/podman/podman.sock`), root 时为 `/run/podman/podman.sock`。
+pub fn discover_socket(
+    container_host: Option<&str>,
+    runtime_dir: Option<&str>,
+    uid: u32,
+) -> Result<ApiEndpoint> {
+    if let Some(host) = container_host.map(str::trim).filter(|host| !host.is_empty()) {
+        return ApiEndpoint::parse(host);
+    }
+    if uid == 0 {
+        return Ok(ApiEndpoint::Unix(PathBuf::from(ROOTFUL_PODMAN_SOCKET)));
+    }
+
+    let runtime_dir = match runtime_dir.filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(format!("/run/user/{uid}")),
+    };
+    Ok(ApiEndpoint::Unix(runtime_dir.join("podman").join("podman.sock")))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PodInspect {
+    #[serde(default, rename = "InfraContainerID")]
+    infra_container_id: String,
+    #[serde(default)]
+    containers: Vec<PodContainer>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PodContainer {
+    id: String,
+}
+
+/// 通过 podman 的 Docker 兼容接口与 libpod 接口操作镜像与容器
+///
+/// 配置了 `engine_compose.pod` 时, 构建器容器按 compose 服务定义经 libpod 接口创建并加入该 pod,
+/// 端口映射声明在 pod 上; 否则仍调用 `podman compose` 创建容器。
+pub struct PodmanApiEngine {
+    client: ApiClient,
+    compat: DockerApiEngine,
+}
+
+impl PodmanApiEngine {
+    pub fn new(client: ApiClient) -> Self {
+        Self { compat: DockerApiEngine::with_compose(client.clone(), CliEngine::podman()), client }
+    }
+
+    /// 使用 `CONTAINER_HOST` 或按当前用户查找 socket
+    pub fn from_env() -> Result<Self> {
+        let container_host = std::env::var("CONTAINER_HOST").ok();
+        let runtime_dir = std::env::var("XDG_RUNTIME_DIR").ok();
+        // SAFETY: geteuid 无副作用且总是成功
+        let uid = unsafe { libc::geteuid() };
+
+        let endpoint = discover_socket(container_host.as_deref(), runtime_dir.as_deref(), uid)?;
+        Ok(Self::new(ApiClient::new(endpoint)))
+    }
+
+    /// libpod exists 接口: 204 存在, 404 不存在
+    async fn exists(&self, path: &str) -> Result<bool> {
+        let (status, body) = self.client.send(Method::GET, path, ApiBody::Empty).await?;
+        match status {
+            StatusCode::NO_CONTENT | StatusCode::OK => Ok(true),
+            StatusCode::NOT_FOUND => Ok(false),
+            status => Err(api_error(status, &body)),
+        }
+    }
+
+    /// pod 不存在时创建, 端口映射取自所有 compose 服务
+    pub async fn ensure_pod(&self, pod: &str, services: &[EngineServiceConfig]) -> Result<()> {
+        if self.exists(&format!("/libpod/pods/{pod}/exists")).await? {
+            return Ok(());
+        }
+
+        tracing::info!("Creating pod: {}", pod);
+        self.client
+            .expect_success(
+                Method::POST,
+                "/libpod/pods/create",
+                ApiBody::Json(pod_spec(pod, services)),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// 在 pod 中按 compose 服务定义创建容器
+    pub async fn create_in_pod(
+        &self,
+        pod: &str,
+        service: &EngineServiceConfig,
+        services: &[EngineServiceConfig],
+        compose_dir: &Path,
+    ) -> Result<()> {
+        self.ensure_pod(pod, services).await?;
+
+        let spec = container_spec(service, pod, compose_dir)?;
+        self.client
+            .expect_success(Method::POST, "/libpod/containers/create", ApiBody::Json(spec))
+            .await?;
+        tracing::info!("Container {} created in pod {}", service.container_name, pod);
+        Ok(())
+    }
+
+    /// pod 中只剩 infra 容器时移除 pod
+    pub async fn remove_pod_if_empty(&self, pod: &str) -> Result<()> {
+        let Some(inspect) =
+            self.client.get_json::<PodInspect>(&format!("/libpod/pods/{pod}/json")).await?
+        else {
+            return Ok(());
+        };
+
+        let members = inspect
+            .containers
+            .iter()
+            .filter(|container| container.id != inspect.infra_container_id)
+            .count();
+        if members > 0 {
+            return Ok(());
+        }
+
+        tracing::info!("Removing empty pod: {}", pod);
+        self.client
+            .expect_success(
+                Method::DELETE,
+                &query(&format!("/libpod/pods/{pod}"), &[("force", "true")]),
+                ApiBody::Empty,
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ContainerEngine for PodmanApiEngine {
+    // ================================================ Engine Basic ================================================
+
+    async fn check_engine(&self) -> Result<()> {
+        if let ApiEndpoint::Unix(path) = self.client.endpoint() {
+            if !path.exists() {
+                return Err(anyhow::anyhow!(
+                    "Podman API socket not found: {} (start it with `systemctl --user enable --now podman.socket` or `podman system service --time=0`)",
+                    path.display()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    async fn ensure_running(&self) -> Result<()> {
+        self.client.expect_success(Method::GET, "/libpod/_ping", ApiBody::Empty).await.map_err(
+            |e| anyhow::anyhow!("Podman is not running. Please start Podman first. ({e})"),
+        )?;
+        Ok(())
+    }
+
+    // ================================================ Image ================================================
+
+    async fn check_image_exists(&self, image: &str, tag: &str) -> Result<bool> {
+        self.exists(&format!("/libpod/images/{image}:{tag}/exists")).await
+    }
+
+    async fn get_image_info(&self, image: &str, tag: &str) -> Result<EngineImageInfo> {
+        self.compat.get_image_info(image, tag).await
+    }
+
+    async fn list_image_tags(&self, image: &str) -> Result<Vec<EngineImageInfo>> {
+        self.compat.list_image_tags(image).await
+    }
+
+    async fn get_image_labels(&self, image: &str, tag: &str) -> Result<BTreeMap<String, String>> {
+        self.compat.get_image_labels(image, tag).await
+    }
+
+    async fn pull_image(&self, image: &str, tag: &str) -> Result<()> {
+        self.compat.pull_image(image, tag).await
+    }
+
+    async fn build_image(
+        &self,
+        name: &str,
+        tag: &str,
+        dockerfile: &str,
+        context: Option<&str>,
+        options: &ImageBuildOptions,
+    ) -> Result<()> {
+        self.compat.build_image(name, tag, dockerfile, context, options).await
+    }
+
+    async fn tag_image(
+        &self,
+        image: &str,
+        tag: &str,
+        repository: &str,
+        target_tag: &str,
+    ) -> Result<()> {
+        self.compat.tag_image(image, tag, repository, target_tag).await
+    }
+
+    async fn push_image(
+        &self,
+        repository: &str,
+        tag: &str,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<()> {
+        self.compat.push_image(repository, tag, auth).await
+    }
+
+    async fn remove_image(&self, image: &str, tag: &str) -> Result<()> {
+        self.compat.remove_image(image, tag).await
+    }
+
+    // ================================================ Container ================================================
+
+    async fn check_container_exists(&self, name: &str) -> Result<bool> {
+        self.exists(&format!("/libpod/containers/{name}/exists")).await
+    }
+
+    async fn create_container(&self, name: &str) -> Result<()> {
+        let compose = ConfKitConfigLoader::get_config().engine_compose;
+        let Some(pod) = compose.pod else {
+            return self.compat.create_container(name).await;
+        };
+
+        let services = ConfKitEngine::get_compose_services().await?;
+        let service = services
+            .iter()
+            .find(|service| service.container_name == name || service.service_name == name)
+            .ok_or_else(|| anyhow::anyhow!("Service not found in compose file: {name}"))?;
+        let compose_dir = Path::new(&compose.file).parent().unwrap_or(Path::new(""));
+
+        self.create_in_pod(&pod, service, &services, compose_dir).await
+    }
+
+    async fn remove_container(&self, name: &str) -> Result<()> {
+        self.compat.remove_container(name).await?;
+
+        match ConfKitConfigLoader::get_config().engine_compose.pod {
+            Some(pod) => self.remove_pod_if_empty(&pod).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn start_container(&self, name: &str) -> Result<()> {
+        self.compat.start_container(name).await
+    }
+
+    async fn stop_container(&self, name: &str) -> Result<()> {
+        self.compat.stop_container(name).await
+    }
+
+    async fn restart_container(&self, name: &str) -> Result<()> {
+        self.compat.restart_container(name).await
+    }
+
+    async fn get_container_info(&self, name: &str) -> Result<EngineContainerInfo> {
+        self.compat.get_container_info(name).await
+    }
+
+    async fn execute_in_container(
+        &self,
+        container: &str,
+        shell: &str,
+        working_dir: &str,
+        cmd: &str,
+        environment: &HashMap<String, String>,
+        task_logger: &TaskLogger,
+    ) -> Result<i32> {
+        self.compat
+            .execute_in_container(container, shell, working_dir, cmd, environment, task_logger)
+            .await
+    }
+}
+
+/// libpod pod 创建参数
+pub fn pod_spec(pod: &str, services: &[EngineServiceConfig]) -> Value {
+    let mut portmappings = vec![];
+    for service in services {
+        for port in service.ports.iter().flatten() {
+            match parse_port(port) {
+                Some(mapping) => portmappings.push(mapping),
+                None => tracing::warn!(
+                    "Unsupported port in service '{}': {}",
+                    service.service_name,
+                    port
+                ),
+            }
+        }
+    }
+
+    json!({ "name": pod, "portmappings": portmappings })
+}
+
+/// libpod 容器创建参数, 由 compose 服务定义转换
+pub fn container_spec(
+    service: &EngineServiceConfig,
+    pod: &str,
+    compose_dir: &Path,
+) -> Result<Value> {
+    let name = if service.container_name.is_empty() {
+        &service.service_name
+    } else {
+        &service.container_name
+    };
+
+    let mut mounts = vec![];
+    let mut volumes = vec![];
+    for volume in service.volumes.iter().flatten() {
+        match parse_volume(volume, compose_dir)? {
+            Some(VolumeMount::Bind { source, target, read_only }) => mounts.push(json!({
+                "type": "bind",
+                "source": source,
+                "destination": target,
+                "options": if read_only { vec!["rbind", "ro"] } else { vec!["rbind"] },
+            })),
+            Some(VolumeMount::Named { name, target, read_only }) => volumes.push(json!({
+                "Name": name,
+                "Dest": target,
+                "Options": if read_only { vec!["ro"] } else { vec![] },
+            })),
+            None => tracing::warn!("Unsupported volume in service '{}': {:?}", name, volume),
+        }
+    }
+
+    let mut spec = json!({
+        "name": name,
+        "image": service.image,
+        "pod": pod,
+        "env": service.environment.clone().unwrap_or_default(),
+        "mounts": mounts,
+        "volumes": volumes,
+    });
+    if let Some(dir) = &service.working_dir {
+        spec["work_dir"] = json!(dir);
+    }
+    if let Some(command) = service.other.get("command").and_then(command_args) {
+        spec["command"] = json!(command);
+    }
+    if let Some(entrypoint) = service.other.get("entrypoint").and_then(command_args) {
+        spec["entrypoint"] = json!(entrypoint);
+    }
+    if let Some(tty) = service.other.get("tty").and_then(serde_yaml::Value::as_bool) {
+        spec["terminal"] = json!(tty);
+    }
+    if let Some(stdin) = service.other.get("stdin_open").and_then(serde_yaml::Value::as_bool) {
+        spec["stdin"] = json!(stdin);
+    }
+
+    Ok(spec)
+}
+
+/// compose 中的挂载
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VolumeMount {
+    /// 宿主机路径, 相对路径基于 compose 文件所在目录
+    Bind { source: PathBuf, target: String, read_only: bool },
+    /// 命名卷
+    Named { name: String, target: String, read_only: bool },
+}
+
+/// 解析 compose 挂载 (短格式 `src:dst[:ro]` 或长格式 type/source/target)
+pub fn parse_volume(value: &serde_yaml::Value, compose_dir: &Path) -> Result<Option<VolumeMount>> {
+    let (source, target, read_only) = match value {
+        serde_yaml::Value::String(volume) => {
+            let parts: Vec<&str> = volume.split(':').collect();
+            match parts.as_slice() {
+                [source, target] => (source.to_string(), target.to_string(), false),
+                [source, target, mode] => {
+                    (source.to_string(), target.to_string(), mode.split(',').any(|m| m == "ro"))
+                }
+                _ => return Ok(None),
+            }
+        }
+        serde_yaml::Value::Mapping(mapping) => {
+            let field = |key: &str| mapping.get(key).and_then(serde_yaml::Value::as_str);
+            let (Some(source), Some(target)) = (field("source"), field("target")) else {
+                return Ok(None);
+            };
+            let read_only =
+                mapping.get("read_only").and_then(serde_yaml::Value::as_bool).unwrap_or(false);
+            (source.to_string(), target.to_string(), read_only)
+        }
+        _ => return Ok(None),
+    };
+
+    let is_path = source.starts_with('.') || source.starts_with('/') || source.starts_with('~');
+    if !is_path {
+        return Ok(Some(VolumeMount::Named { name: source, target, read_only }));
+    }
+
+    let source = match source.strip_prefix("~/") {
+        Some(rest) => PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(rest),
+        None => compose_dir.join(source.trim_start_matches("./")),
+    };
+    Ok(Some(VolumeMount::Bind { source: std::path::absolute(source)?, target, read_only }))
+}
+
+/// 解析端口映射 `[ip:]host:container[/protocol]` 或 `container[/protocol]`
+///
+/// 端口可写作范围 `8000-8010`, 宿主机与容器的范围长度需一致, 转换为 libpod 的 `range`
+pub fn parse_port(port: &str) -> Option<Value> {
+    let (mapping, protocol) = port.split_once('/').unwrap_or((port, "tcp"));
+    let parts: Vec<&str> = mapping.split(':').collect();
+    let (host_ip, host_port, container_port) = match parts.as_slice() {
+        [container] => ("", "", *container),
+        [host, container] => ("", *host, *container),
+        [ip, host, container] => (*ip, *host, *container),
+        _ => return None,
+    };
+
+    let (container_port, range) = parse_port_range(container_port)?;
+    let mut value = json!({ "container_port": container_port, "protocol": protocol });
+    // 未指定宿主机端口时由 podman 随机分配
+    if !host_port.is_empty() {
+        let (host_port, host_range) = parse_port_range(host_port)?;
+        if host_range != range {
+            return None;
+        }
+        value["host_port"] = json!(host_port);
+    }
+    if range > 1 {
+        value["range"] = json!(range);
+    }
+    if !host_ip.is_empty() {
+        value["host_ip"] = json!(host_ip);
+    }
+    Some(value)
+}
+
+/// 解析 `port` 或 `start-end`, 返回起始端口与端口数
+fn parse_port_range(port: &str) -> Option<(u16, u16)> {
+    match port.split_once('-') {
+        Some((start, end)) => {
+            let start: u16 = start.parse().ok()?;
+            let end: u16 = end.parse().ok()?;
+            (end >= start).then(|| (start, end - start + 1))
+        }
+        None => Some((port.parse().ok()?, 1)),
+    }
+}
+
+/// compose 的 command / entrypoint: 字符串按空白切分, 列表原样使用
+fn command_args(value: &serde_yaml::Value) -> Option<Vec<String>> {
+    match value {
+        serde_yaml::Value::String(command) => {
+            Some(command.split_whitespace().map(str::to_string).collect())
+        }
+        serde_yaml::Value::Sequence(items) => {
+            Some(items.iter().filter_map(|item| item.as_str().map(str::to_string)).collect())
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,629 @@
+//! Author: xiaoYown
+//! Created: 2026-10-19
+//! Description: Shared engine implementation for docker-compatible CLIs (docker, podman, nerdctl)
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    core::executor::context::resolve_container_variables,
+    engine::{traits::ContainerEngine, ConfKitEngine},
+    infra::config::ConfKitConfigLoader,
+    infra::logger::TaskLogger,
+    infra::registry::DockerConfig,
+    types::common::LogCallback,
+    types::config::{
+        BuildSecret, ContainerStatus, EngineContainerInfo, EngineImageInfo, EngineServiceConfig,
+        ImageBuildOptions, ImageStatus, RegistryAuth,
+    },
+    utils::command::CommandUtil,
+};
+
+/// `images --format` 输出列: ID, Tag, CreatedAt, Size
+const IMAGE_FORMAT: &str = "{{.ID}}\t{{.Tag}}\t{{.CreatedAt}}\t{{.Size}}";
+/// `ps --format` 输出列: ID, Image, Status, CreatedAt, Size
+const CONTAINER_FORMAT: &str = "{{.ID}}\t{{.Image}}\t{{.Status}}\t{{.CreatedAt}}\t{{.Size}}";
+
+/// 基于命令行的引擎实现
+///
+/// docker, podman 与 nerdctl 的命令及 `--format` 输出一致, 仅可执行文件与提示信息不同。
+#[derive(Debug, Clone)]
+pub struct CliEngine {
+    /// 可执行文件
+    program: String,
+    /// 展示名称
+    label: &'static str,
+    /// 引擎未运行时的提示
+    not_running: &'static str,
+    /// 执行 compose 命令时附加的环境变量
+    compose_env: &'static [(&'static str, &'static str)],
+    /// 推送凭据的临时存放方式
+    auth_store: AuthStore,
+}
+
+/// 登录凭据的存放位置, 推送时指向临时目录, 避免凭据留在引擎的凭据存储中
+#[derive(Debug, Clone, Copy)]
+enum AuthStore {
+    /// `DOCKER_CONFIG` 指定的配置目录 (docker, nerdctl)
+    DockerConfig,
+    /// `REGISTRY_AUTH_FILE` 指定的认证文件 (podman)
+    AuthFile,
+}
+
+impl CliEngine {
+    pub fn docker() -> Self {
+        Self {
+            program: "docker".to_string(),
+            label: "Docker",
+            not_running: "Docker daemon is not running. Please start Docker first.",
+            compose_env: &[],
+            auth_store: AuthStore::DockerConfig,
+        }
+    }
+
+    pub fn podman() -> Self {
+        Self {
+            program: "podman".to_string(),
+            label: "Podman",
+            not_running: "Podman is not running. Please start Podman first.",
+            // 抑制 podman compose 的外部 provider 横幅警告
+            compose_env: &[("PODMAN_COMPOSE_WARNING_LOGS", "false")],
+            auth_store: AuthStore::AuthFile,
+        }
+    }
+
+    /// nerdctl 通过 containerd 运行容器, 镜像构建依赖 buildkitd;
+    /// containerd 命名空间沿用 nerdctl 自身的 `CONTAINERD_NAMESPACE` 环境变量
+    pub fn nerdctl() -> Self {
+        Self {
+            program: "nerdctl".to_string(),
+            label: "nerdctl",
+            not_running: "containerd is not running. Please start containerd (and buildkitd for image builds) first.",
+            compose_env: &[],
+            auth_store: AuthStore::DockerConfig,
+        }
+    }
+
+    /// 替换可执行文件, 如不在 PATH 中的安装路径或测试桩程序
+    #[allow(dead_code)]
+    pub fn with_program(mut self, program: impl Into<String>) -> Self {
+        self.program = program.into();
+        self
+    }
+
+    fn command(&self) -> Command {
+        Command::new(&self.program)
+    }
+
+    fn async_command(&self) -> tokio::process::Command {
+        tokio::process::Command::new(&self.program)
+    }
+
+    /// 退出码非 0 时返回错误, 错误中只包含子命令, 避免泄露构建参数等内容
+    fn ensure_success(&self, command: &tokio::process::Command, exit_code: i32) -> Result<()> {
+        if exit_code == 0 {
+            return Ok(());
+        }
+
+        let action = command.as_std().get_args().next().unwrap_or_default().to_string_lossy();
+        Err(anyhow::anyhow!("{} {} failed with exit code {}", self.label, action, exit_code))
+    }
+
+    /// 执行命令并将输出写入日志, stderr 中含 ERROR/FAILED 的行按错误输出
+    async fn run_with_progress(&self, mut command: tokio::process::Command) -> Result<()> {
+        let exit_code = CommandUtil::execute_command_with_output(
+            &mut command,
+            Some(Box::new(|line| tracing::info!("{}", line))),
+            Some(Box::new(|line| {
+                // 根据内容判断是进度还是错误
+                if line.contains("ERROR") || line.contains("FAILED") {
+                    tracing::error!("✗ {}", line);
+                } else {
+                    tracing::info!("● {}", line);
+                }
+            })),
+        )
+        .await?;
+
+        self.ensure_success(&command, exit_code)
+    }
+
+    /// 以 `--password-stdin` 登录镜像仓库, 凭据写入 auth_dir, 密码不出现在进程参数中
+    async fn login(&self, auth: &RegistryAuth, auth_dir: &Path) -> Result<()> {
+        let mut child = self
+            .auth_command(auth_dir)
+            .args(["login", &auth.server_address, "--username", &auth.username])
+            .arg("--password-stdin")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(auth.password.as_bytes()).await?;
+        }
+
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "{} login to {} failed: {}",
+                self.label,
+                auth.server_address,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+
+    /// 准备临时登录目录: docker 配置沿用当前配置中凭据以外的设置与 contexts
+    fn prepare_auth_dir(&self, auth_dir: &Path) -> Result<()> {
+        if let AuthStore::DockerConfig = self.auth_store {
+            let current = DockerConfig::default_path();
+            DockerConfig::load(&current)?
+                .without_credentials(auth_dir.join("config.json"))
+                .save()?;
+
+            #[cfg(unix)]
+            if let Some(contexts) = current.parent().map(|dir| dir.join("contexts")) {
+                if contexts.is_dir() {
+                    std::os::unix::fs::symlink(contexts, auth_dir.join("contexts"))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 凭据读写指向 auth_dir 的命令
+    fn auth_command(&self, auth_dir: &Path) -> tokio::process::Command {
+        let mut command = self.async_command();
+        match self.auth_store {
+            AuthStore::DockerConfig => command.env("DOCKER_CONFIG", auth_dir),
+            AuthStore::AuthFile => command.env("REGISTRY_AUTH_FILE", auth_dir.join("auth.json")),
+        };
+        command
+    }
+
+    /// 执行 `<program> <action> <name>`
+    async fn run_simple(&self, args: &[&str], stderr_as_error: bool) -> Result<()> {
+        let mut command = self.async_command();
+        command.args(args);
+
+        let stderr_callback: LogCallback = if stderr_as_error {
+            Box::new(|line| tracing::error!("{}", line))
+        } else {
+            Box::new(|line| tracing::info!("{}", line))
+        };
+
+        let exit_code = CommandUtil::execute_command_with_output(
+            &mut command,
+            Some(Box::new(|line| tracing::info!("{}", line))),
+            Some(stderr_callback),
+        )
+        .await?;
+
+        self.ensure_success(&command, exit_code)
+    }
+}
+
+#[async_trait]
+impl ContainerEngine for CliEngine {
+    // ================================================ Engine Basic ================================================
+
+    async fn check_engine(&self) -> Result<()> {
+        if self.command().arg("--version").output().is_err() {
+            return Err(anyhow::anyhow!("{} not installed", self.label));
+        }
+
+        Ok(())
+    }
+
+    async fn ensure_running(&self) -> Result<()> {
+        let output = self.command().arg("info").output()?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(self.not_running));
+        }
+        Ok(())
+    }
+
+    // ================================================ Image ================================================
+
+    async fn check_image_exists(&self, image: &str, tag: &str) -> Result<bool> {
+        let output =
+            self.command().arg("images").arg("-q").arg(format!("{image}:{tag}")).output()?;
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to check image existence: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        // 空输出表示镜像不存在
+        Ok(!String::from_utf8_lossy(&output.stdout).trim().is_empty())
+    }
+
+    async fn get_image_info(&self, image: &str, tag: &str) -> Result<EngineImageInfo> {
+        let output = self
+            .command()
+            .arg("images")
+            .arg("--format")
+            .arg(IMAGE_FORMAT)
+            .arg(format!("{image}:{tag}"))
+            .output()?;
+
+        if !output.status.success() {
+            return Ok(parse_image_output(image, tag, ""));
+        }
+
+        Ok(parse_image_output(image, tag, &String::from_utf8_lossy(&output.stdout)))
+    }
+
+    async fn list_image_tags(&self, image: &str) -> Result<Vec<EngineImageInfo>> {
+        let output =
+            self.command().arg("images").arg("--format").arg(IMAGE_FORMAT).arg(image).output()?;
+
+        if !output.status.success() {
+            return Ok(vec![]);
+        }
+
+        Ok(parse_image_list_output(image, &String::from_utf8_lossy(&output.stdout)))
+    }
+
+    async fn get_image_labels(&self, image: &str, tag: &str) -> Result<BTreeMap<String, String>> {
+        let output = self
+            .command()
+            .arg("image")
+            .arg("inspect")
+            .arg("--format")
+            .arg("{{json .Config.Labels}}")
+            .arg(format!("{image}:{tag}"))
+            .output()?;
+
+        if !output.status.success() {
+            return Ok(BTreeMap::new());
+        }
+
+        Ok(parse_labels_output(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    async fn pull_image(&self, image: &str, tag: &str) -> Result<()> {
+        let mut command = self.async_command();
+        command.arg("pull").arg(format!("{image}:{tag}"));
+
+        self.run_with_progress(command).await
+    }
+
+    async fn build_image(
+        &self,
+        name: &str,
+        tag: &str,
+        dockerfile: &str,
+        context: Option<&str>,
+        options: &ImageBuildOptions,
+    ) -> Result<()> {
+        let mut command = self.async_command();
+        command.args(build_command_args(name, tag, dockerfile, context, options));
+
+        self.run_with_progress(command).await
+    }
+
+    async fn tag_image(
+        &self,
+        image: &str,
+        tag: &str,
+        repository: &str,
+        target_tag: &str,
+    ) -> Result<()> {
+        self.run_simple(
+            &["tag", &format!("{image}:{tag}"), &format!("{repository}:{target_tag}")],
+            true,
+        )
+        .await
+    }
+
+    async fn push_image(
+        &self,
+        repository: &str,
+        tag: &str,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<()> {
+        let Some(auth) = auth else {
+            let mut command = self.async_command();
+            command.arg("push").arg(format!("{repository}:{tag}"));
+            return self.run_with_progress(command).await;
+        };
+
+        // 在临时目录中登录并推送, 目录随 auth_dir 释放删除, 凭据不会留在引擎的凭据存储中
+        let auth_dir = tempfile::tempdir()?;
+        self.prepare_auth_dir(auth_dir.path())?;
+        self.login(auth, auth_dir.path()).await?;
+
+        let mut command = self.auth_command(auth_dir.path());
+        command.arg("push").arg(format!("{repository}:{tag}"));
+        self.run_with_progress(command).await
+    }
+
+    async fn remove_image(&self, image: &str, tag: &str) -> Result<()> {
+        self.run_simple(&["rmi", &format!("{image}:{tag}")], true).await
+    }
+
+    // ================================================ Container ================================================
+
+    async fn check_container_exists(&self, name: &str) -> Result<bool> {
+        let output = self
+            .command()
+            .arg("ps")
+            .arg("-a")
+            .arg("--filter")
+            .arg(format!("name=^{name}$"))
+            .arg("--quiet")
+            .output()?;
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to check container existence: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        // 输出不为空即包含有效的容器ID
+        Ok(!String::from_utf8_lossy(&output.stdout).trim().is_empty())
+    }
+
+    async fn create_container(&self, name: &str) -> Result<()> {
+        let confkit_config = ConfKitConfigLoader::get_config();
+        let engine_compose_file_path = confkit_config.engine_compose.file;
+        let project_name = confkit_config.engine_compose.project;
+
+        let mut command = self.async_command();
+        command.envs(self.compose_env.iter().copied());
+        command
+            .arg("compose")
+            .arg("-p")
+            .arg(project_name)
+            .arg("-f")
+            .arg(engine_compose_file_path.as_str())
+            .arg("create")
+            .arg(name);
+
+        let exit_code = CommandUtil::execute_command_with_output(
+            &mut command,
+            Some(Box::new(|line| tracing::info!("{}", line))),
+            Some(Box::new(|line| tracing::info!("{}", line))),
+        )
+        .await?;
+
+        self.ensure_success(&command, exit_code)
+    }
+
+    async fn remove_container(&self, name: &str) -> Result<()> {
+        self.run_simple(&["rm", name], false).await
+    }
+
+    async fn start_container(&self, name: &str) -> Result<()> {
+        self.run_simple(&["start", name], true).await
+    }
+
+    async fn stop_container(&self, name: &str) -> Result<()> {
+        self.run_simple(&["stop", name], true).await
+    }
+
+    async fn restart_container(&self, name: &str) -> Result<()> {
+        tracing::info!(" --------- Restarting container: {} ---------", name);
+
+        self.run_simple(&["restart", name], true).await
+    }
+
+    async fn get_container_info(&self, name: &str) -> Result<EngineContainerInfo> {
+        if !self.check_container_exists(name).await? {
+            return Ok(unbuilt_container(name, None));
+        }
+
+        let service_config =
+            ConfKitEngine::get_compose_service_config_by_container_name(name).await?;
+        let service_config = service_config.unwrap();
+
+        tracing::debug!("name: {name}, image: {}", service_config.image);
+
+        let output = self
+            .command()
+            .arg("ps")
+            .arg("-a")
+            .arg("--filter")
+            .arg(format!("name={name}"))
+            .arg("--format")
+            .arg(CONTAINER_FORMAT)
+            .output()?;
+
+        if !output.status.success() {
+            return Ok(unbuilt_container(name, Some(service_config)));
+        }
+
+        Ok(parse_container_output(name, &String::from_utf8_lossy(&output.stdout), service_config))
+    }
+
+    async fn execute_in_container(
+        &self,
+        container: &str,
+        shell: &str,
+        working_dir: &str,
+        cmd: &str,
+        environment: &HashMap<String, String>,
+        task_logger: &TaskLogger,
+    ) -> Result<i32> {
+        let mut command = self.async_command();
+
+        command.args(["exec", "-i"]);
+
+        resolve_container_variables(&mut command, environment);
+
+        command.args(["-w", working_dir]);
+
+        command.args([container, shell, "-c", cmd]);
+
+        // 创建回调
+        let stdout_callback: Option<LogCallback> = {
+            let task_logger = task_logger.clone();
+            Some(Box::new(move |line| {
+                let _ = task_logger.info(&format!("    | {}", line));
+            }))
+        };
+
+        let stderr_callback: Option<LogCallback> = {
+            let task_logger = task_logger.clone();
+            Some(Box::new(move |line| {
+                let _ = task_logger.info(&format!("    | {}", line));
+            }))
+        };
+
+        CommandUtil::execute_command_with_output(&mut command, stdout_callback, stderr_callback)
+            .await
+    }
+}
+
+/// `build` 命令参数, 构建上下文放在最后
+pub fn build_command_args(
+    name: &str,
+    tag: &str,
+    dockerfile: &str,
+    context: Option<&str>,
+    options: &ImageBuildOptions,
+) -> Vec<String> {
+    let mut args: Vec<String> =
+        vec!["build".into(), "-t".into(), format!("{name}:{tag}"), "-f".into(), dockerfile.into()];
+
+    for (key, value) in &options.build_args {
+        args.extend(["--build-arg".into(), format!("{key}={value}")]);
+    }
+    if let Some(target) = &options.target {
+        args.extend(["--target".into(), target.clone()]);
+    }
+    for (key, value) in &options.labels {
+        args.extend(["--label".into(), format!("{key}={value}")]);
+    }
+    if !options.platforms.is_empty() {
+        args.extend(["--platform".into(), options.platforms.join(",")]);
+    }
+    if options.no_cache {
+        args.push("--no-cache".into());
+    }
+    if options.pull {
+        args.push("--pull".into());
+    }
+    for secret in &options.secrets {
+        let secret = match secret {
+            BuildSecret::File { id, path } => format!("id={id},src={path}"),
+            BuildSecret::Env { id, var } => format!("id={id},env={var}"),
+        };
+        args.extend(["--secret".into(), secret]);
+    }
+
+    args.push(context.unwrap_or(".").into());
+    args
+}
+
+/// 解析 `image inspect --format '{{json .Config.Labels}}'` 输出, 无标签时为 `null`
+pub fn parse_labels_output(output: &str) -> BTreeMap<String, String> {
+    serde_json::from_str::<Option<BTreeMap<String, String>>>(output.trim())
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// 解析 `ps` 状态列, 如 "Up 3 minutes" / "Exited (0) 2 hours ago"
+pub fn parse_container_status(status: &str) -> ContainerStatus {
+    match status {
+        is_up if is_up.contains("Up") => ContainerStatus::Up,
+        is_exited if is_exited.contains("Exited") => ContainerStatus::Exited,
+        is_created if is_created.contains("Created") => ContainerStatus::Created,
+        is_paused if is_paused.contains("Paused") => ContainerStatus::Paused,
+        is_restarting if is_restarting.contains("Restarting") => ContainerStatus::Restarting,
+        is_dead if is_dead.contains("Dead") => ContainerStatus::Dead,
+        is_removing if is_removing.contains("Removing") => ContainerStatus::Removing,
+        _ => ContainerStatus::Unbuilt,
+    }
+}
+
+/// 解析 `images --format` 输出的首行, 输出为空时状态为 Unbuilt
+pub fn parse_image_output(image: &str, tag: &str, output: &str) -> EngineImageInfo {
+    let line = output.lines().find(|line| !line.trim().is_empty()).unwrap_or_default();
+    let mut parts: Vec<&str> = line.trim().split('\t').collect();
+    parts.resize(4, "");
+
+    EngineImageInfo {
+        id: parts[0].to_string(),
+        name: image.to_string(),
+        tag: tag.to_string(),
+        created_at: parts[2].to_string(),
+        size: parts[3].to_string(),
+        status: if line.trim().is_empty() { ImageStatus::Unbuilt } else { ImageStatus::Built },
+    }
+}
+
+/// 解析 `images --format` 输出的全部行, 跳过无标签 (<none>) 的镜像
+pub fn parse_image_list_output(image: &str, output: &str) -> Vec<EngineImageInfo> {
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let tag = line.trim().split('\t').nth(1).unwrap_or_default();
+            parse_image_output(image, tag, line)
+        })
+        .filter(|info| !info.tag.is_empty() && info.tag != "<none>")
+        .collect()
+}
+
+/// 解析 `ps --format` 输出的首行, 镜像列为空时使用 compose 服务中的镜像
+pub fn parse_container_output(
+    name: &str,
+    output: &str,
+    service_config: EngineServiceConfig,
+) -> EngineContainerInfo {
+    let Some(line) = output.lines().find(|line| !line.trim().is_empty()) else {
+        return unbuilt_container(name, Some(service_config));
+    };
+
+    let mut parts: Vec<&str> = line.split('\t').collect();
+    // 列数不足时补全
+    parts.resize(5, "");
+
+    let image = match parts[1] {
+        "" => service_config.image,
+        img => img.to_string(),
+    };
+
+    EngineContainerInfo {
+        id: parts[0].to_string(),
+        name: name.to_string(),
+        image,
+        created_at: parts[3].to_string(),
+        size: parts[4].to_string(),
+        working_dir: service_config.working_dir,
+        status: parse_container_status(parts[2]),
+    }
+}
+
+fn unbuilt_container(
+    name: &str,
+    service_config: Option<EngineServiceConfig>,
+) -> EngineContainerInfo {
+    let (image, working_dir) = match service_config {
+        Some(service) => (service.image, service.working_dir),
+        None => (String::new(), None),
+    };
+
+    EngineContainerInfo {
+        id: "".to_string(),
+        name: name.to_string(),
+        image,
+        created_at: "".to_string(),
+        size: "".to_string(),
+        working_dir,
+        status: ContainerStatus::Unbuilt,
+    }
+}
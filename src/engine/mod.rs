@@ -2,17 +2,26 @@
 //! Created: 2025-07-14
 //! Description: ConfKit Engine 统一引擎调用
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
-use crate::engine::docker::DockerEngine;
-use crate::engine::podman::PodmanEngine;
+use crate::infra::config::ConfKitConfigLoader;
 use crate::infra::logger::TaskLogger;
-use crate::shared::global::ENGINE;
-use crate::types::config::{Engine, EngineContainerInfo, EngineImageInfo, EngineServiceConfig};
+use crate::shared::global::{ENGINE, ENGINE_REGISTRY};
+use crate::types::config::{
+    Engine, EngineContainerInfo, EngineImageInfo, EngineMode, EngineServiceConfig,
+    ImageBuildOptions, RegistryAuth,
+};
 use anyhow::Result;
 
-mod docker;
-mod podman;
+pub mod api;
+pub mod cli;
+pub mod registry;
+pub mod traits;
+
+use api::docker::DockerApiEngine;
+use api::podman::PodmanApiEngine;
+use traits::ContainerEngine;
 
 pub struct ConfKitEngine;
 
@@ -26,21 +35,66 @@ impl ConfKitEngine {
         }
     }
 
-    // 检测当前宿主机是否支持引擎
+    // 从注册表中获取引擎实现
+    fn implementation(engine: &Engine) -> Result<Arc<dyn ContainerEngine>> {
+        if *engine == Engine::None {
+            anyhow::bail!("No container engine configured (engine: none)");
+        }
+
+        ENGINE_REGISTRY
+            .read()
+            .unwrap()
+            .get(engine)
+            .ok_or_else(|| anyhow::anyhow!("No implementation registered for engine {engine:?}"))
+    }
+
+    // 当前引擎的实现
+    async fn current() -> Result<Arc<dyn ContainerEngine>> {
+        Self::implementation(&Self::get_engine().await?)
+    }
+
+    // 检测当前宿主机是否支持引擎, engine: none 时无需检测
     pub async fn check_engine(engine: Engine) -> Result<()> {
-        match engine {
-            Engine::Docker => DockerEngine::check_engine().await,
-            Engine::Podman => PodmanEngine::check_engine().await,
+        if engine == Engine::None {
+            return Ok(());
         }
+        Self::implementation(&engine)?.check_engine().await
+    }
+
+    // 是否未配置容器引擎 (engine: none), 此时只能在宿主机执行步骤
+    pub async fn is_host_only() -> Result<bool> {
+        Ok(Self::get_engine().await? == Engine::None)
+    }
+
+    // 依赖容器引擎的命令在 engine: none 时给出说明
+    pub async fn require_engine(command: &str) -> Result<()> {
+        if Self::is_host_only().await? {
+            anyhow::bail!(
+                "`confkit {command}` is unavailable with engine: none. Set engine to docker, podman or nerdctl in .confkit.yml to manage {command}s"
+            );
+        }
+        Ok(())
     }
 
     // 检测容器引擎是否正在运行
     pub async fn ensure_running() -> Result<()> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::ensure_running().await,
-            Engine::Podman => PodmanEngine::ensure_running().await,
-        }
+        Self::current().await?.ensure_running().await
+    }
+
+    // 设置引擎调用方式, api 模式下以 HTTP 接口实现替换注册表中的命令行实现
+    pub fn set_mode(engine: &Engine, mode: EngineMode) -> Result<()> {
+        let implementation: Arc<dyn ContainerEngine> = match (engine, mode) {
+            (_, EngineMode::Cli) | (Engine::None, _) => return Ok(()),
+            (Engine::Docker, EngineMode::Api) => Arc::new(DockerApiEngine::from_env()?),
+            (Engine::Podman, EngineMode::Api) => Arc::new(PodmanApiEngine::from_env()?),
+            // nerdctl 没有 HTTP 接口, 只能调用命令行
+            (Engine::Nerdctl, EngineMode::Api) => {
+                anyhow::bail!("engine_mode 'api' is not supported by nerdctl, use engine_mode: cli")
+            }
+        };
+
+        ENGINE_REGISTRY.write().unwrap().register(engine.clone(), implementation);
+        Ok(())
     }
 
     // 设置当前宿主机使用的引擎
@@ -57,31 +111,29 @@ impl ConfKitEngine {
 
     // 检查镜像是否存在
     pub async fn check_image_exists(image: &str, tag: &str) -> Result<bool> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::check_image_exists(image, tag).await,
-            Engine::Podman => PodmanEngine::check_image_exists(image, tag).await,
-        }
+        Self::current().await?.check_image_exists(image, tag).await
     }
 
     // 获取镜像信息
     pub async fn get_image_info(image: &str, tag: &str) -> Result<EngineImageInfo> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::get_image_info(image, tag).await,
-            Engine::Podman => PodmanEngine::get_image_info(image, tag).await,
-        }
+        Self::current().await?.get_image_info(image, tag).await
+    }
+
+    // 列出镜像在本地的全部标签
+    pub async fn list_image_tags(image: &str) -> Result<Vec<EngineImageInfo>> {
+        Self::current().await?.list_image_tags(image).await
+    }
+
+    // 获取镜像标签
+    pub async fn get_image_labels(image: &str, tag: &str) -> Result<BTreeMap<String, String>> {
+        Self::current().await?.get_image_labels(image, tag).await
     }
 
     // 拉取远程镜像到本地进行缓存
     pub async fn pull_image(image: &str, tag: &str) -> Result<()> {
         tracing::info!("Pulling image \"{}\"", image);
 
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::pull_image(image, tag).await,
-            Engine::Podman => PodmanEngine::pull_image(image, tag).await,
-        }
+        Self::current().await?.pull_image(image, tag).await
     }
 
     // 构建镜像
@@ -90,88 +142,74 @@ impl ConfKitEngine {
         tag: &str,
         dockerfile: &str,
         context: Option<&str>,
+        options: &ImageBuildOptions,
     ) -> Result<()> {
         tracing::info!("Building image \"{}\"", name);
 
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::build_image(name, tag, dockerfile, context).await,
-            Engine::Podman => PodmanEngine::build_image(name, tag, dockerfile, context).await,
-        }
+        Self::current().await?.build_image(name, tag, dockerfile, context, options).await
+    }
+
+    // 为本地镜像添加新的引用
+    pub async fn tag_image(
+        image: &str,
+        tag: &str,
+        repository: &str,
+        target_tag: &str,
+    ) -> Result<()> {
+        Self::current().await?.tag_image(image, tag, repository, target_tag).await
+    }
+
+    // 推送镜像到镜像仓库
+    pub async fn push_image(
+        repository: &str,
+        tag: &str,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<()> {
+        tracing::info!("Pushing image \"{}:{}\"", repository, tag);
+
+        Self::current().await?.push_image(repository, tag, auth).await
     }
 
     // 移除镜像
     pub async fn remove_image(image: &str, tag: &str) -> Result<()> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::remove_image(image, tag).await,
-            Engine::Podman => PodmanEngine::remove_image(image, tag).await,
-        }
+        Self::current().await?.remove_image(image, tag).await
     }
 
     // ================================================ Container ================================================
 
     // 检查容器是否存在
     pub async fn check_container_exists(name: &str) -> Result<bool> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::check_container_exists(name).await,
-            Engine::Podman => PodmanEngine::check_container_exists(name).await,
-        }
+        Self::current().await?.check_container_exists(name).await
     }
 
     // 创建容器
     pub async fn create_container(name: &str) -> Result<()> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::create_container(name).await,
-            Engine::Podman => PodmanEngine::create_container(name).await,
-        }
+        Self::current().await?.create_container(name).await
     }
 
     // 移除容器
     pub async fn remove_container(name: &str) -> Result<()> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::remove_container(name).await,
-            Engine::Podman => PodmanEngine::remove_container(name).await,
-        }
+        Self::current().await?.remove_container(name).await
     }
 
     // 启动容器
     pub async fn start_container(name: &str) -> Result<()> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::start_container(name).await,
-            Engine::Podman => PodmanEngine::start_container(name).await,
-        }
+        Self::current().await?.start_container(name).await
     }
 
     // 停止容器
     pub async fn stop_container(name: &str) -> Result<()> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::stop_container(name).await,
-            Engine::Podman => PodmanEngine::stop_container(name).await,
-        }
+        Self::current().await?.stop_container(name).await
     }
 
     // 重启容器
     pub async fn restart_container(name: &str) -> Result<()> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::restart_container(name).await,
-            Engine::Podman => PodmanEngine::restart_container(name).await,
-        }
+        Self::current().await?.restart_container(name).await
     }
 
     // 获取容器信息
     pub async fn get_container_info(name: &str) -> Result<EngineContainerInfo> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::get_container_info(name).await,
-            Engine::Podman => PodmanEngine::get_container_info(name).await,
-        }
+        Self::current().await?.get_container_info(name).await
     }
 
     // 在容器中执行命令
@@ -183,56 +221,42 @@ impl ConfKitEngine {
         environment: &HashMap<String, String>,
         task_logger: &TaskLogger,
     ) -> Result<i32> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => {
-                DockerEngine::execute_in_container(
-                    container,
-                    shell,
-                    working_dir,
-                    cmd,
-                    environment,
-                    task_logger,
-                )
-                .await
-            }
-            Engine::Podman => {
-                PodmanEngine::execute_in_container(
-                    container,
-                    shell,
-                    working_dir,
-                    cmd,
-                    environment,
-                    task_logger,
-                )
-                .await
-            }
-        }
+        Self::current()
+            .await?
+            .execute_in_container(container, shell, working_dir, cmd, environment, task_logger)
+            .await
     }
 
     // ================================================ Docker Compose ================================================
 
-    // 获取 Docker Compose 服务列表
+    // 获取 Docker Compose 服务列表, 与引擎无关
     pub async fn get_compose_services() -> Result<Vec<EngineServiceConfig>> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => DockerEngine::get_compose_services().await,
-            Engine::Podman => PodmanEngine::get_compose_services().await,
+        let config = ConfKitConfigLoader::get_engine_compose_config().await?;
+        let mut services = vec![];
+
+        for (service_name, value) in config.services {
+            services.push(EngineServiceConfig {
+                service_name,
+                container_name: value.container_name,
+                image: value.image,
+                working_dir: value.working_dir,
+                ports: value.ports,
+                environment: value.environment,
+                volumes: value.volumes,
+                depends_on: value.depends_on,
+                other: value.other,
+            });
         }
+
+        Ok(services)
     }
 
     // 获取 Docker Compose 服务配置
     pub async fn get_compose_service_config_by_container_name(
         name: &str,
     ) -> Result<Option<EngineServiceConfig>> {
-        let engine = Self::get_engine().await?;
-        match engine {
-            Engine::Docker => {
-                DockerEngine::get_compose_service_config_by_container_name(name).await
-            }
-            Engine::Podman => {
-                PodmanEngine::get_compose_service_config_by_container_name(name).await
-            }
-        }
+        let services = Self::get_compose_services().await?;
+
+        Ok(services.into_iter().find(|service| service.container_name == name))
     }
 }
@@ -0,0 +1,48 @@
+//! Author: xiaoYown
+//! Created: 2026-10-19
+//! Description: Registry mapping `Engine` to its `ContainerEngine` implementation
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::engine::cli::CliEngine;
+use crate::engine::traits::ContainerEngine;
+use crate::types::config::Engine;
+
+/// 引擎注册表
+///
+/// 默认注册内置的 docker / podman / nerdctl 实现, 可通过 `register` 替换为自定义或测试用实现。
+pub struct EngineRegistry {
+    engines: HashMap<Engine, Arc<dyn ContainerEngine>>,
+}
+
+impl Default for EngineRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Engine::Docker, Arc::new(CliEngine::docker()));
+        registry.register(Engine::Podman, Arc::new(CliEngine::podman()));
+        registry.register(Engine::Nerdctl, Arc::new(CliEngine::nerdctl()));
+        registry
+    }
+}
+
+impl EngineRegistry {
+    /// 不含任何实现的注册表
+    pub fn empty() -> Self {
+        Self { engines: HashMap::new() }
+    }
+
+    /// 注册实现, 已存在时替换并返回旧实现
+    pub fn register(
+        &mut self,
+        engine: Engine,
+        implementation: Arc<dyn ContainerEngine>,
+    ) -> Option<Arc<dyn ContainerEngine>> {
+        self.engines.insert(engine, implementation)
+    }
+
+    /// 获取引擎对应的实现
+    pub fn get(&self, engine: &Engine) -> Option<Arc<dyn ContainerEngine>> {
+        self.engines.get(engine).cloned()
+    }
+}
@@ -0,0 +1,107 @@
+//! Author: xiaoYown
+//! Created: 2026-10-19
+//! Description: Container engine trait implemented by every engine backend
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::infra::logger::TaskLogger;
+use crate::types::config::{EngineContainerInfo, EngineImageInfo, ImageBuildOptions, RegistryAuth};
+
+/// 容器引擎
+///
+/// 每种 `Engine` 对应一个实现, 由 `EngineRegistry` 按配置选择;
+/// compose 配置的读取与引擎无关, 不在此 trait 中。
+#[async_trait]
+pub trait ContainerEngine: Send + Sync {
+    // ================================================ Engine Basic ================================================
+
+    /// 检测当前宿主机是否安装了该引擎
+    async fn check_engine(&self) -> Result<()>;
+
+    /// 检测引擎守护进程是否正在运行
+    async fn ensure_running(&self) -> Result<()>;
+
+    // ================================================ Image ================================================
+
+    /// 检查镜像是否存在
+    async fn check_image_exists(&self, image: &str, tag: &str) -> Result<bool>;
+
+    /// 获取镜像信息, 镜像不存在时状态为 Unbuilt
+    async fn get_image_info(&self, image: &str, tag: &str) -> Result<EngineImageInfo>;
+
+    /// 列出镜像在本地的全部标签, 不含无标签 (<none>) 的镜像
+    async fn list_image_tags(&self, image: &str) -> Result<Vec<EngineImageInfo>>;
+
+    /// 获取镜像标签, 镜像不存在时为空
+    async fn get_image_labels(&self, image: &str, tag: &str) -> Result<BTreeMap<String, String>>;
+
+    /// 拉取远程镜像到本地进行缓存
+    async fn pull_image(&self, image: &str, tag: &str) -> Result<()>;
+
+    /// 构建镜像, context 为空时使用当前目录
+    async fn build_image(
+        &self,
+        name: &str,
+        tag: &str,
+        dockerfile: &str,
+        context: Option<&str>,
+        options: &ImageBuildOptions,
+    ) -> Result<()>;
+
+    /// 为本地镜像添加新的引用 repository:target_tag
+    async fn tag_image(
+        &self,
+        image: &str,
+        tag: &str,
+        repository: &str,
+        target_tag: &str,
+    ) -> Result<()>;
+
+    /// 推送镜像, auth 为空时使用引擎自身保存的凭据
+    async fn push_image(
+        &self,
+        repository: &str,
+        tag: &str,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<()>;
+
+    /// 移除镜像
+    async fn remove_image(&self, image: &str, tag: &str) -> Result<()>;
+
+    // ================================================ Container ================================================
+
+    /// 检查容器是否存在
+    async fn check_container_exists(&self, name: &str) -> Result<bool>;
+
+    /// 按 compose 文件中的服务创建容器
+    async fn create_container(&self, name: &str) -> Result<()>;
+
+    /// 移除容器
+    async fn remove_container(&self, name: &str) -> Result<()>;
+
+    /// 启动容器
+    async fn start_container(&self, name: &str) -> Result<()>;
+
+    /// 停止容器
+    async fn stop_container(&self, name: &str) -> Result<()>;
+
+    /// 重启容器
+    async fn restart_container(&self, name: &str) -> Result<()>;
+
+    /// 获取容器信息, 容器不存在时状态为 Unbuilt
+    async fn get_container_info(&self, name: &str) -> Result<EngineContainerInfo>;
+
+    /// 在容器中执行命令, 返回退出码
+    async fn execute_in_container(
+        &self,
+        container: &str,
+        shell: &str,
+        working_dir: &str,
+        cmd: &str,
+        environment: &HashMap<String, String>,
+        task_logger: &TaskLogger,
+    ) -> Result<i32>;
+}
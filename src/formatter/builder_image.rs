@@ -2,6 +2,9 @@
 //! Created: 2025-07-14
 //! Description: Builder Image Formatter
 
+use anyhow::Result;
+
+use crate::core::builder::graph::ImageGraph;
 use crate::types::config::ConfKitImageInfo;
 use tabled::{builder::Builder, settings::Style};
 
@@ -42,6 +45,65 @@ impl BuilderImageFormatter {
         println!("{table}");
     }
 
+    // 镜像依赖树: 外部基础镜像为根, 本地基础镜像之下为依赖它的镜像
+    pub fn format_tree(images: &[ConfKitImageInfo]) -> Result<String> {
+        let graph = ImageGraph::new(
+            images
+                .iter()
+                .map(|image| (image.name.as_str(), image.base_image.as_str(), image.tag.as_str())),
+        );
+        // 存在循环依赖时无法构成树
+        graph.build_order()?;
+
+        let external = |index: usize| format!("{}:{}", images[index].base_image, images[index].tag);
+        let roots = graph.roots();
+        let mut bases: Vec<String> = vec![];
+        for &root in &roots {
+            if !bases.contains(&external(root)) {
+                bases.push(external(root));
+            }
+        }
+
+        let mut lines = vec![];
+        for base in bases {
+            lines.push(base.clone());
+            let nodes: Vec<usize> =
+                roots.iter().copied().filter(|&root| external(root) == base).collect();
+            Self::push_tree_nodes(images, &graph, &nodes, "", &mut lines);
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    fn push_tree_nodes(
+        images: &[ConfKitImageInfo],
+        graph: &ImageGraph,
+        nodes: &[usize],
+        prefix: &str,
+        lines: &mut Vec<String>,
+    ) {
+        for (position, &index) in nodes.iter().enumerate() {
+            let last = position == nodes.len() - 1;
+            let image = &images[index];
+            lines.push(format!(
+                "{prefix}{}{}:{} ({})",
+                if last { "└── " } else { "├── " },
+                image.name,
+                image.tag,
+                image.status
+            ));
+
+            let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+            Self::push_tree_nodes(images, graph, &graph.children(index), &prefix, lines);
+        }
+    }
+
+    // 打印镜像依赖树
+    pub fn print_tree(images: &[ConfKitImageInfo]) -> Result<()> {
+        println!("{}", Self::format_tree(images)?);
+        Ok(())
+    }
+
     // 打印单个镜像信息
     pub fn print_image_info(image: Option<&ConfKitImageInfo>) {
         if image.is_none() {
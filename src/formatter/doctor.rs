@@ -0,0 +1,48 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Doctor Formatter
+
+use anyhow::Result;
+use tabled::{builder::Builder, settings::Style};
+
+use crate::core::doctor::{CheckStatus, DoctorReport};
+
+pub struct DoctorFormatter;
+
+impl DoctorFormatter {
+    // 打印检查结果表格
+    pub fn print_table(report: &DoctorReport) {
+        let mut builder = Builder::new();
+
+        builder.push_record(vec!["● Check", "● Status", "● Detail"]);
+
+        for check in &report.checks {
+            let status = match check.status {
+                CheckStatus::Pass => "✓ pass",
+                CheckStatus::Warn => "! warn",
+                CheckStatus::Fail => "✗ fail",
+            };
+            builder.push_record(vec![check.name.clone(), status.to_string(), check.detail.clone()]);
+        }
+
+        let mut table = builder.build();
+        table.with(Style::ascii_rounded());
+
+        println!("{table}");
+    }
+
+    // 以 JSON 格式打印检查结果
+    pub fn print_json(report: &DoctorReport) -> Result<()> {
+        let output = serde_json::json!({
+            "checks": report.checks,
+            "summary": {
+                "pass": report.count(CheckStatus::Pass),
+                "warn": report.count(CheckStatus::Warn),
+                "fail": report.count(CheckStatus::Fail),
+            },
+        });
+
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Environment Formatter
+
+use anyhow::Result;
+use tabled::{builder::Builder, settings::Style};
+
+use crate::core::executor::environment::{EnvironmentEntry, EnvironmentLayers};
+
+pub struct EnvironmentFormatter;
+
+impl EnvironmentFormatter {
+    // 打印环境变量表格 (变量, 值, 来源, 被覆盖的值)
+    pub fn print_table(layers: &EnvironmentLayers) {
+        let mut builder = Builder::new();
+
+        builder.push_record(vec!["● Key", "● Value", "● Source", "● Overrides"]);
+
+        for entry in layers.entries() {
+            let overrides = entry
+                .overridden
+                .iter()
+                .map(|o| format!("{} ({})", o.value, o.source))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            builder.push_record(vec![
+                entry.key.clone(),
+                entry.value.clone(),
+                entry.source.to_string(),
+                overrides,
+            ]);
+        }
+
+        let mut table = builder.build();
+        table.with(Style::ascii_rounded());
+
+        println!("{table}");
+    }
+
+    // 以 JSON 格式打印环境变量
+    pub fn print_json(
+        space_name: &str,
+        project_name: &str,
+        layers: &EnvironmentLayers,
+    ) -> Result<()> {
+        let variables: Vec<&EnvironmentEntry> = layers.entries().collect();
+        let output = serde_json::json!({
+            "space": space_name,
+            "project": project_name,
+            "variables": variables,
+        });
+
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        Ok(())
+    }
+}
@@ -4,5 +4,7 @@
 
 pub mod builder_container;
 pub mod builder_image;
+pub mod doctor;
+pub mod environment;
 pub mod log;
 pub mod path;
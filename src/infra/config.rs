@@ -9,13 +9,15 @@ use std::path::Path;
 use anyhow::Result;
 use tokio::fs::read_to_string;
 
-use crate::shared::constants::CONFKIT_CONFIG_FILE;
-use crate::shared::global::CONFIG;
+use crate::core::validator::{ConfigValidator, Diagnostic, Severity};
+use crate::infra::project_index::IndexedProject;
+use crate::shared::constants::{CONFKIT_CONFIG_FILE, CONFKIT_CONFIG_FILES};
+use crate::shared::global::{CONFIG, PROJECT_INDEX};
 use crate::types::config::{
-    ConfKitConfig, ConfKitImageConfig, ConfKitProjectConfig, ConfKitSourceConfig,
-    ConfKitSpaceConfig, EngineComposeConfig,
+    ConfKitConfig, ConfKitEnvironmentFileFormat, ConfKitImageConfig, ConfKitProjectConfig,
+    ConfKitSourceConfig, ConfKitSpaceConfig, EngineComposeConfig,
 };
-use crate::utils::fs::get_yaml_files_in_dir;
+use crate::utils::dotenv;
 
 pub struct ConfKitConfigLoader;
 
@@ -24,23 +26,39 @@ impl ConfKitConfigLoader {
 
     // 判断配置文件是否存在
     pub async fn is_config_file_exists() -> bool {
-        Path::new(CONFKIT_CONFIG_FILE).exists()
+        Self::config_file_path().is_some()
+    }
+
+    /// 当前目录下的配置文件 (.confkit.yml / .confkit.toml / .confkit.json)
+    pub fn config_file_path() -> Option<&'static str> {
+        let mut existing = CONFKIT_CONFIG_FILES.iter().filter(|file| Path::new(file).is_file());
+        let path = existing.next()?;
+        for ignored in existing {
+            tracing::warn!("Both {} and {} exist, {} is ignored", path, ignored, ignored);
+        }
+        Some(path)
     }
 
     // 设置全局配置文件
     pub async fn set_config() -> Result<()> {
-        let config = Self::from_file(CONFKIT_CONFIG_FILE).await?;
+        let path = Self::config_file_path().unwrap_or(CONFKIT_CONFIG_FILE);
+        let config = Self::from_file(path).await?;
         let mut guard = CONFIG.write().unwrap();
         *guard = Some(config);
         Ok(())
     }
 
-    /// 从YAML文件加载配置
+    /// 从YAML文件加载配置, 合并 include 引入的文件
     pub async fn from_file(path: &str) -> Result<ConfKitConfig> {
-        let content = read_to_string(path).await?;
-        let config: ConfKitConfig = serde_yaml::from_str(&content)?;
-        Self::validate(&config)?;
-        Ok(config)
+        let (config, diagnostics) = ConfigValidator::load_config(path);
+        let (errors, warnings): (Vec<Diagnostic>, Vec<Diagnostic>) =
+            diagnostics.into_iter().partition(|d| d.severity == Severity::Error);
+
+        Self::warn_diagnostics(&warnings);
+        match config {
+            Some(loaded) if errors.is_empty() => Ok(loaded.config),
+            _ => Err(anyhow::anyhow!("{}", Self::join_diagnostics(&errors))),
+        }
     }
 
     /// 获取配置
@@ -69,29 +87,30 @@ impl ConfKitConfigLoader {
 
     // 获取 space 下的所有项目配置
     pub async fn get_project_config_list(space_name: &str) -> Result<Vec<ConfKitProjectConfig>> {
-        let space_config = Self::get_space_config(space_name).await?;
+        let projects = Self::get_indexed_projects(space_name).await?;
+        Ok(projects.into_iter().map(|project| project.config).collect())
+    }
 
-        if space_config.is_none() {
+    /// 获取 space 下的所有项目及其来源文件, 首次访问时加载并缓存
+    pub async fn get_indexed_projects(space_name: &str) -> Result<Vec<IndexedProject>> {
+        let Some(space_config) = Self::get_space_config(space_name).await? else {
             return Ok(vec![]);
-        }
+        };
 
-        let space_config = space_config.unwrap();
-
-        let yaml_files = get_yaml_files_in_dir(&space_config.path)?;
-
-        let mut project_config_list: Vec<ConfKitProjectConfig> = vec![];
-
-        for file_name in yaml_files {
-            let file_path = Path::new(&space_config.path).join(&file_name);
-            let project_config = read_to_string(&file_path).await?;
+        if let Some(projects) = PROJECT_INDEX.read().unwrap().projects(space_name) {
+            return Ok(projects.into_iter().cloned().collect());
+        }
 
-            match serde_yaml::from_str::<ConfKitProjectConfig>(&project_config) {
-                Ok(project_config) => project_config_list.push(project_config),
-                Err(e) => tracing::warn!("Failed to parse project config: {}", e),
-            }
+        let mut index = PROJECT_INDEX.write().unwrap();
+        if !index.is_loaded(space_name) {
+            index.load_space(space_name, &space_config.path)?;
         }
+        Ok(index.projects(space_name).unwrap_or_default().into_iter().cloned().collect())
+    }
 
-        Ok(project_config_list)
+    /// 重新加载已缓存的项目配置, 仅重新解析有变化的文件
+    pub fn reload_projects() -> Result<()> {
+        PROJECT_INDEX.write().unwrap().reload()
     }
 
     // ================================================ Projects ================================================
@@ -101,11 +120,18 @@ impl ConfKitConfigLoader {
         space_name: &str,
         poject_name: &str,
     ) -> Result<Option<ConfKitProjectConfig>> {
-        let project_config_list = Self::get_project_config_list(space_name).await?;
-
-        let project_config = project_config_list.iter().find(|project| project.name == poject_name);
+        let project = Self::get_indexed_project(space_name, poject_name).await?;
+        Ok(project.map(|project| project.config))
+    }
 
-        Ok(project_config.cloned())
+    /// 获取项目配置及其来源文件
+    pub async fn get_indexed_project(
+        space_name: &str,
+        project_name: &str,
+    ) -> Result<Option<IndexedProject>> {
+        // 确保 space 已加载
+        Self::get_indexed_projects(space_name).await?;
+        Ok(PROJECT_INDEX.read().unwrap().get(space_name, project_name).cloned())
     }
 
     // 获取项目源信息
@@ -139,49 +165,10 @@ impl ConfKitConfigLoader {
         let mut env_mixed = HashMap::new();
 
         // 环境文件解析
-        if let Some(environment_files) = &project_config.environment_files {
-            for env_file in environment_files {
-                let path = Path::new(&env_file.path);
-                let file_content = match fs::read_to_string(path) {
-                    Ok(content) => content,
-                    Err(e) => {
-                        tracing::warn!(
-                            "Failed to read environment file '{}': {}",
-                            env_file.path,
-                            e
-                        );
-                        continue;
-                    }
-                };
-
-                let parsed = match env_file.format.as_str() {
-                    "yaml" => {
-                        match serde_yaml::from_str::<HashMap<String, String>>(&file_content) {
-                            Ok(data) => data,
-                            Err(e) => {
-                                tracing::warn!(
-                                    "Failed to parse yaml environment file '{}': {}",
-                                    env_file.path,
-                                    e
-                                );
-                                continue;
-                            }
-                        }
-                    }
-                    "env" => Self::parse_env_file(&file_content),
-                    _ => {
-                        tracing::warn!(
-                            "Unsupported environment file format '{}', skipping",
-                            env_file.format
-                        );
-                        continue;
-                    }
-                };
-
-                for (key, value) in parsed {
-                    env_from_file.insert(key.clone(), value.clone());
-                    env_mixed.insert(key, value);
-                }
+        for (_, parsed) in Self::load_environment_files(&project_config) {
+            for (key, value) in parsed {
+                env_from_file.insert(key.clone(), value.clone());
+                env_mixed.insert(key, value);
             }
         }
 
@@ -196,6 +183,72 @@ impl ConfKitConfigLoader {
         Ok((env_mixed, env_from_conf, env_from_file))
     }
 
+    /// 按声明顺序解析项目的环境变量文件, 返回 (文件路径, 变量) 列表
+    ///
+    /// 读取或解析失败的文件会记录警告并跳过。
+    pub fn load_environment_files(
+        project_config: &ConfKitProjectConfig,
+    ) -> Vec<(String, HashMap<String, String>)> {
+        let mut files = vec![];
+
+        let environment_files = match &project_config.environment_files {
+            Some(environment_files) => environment_files,
+            None => return files,
+        };
+
+        for env_file in environment_files {
+            let path = Path::new(&env_file.path);
+            let file_content = match fs::read_to_string(path) {
+                Ok(content) => content,
+                Err(e) => {
+                    tracing::warn!("Failed to read environment file '{}': {}", env_file.path, e);
+                    continue;
+                }
+            };
+
+            let parsed = match env_file.format {
+                ConfKitEnvironmentFileFormat::Yaml => {
+                    match serde_yaml::from_str::<HashMap<String, String>>(&file_content) {
+                        Ok(data) => data,
+                        Err(e) => {
+                            tracing::warn!(
+                                "Failed to parse yaml environment file '{}': {}",
+                                env_file.path,
+                                e
+                            );
+                            continue;
+                        }
+                    }
+                }
+                ConfKitEnvironmentFileFormat::Env => {
+                    Self::parse_env_file(&env_file.path, &file_content)
+                }
+                ConfKitEnvironmentFileFormat::Json | ConfKitEnvironmentFileFormat::Toml => {
+                    match Self::parse_structured_env_file(
+                        &env_file.path,
+                        &env_file.format,
+                        &file_content,
+                    ) {
+                        Ok(data) => data,
+                        Err(e) => {
+                            tracing::warn!(
+                                "Failed to parse {} environment file '{}': {}",
+                                env_file.format,
+                                env_file.path,
+                                e
+                            );
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            files.push((env_file.path.clone(), parsed));
+        }
+
+        files
+    }
+
     // ================================================ Docker Compose ================================================
 
     // 获取 Engine Compose 配置文件
@@ -219,31 +272,78 @@ impl ConfKitConfigLoader {
         Ok(image.cloned())
     }
 
-    /// 解析 .env 格式文件内容
+    /// 解析 .env 格式文件内容, 解析警告带行号输出
+    fn parse_env_file(path: &str, content: &str) -> HashMap<String, String> {
+        let parsed = dotenv::parse(content);
+        for warning in &parsed.warnings {
+            tracing::warn!("Environment file '{}' {}", path, warning);
+        }
+        parsed.values
+    }
+
+    /// 解析 json / toml 环境变量文件的顶层键值
     ///
-    /// 每行格式为 `KEY=VALUE`，忽略空行和 `#` 开头的注释行。
-    fn parse_env_file(content: &str) -> HashMap<String, String> {
+    /// 字符串、数字与布尔值转换为字符串, 其他类型记录警告并跳过。
+    fn parse_structured_env_file(
+        path: &str,
+        format: &ConfKitEnvironmentFileFormat,
+        content: &str,
+    ) -> Result<HashMap<String, String>> {
         let mut map = HashMap::new();
-        for line in content.lines() {
-            let trimmed = line.trim();
-            if trimmed.is_empty() || trimmed.starts_with('#') {
-                continue;
+        let mut skipped = vec![];
+
+        match format {
+            ConfKitEnvironmentFileFormat::Json => {
+                let data: serde_json::Map<String, serde_json::Value> =
+                    serde_json::from_str(content)?;
+                for (key, value) in data {
+                    match value {
+                        serde_json::Value::String(value) => {
+                            map.insert(key, value);
+                        }
+                        serde_json::Value::Number(_) | serde_json::Value::Bool(_) => {
+                            map.insert(key, value.to_string());
+                        }
+                        _ => skipped.push(key),
+                    }
+                }
             }
-            if let Some((key, value)) = trimmed.split_once('=') {
-                let key = key.trim();
-                let value = value.trim();
-                if !key.is_empty() {
-                    map.insert(key.to_string(), value.to_string());
+            _ => {
+                let data: toml::Table = toml::from_str(content)?;
+                for (key, value) in data {
+                    match value {
+                        toml::Value::String(value) => {
+                            map.insert(key, value);
+                        }
+                        toml::Value::Integer(_)
+                        | toml::Value::Float(_)
+                        | toml::Value::Boolean(_) => {
+                            map.insert(key, value.to_string());
+                        }
+                        _ => skipped.push(key),
+                    }
                 }
             }
         }
-        map
+
+        for key in skipped {
+            tracing::warn!(
+                "Environment file '{}': value of '{}' is not a string, number or boolean, skipping",
+                path,
+                key
+            );
+        }
+
+        Ok(map)
     }
 
-    /// 验证配置
-    fn validate(_config: &ConfKitConfig) -> Result<()> {
-        // TODO: 实现配置验证逻辑
-        tracing::debug!("验证项目配置 - 待实现");
-        Ok(())
+    fn warn_diagnostics(diagnostics: &[Diagnostic]) {
+        for diagnostic in diagnostics {
+            tracing::warn!("{}", diagnostic);
+        }
+    }
+
+    fn join_diagnostics(diagnostics: &[Diagnostic]) -> String {
+        diagnostics.iter().map(|d| d.to_string()).collect::<Vec<_>>().join("\n")
     }
 }
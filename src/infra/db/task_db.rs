@@ -3,7 +3,8 @@
 //! Description: SQLite task metadata database
 
 use anyhow::Result;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OpenFlags};
+use std::path::Path;
 
 use crate::core::executor::types::{TaskMetadata, TaskStatus};
 use crate::shared::constants::{HOST_LOG_DIR, TASK_DB_FILE};
@@ -64,6 +65,13 @@ impl TaskDb {
         Ok(Self { conn })
     }
 
+    /// 以只读方式打开数据库并执行完整性检查, 返回 `PRAGMA integrity_check` 的结果
+    pub fn integrity_check(path: &Path) -> Result<String> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let result: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+        Ok(result)
+    }
+
     /// 插入新任务
     pub fn insert_task(&self, metadata: &TaskMetadata, log_path: &str) -> Result<()> {
         let steps_json = serde_json::to_string(&metadata.steps)?;
@@ -4,12 +4,14 @@
 
 use std::fs::{self, OpenOptions};
 use std::io::Write;
+use std::sync::{Arc, RwLock};
 
 use anyhow::Result;
 use chrono::Local;
 use tokio::sync::{mpsc, oneshot};
 
 use super::LogLevel;
+use crate::utils::mask::{redact, secret_fragments};
 
 /// 内部日志命令
 enum LogCommand {
@@ -21,6 +23,8 @@ enum LogCommand {
 #[derive(Clone)]
 pub struct TaskLogger {
     sender: mpsc::UnboundedSender<LogCommand>,
+    /// 需要脱敏的密钥片段 (所有 clone 共享)
+    secrets: Arc<RwLock<Vec<String>>>,
 }
 
 impl TaskLogger {
@@ -30,7 +34,21 @@ impl TaskLogger {
         // 启动后台消费者 task
         tokio::spawn(Self::consumer(receiver, log_path));
 
-        Self { sender }
+        Self { sender, secrets: Arc::new(RwLock::new(vec![])) }
+    }
+
+    /// 注册需要脱敏的密钥值, 之后记录的日志中出现的密钥值都会被替换
+    pub fn add_secrets(&self, values: &[String]) {
+        let mut secrets = self.secrets.write().unwrap();
+        for value in values {
+            for fragment in secret_fragments(value) {
+                if !secrets.contains(&fragment) {
+                    secrets.push(fragment);
+                }
+            }
+        }
+        // 长度降序, 优先替换较长的密钥
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
     }
 
     /// 后台消费者：顺序处理所有日志命令
@@ -75,8 +93,9 @@ impl TaskLogger {
 
     /// 记录指定级别的日志
     pub fn log_with_level(&self, message: &str, level: LogLevel) -> Result<(), anyhow::Error> {
+        let message = redact(message, &self.secrets.read().unwrap());
         self.sender
-            .send(LogCommand::Log { message: message.to_string(), level })
+            .send(LogCommand::Log { message, level })
             .map_err(|_| anyhow::anyhow!("Log channel closed"))?;
         Ok(())
     }
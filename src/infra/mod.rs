@@ -6,3 +6,7 @@ pub mod config;
 pub mod db;
 pub mod git;
 pub mod logger;
+pub mod project_index;
+pub mod registry;
+pub mod schema;
+pub mod secret;
@@ -0,0 +1,148 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Cached project index keyed by (space, project)
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::Result;
+
+use crate::core::validator::{ConfigFileFormat, ConfigValidator};
+use crate::types::config::ConfKitProjectConfig;
+use crate::utils::fs::get_files_with_extensions_in_dir;
+
+/// 已加载的项目配置及其来源文件
+#[derive(Debug, Clone)]
+pub struct IndexedProject {
+    /// 项目配置文件
+    pub path: PathBuf,
+    /// 加载时文件的修改时间
+    pub modified: Option<SystemTime>,
+    pub config: ConfKitProjectConfig,
+}
+
+/// 已解析的文件, 解析失败时 config 为 None
+#[derive(Debug)]
+struct CachedFile {
+    modified: Option<SystemTime>,
+    config: Option<ConfKitProjectConfig>,
+}
+
+/// 已加载的 space
+#[derive(Debug)]
+struct IndexedSpace {
+    dir: String,
+    /// 项目名, 按文件名排序
+    projects: Vec<String>,
+}
+
+/// 项目索引
+///
+/// 首次访问某个 space 时扫描并解析目录下的项目文件, 之后的查找直接命中缓存。
+/// 文件变更后调用 `reload()`, 仅重新解析修改时间变化的文件。
+#[derive(Debug, Default)]
+pub struct ProjectIndex {
+    spaces: HashMap<String, IndexedSpace>,
+    projects: HashMap<(String, String), IndexedProject>,
+    files: HashMap<PathBuf, CachedFile>,
+}
+
+impl ProjectIndex {
+    /// space 是否已加载
+    pub fn is_loaded(&self, space_name: &str) -> bool {
+        self.spaces.contains_key(space_name)
+    }
+
+    /// 扫描并加载 space 目录下的项目文件, 未变化的文件复用已解析的结果
+    pub fn load_space(&mut self, space_name: &str, dir: &str) -> Result<()> {
+        let mut file_names = get_files_with_extensions_in_dir(dir, ConfigFileFormat::EXTENSIONS)?;
+        file_names.sort();
+
+        self.projects.retain(|(space, _), _| space != space_name);
+        let mut names = vec![];
+
+        for file_name in file_names {
+            let path = Path::new(dir).join(&file_name);
+            let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+
+            let cached = self.files.get(&path).filter(|file| file.modified == modified);
+            let config = match cached {
+                Some(file) => file.config.clone(),
+                None => {
+                    let config = Self::parse_file(&path)?;
+                    self.files
+                        .insert(path.clone(), CachedFile { modified, config: config.clone() });
+                    config
+                }
+            };
+            let Some(config) = config else {
+                continue;
+            };
+
+            let key = (space_name.to_string(), config.name.clone());
+            if let Some(first) = self.projects.get(&key) {
+                tracing::warn!(
+                    "Duplicate project '{}' in space '{}': {} is ignored (first defined in {})",
+                    config.name,
+                    space_name,
+                    path.display(),
+                    first.path.display()
+                );
+                continue;
+            }
+
+            names.push(config.name.clone());
+            self.projects.insert(key, IndexedProject { path, modified, config });
+        }
+
+        self.spaces
+            .insert(space_name.to_string(), IndexedSpace { dir: dir.to_string(), projects: names });
+        Ok(())
+    }
+
+    /// 重新扫描所有已加载的 space, 用于交互模式等长时间运行的场景
+    pub fn reload(&mut self) -> Result<()> {
+        let spaces: Vec<(String, String)> =
+            self.spaces.iter().map(|(name, space)| (name.clone(), space.dir.clone())).collect();
+
+        // 已删除的文件不再保留
+        self.files.retain(|path, _| path.is_file());
+
+        for (name, dir) in spaces {
+            self.load_space(&name, &dir)?;
+        }
+        Ok(())
+    }
+
+    /// space 下的项目, 按文件名排序; space 未加载时返回 None
+    pub fn projects(&self, space_name: &str) -> Option<Vec<&IndexedProject>> {
+        let space = self.spaces.get(space_name)?;
+        Some(
+            space
+                .projects
+                .iter()
+                .filter_map(|name| self.projects.get(&(space_name.to_string(), name.clone())))
+                .collect(),
+        )
+    }
+
+    /// 按 (space, project) 查找
+    pub fn get(&self, space_name: &str, project_name: &str) -> Option<&IndexedProject> {
+        self.projects.get(&(space_name.to_string(), project_name.to_string()))
+    }
+
+    /// 解析项目文件, 解析失败或含未知字段时输出带行列号的警告
+    fn parse_file(path: &Path) -> Result<Option<ConfKitProjectConfig>> {
+        let content = fs::read_to_string(path)?;
+        let (config, diagnostics) = ConfigValidator::parse_config::<ConfKitProjectConfig>(
+            &path.to_string_lossy(),
+            &content,
+        );
+        for diagnostic in diagnostics {
+            tracing::warn!("{}", diagnostic);
+        }
+        Ok(config)
+    }
+}
@@ -0,0 +1,257 @@
+//! Author: xiaoYown
+//! Created: 2026-10-19
+//! Description: Container registry credentials (docker config.json) and login checks
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use reqwest::header::WWW_AUTHENTICATE;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+use crate::types::config::RegistryAuth;
+use crate::utils::fs::write_private_file;
+
+/// docker config.json 中 Docker Hub 的凭据键
+const DOCKER_HUB_AUTH_KEY: &str = "https://index.docker.io/v1/";
+/// Docker Hub 的 registry API 地址
+const DOCKER_HUB_API: &str = "registry-1.docker.io";
+
+/// 镜像仓库主机名, 去除协议与路径, Docker Hub 的各种写法统一为 index.docker.io
+pub fn normalize_registry(registry: &str) -> String {
+    let host = registry
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .split('/')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    match host.as_str() {
+        "docker.io" | "index.docker.io" | DOCKER_HUB_API => "index.docker.io".to_string(),
+        _ => host,
+    }
+}
+
+/// registry API 地址, 本机地址与 `insecure` 时使用 http
+pub fn registry_base_url(registry: &str, insecure: bool) -> String {
+    let registry = registry.trim().trim_end_matches('/');
+    if registry.starts_with("http://") || registry.starts_with("https://") {
+        return registry.to_string();
+    }
+
+    let host = match normalize_registry(registry).as_str() {
+        "index.docker.io" => DOCKER_HUB_API.to_string(),
+        host => host.to_string(),
+    };
+    let hostname = host.rsplit_once(':').map_or(host.as_str(), |(name, _)| name);
+    let local = matches!(hostname, "localhost" | "[::1]") || hostname.starts_with("127.");
+
+    if insecure || local {
+        format!("http://{host}")
+    } else {
+        format!("https://{host}")
+    }
+}
+
+/// docker 配置文件 (`$DOCKER_CONFIG/config.json` 或 `~/.docker/config.json`)
+///
+/// podman 与 nerdctl 同样读取该文件中的 `auths`; 写入时保留其余字段。
+#[derive(Debug)]
+pub struct DockerConfig {
+    path: PathBuf,
+    value: Value,
+}
+
+impl DockerConfig {
+    pub fn default_path() -> PathBuf {
+        match std::env::var("DOCKER_CONFIG") {
+            Ok(dir) if !dir.trim().is_empty() => PathBuf::from(dir).join("config.json"),
+            _ => {
+                PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".docker/config.json")
+            }
+        }
+    }
+
+    /// 读取配置文件, 文件不存在时为空配置
+    pub fn load(path: &Path) -> Result<Self> {
+        let value = if path.is_file() {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?
+        } else {
+            json!({})
+        };
+
+        Ok(Self { path: path.to_path_buf(), value })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 配置了凭据助手 (credsStore) 时, 引擎命令行不读取 `auths` 中的凭据
+    pub fn credentials_store(&self) -> Option<&str> {
+        self.value["credsStore"].as_str()
+    }
+
+    /// `auths` 中指定仓库的凭据, 仅支持 `auth` 字段 (base64 的 user:password)
+    pub fn auth(&self, registry: &str) -> Result<Option<RegistryAuth>> {
+        let Some(auths) = self.value["auths"].as_object() else {
+            return Ok(None);
+        };
+
+        let host = normalize_registry(registry);
+        let Some(entry) = auths.iter().find(|(key, _)| normalize_registry(key) == host) else {
+            return Ok(None);
+        };
+        let Some(encoded) = entry.1["auth"].as_str().filter(|auth| !auth.is_empty()) else {
+            return Ok(None);
+        };
+
+        let decoded = STANDARD.decode(encoded).with_context(|| {
+            format!("Invalid auth for '{}' in {}", entry.0, self.path.display())
+        })?;
+        let decoded = String::from_utf8(decoded)?;
+        let (username, password) = decoded.split_once(':').ok_or_else(|| {
+            anyhow::anyhow!("Invalid auth for '{}' in {}", entry.0, self.path.display())
+        })?;
+
+        Ok(Some(RegistryAuth {
+            username: username.to_string(),
+            password: password.to_string(),
+            server_address: registry.to_string(),
+        }))
+    }
+
+    /// 写入凭据, 替换该仓库已有的条目
+    pub fn set_auth(&mut self, auth: &RegistryAuth) {
+        self.remove_auth(&auth.server_address);
+
+        let key = match normalize_registry(&auth.server_address).as_str() {
+            "index.docker.io" => DOCKER_HUB_AUTH_KEY.to_string(),
+            host => host.to_string(),
+        };
+        let encoded = STANDARD.encode(format!("{}:{}", auth.username, auth.password));
+
+        if !self.value.is_object() {
+            self.value = json!({});
+        }
+        if !self.value["auths"].is_object() {
+            self.value["auths"] = json!({});
+        }
+        self.value["auths"][key] = json!({ "auth": encoded });
+    }
+
+    /// 移除仓库凭据, 返回是否存在
+    pub fn remove_auth(&mut self, registry: &str) -> bool {
+        let host = normalize_registry(registry);
+        let Some(auths) = self.value["auths"].as_object_mut() else {
+            return false;
+        };
+
+        let before = auths.len();
+        auths.retain(|key, _| normalize_registry(key) != host);
+        auths.len() != before
+    }
+
+    /// 去掉凭据 (auths / credsStore / credHelpers) 的副本, 保存位置为 path
+    ///
+    /// 保留 currentContext 等其他设置, 用于在临时目录中登录
+    pub fn without_credentials(&self, path: PathBuf) -> Self {
+        let mut value = self.value.clone();
+        if let Some(fields) = value.as_object_mut() {
+            for field in ["auths", "credsStore", "credHelpers"] {
+                fields.remove(field);
+            }
+        }
+        Self { path, value }
+    }
+
+    /// 保存配置文件, 仅当前用户可读写
+    pub fn save(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(&self.value)? + "\n";
+        write_private_file(&self.path, content.as_bytes())
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+/// 解析 `WWW-Authenticate`, 返回 (scheme, 参数)
+pub fn parse_challenge(header: &str) -> (String, HashMap<String, String>) {
+    let (scheme, rest) = header.trim().split_once(' ').unwrap_or((header.trim(), ""));
+    let pattern = regex::Regex::new(r#"(\w+)="([^"]*)""#).unwrap();
+    let params =
+        pattern.captures_iter(rest).map(|caps| (caps[1].to_lowercase(), caps[2].to_string()));
+
+    (scheme.to_lowercase(), params.collect())
+}
+
+/// registry v2 API 客户端, 用于登录前校验凭据
+pub struct RegistryClient {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl RegistryClient {
+    pub fn new(registry: &str, insecure: bool) -> Self {
+        Self { base_url: registry_base_url(registry, insecure), client: reqwest::Client::new() }
+    }
+
+    /// 校验凭据: 请求 `/v2/`, 按返回的质询使用 Basic 认证或向 token 服务换取令牌
+    pub async fn check_login(&self, auth: &RegistryAuth) -> Result<()> {
+        let url = format!("{}/v2/", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("Cannot connect to registry at {}", self.base_url))?;
+
+        match response.status() {
+            // 仓库无需认证
+            status if status.is_success() => return Ok(()),
+            StatusCode::UNAUTHORIZED => {}
+            status => anyhow::bail!("Registry {} returned {}", self.base_url, status),
+        }
+
+        let challenge = response
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|value| value.to_str().ok())
+            .map(parse_challenge);
+
+        let request = match challenge {
+            Some((scheme, params)) if scheme == "bearer" => {
+                let realm = params.get("realm").ok_or_else(|| {
+                    anyhow::anyhow!("Registry {} sent a bearer challenge without realm", url)
+                })?;
+                let mut query = vec![("account", auth.username.as_str())];
+                if let Some(service) = params.get("service") {
+                    query.push(("service", service));
+                }
+                self.client.get(realm).query(&query)
+            }
+            _ => self.client.get(&url),
+        };
+
+        let status = request
+            .basic_auth(&auth.username, Some(&auth.password))
+            .send()
+            .await
+            .with_context(|| format!("Cannot connect to registry at {}", self.base_url))?
+            .status();
+
+        match status {
+            status if status.is_success() => Ok(()),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                anyhow::bail!("Login to {} failed: invalid username or password", self.base_url)
+            }
+            status => anyhow::bail!("Login to {} failed: {}", self.base_url, status),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: JSON Schema of config files
+
+use anyhow::Result;
+use schemars::gen::SchemaSettings;
+use schemars::schema::RootSchema;
+
+use crate::types::config::{ConfKitConfig, ConfKitProjectConfig};
+
+/// 配置文件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSchemaKind {
+    /// 主配置文件 (.confkit.yml)
+    Root,
+    /// space 下的项目配置文件
+    Project,
+}
+
+pub struct ConfigSchema;
+
+impl ConfigSchema {
+    /// 由配置类型生成 JSON Schema (draft-07)
+    pub fn generate(kind: ConfigSchemaKind) -> RootSchema {
+        let generator = SchemaSettings::draft07().into_generator();
+        match kind {
+            ConfigSchemaKind::Root => generator.into_root_schema_for::<ConfKitConfig>(),
+            ConfigSchemaKind::Project => generator.into_root_schema_for::<ConfKitProjectConfig>(),
+        }
+    }
+
+    /// 格式化输出的 JSON Schema
+    pub fn to_json(kind: ConfigSchemaKind) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&Self::generate(kind))?)
+    }
+}
@@ -0,0 +1,130 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Secret cipher (ChaCha20-Poly1305)
+
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::shared::constants::{CONFKIT_SECRETS_DIR, SECRET_KEY_FILE};
+use crate::utils::fs::write_private_file;
+
+/// 加密文件头, 用于识别格式版本
+pub const SECRET_FILE_HEADER: &str = "CONFKIT-SECRETS-V1";
+
+/// 密钥加解密器
+pub struct SecretCipher {
+    key: LessSafeKey,
+}
+
+impl SecretCipher {
+    /// 主密钥文件路径: .confkit/secrets/master.key
+    pub fn key_path() -> PathBuf {
+        Path::new(CONFKIT_SECRETS_DIR).join(SECRET_KEY_FILE)
+    }
+
+    /// 从主密钥文件加载
+    pub fn load() -> Result<Self> {
+        let key_path = Self::key_path();
+        let encoded = fs::read_to_string(&key_path).map_err(|e| {
+            anyhow::anyhow!("Failed to read secret key '{}': {}", key_path.display(), e)
+        })?;
+        Self::from_encoded_key(encoded.trim())
+    }
+
+    /// 加载主密钥, 不存在时生成新密钥
+    pub fn load_or_create() -> Result<Self> {
+        if Self::key_path().exists() {
+            return Self::load();
+        }
+
+        let encoded = Self::generate_key()?;
+        Self::write_key(&Self::key_path(), &encoded)?;
+        tracing::info!("Generated secret key: {}", Self::key_path().display());
+        Self::from_encoded_key(&encoded)
+    }
+
+    /// 生成 base64 编码的随机主密钥
+    pub fn generate_key() -> Result<String> {
+        let mut bytes = vec![0u8; CHACHA20_POLY1305.key_len()];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| anyhow::anyhow!("Failed to generate secret key"))?;
+        Ok(BASE64.encode(bytes))
+    }
+
+    /// 写入主密钥文件 (仅所有者可读写)
+    ///
+    /// 同时在密钥目录写入 .gitignore, 避免主密钥被提交。
+    pub fn write_key(path: &Path, encoded: &str) -> Result<()> {
+        write_private_file(path, format!("{encoded}\n").as_bytes())?;
+
+        if let Some(dir) = path.parent() {
+            let gitignore = dir.join(".gitignore");
+            if !gitignore.exists() {
+                fs::write(gitignore, "*.key\n*.key.old\n")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 从 base64 编码的主密钥创建
+    pub fn from_encoded_key(encoded: &str) -> Result<Self> {
+        let bytes = BASE64
+            .decode(encoded)
+            .map_err(|e| anyhow::anyhow!("Invalid secret key encoding: {}", e))?;
+        let unbound = UnboundKey::new(&CHACHA20_POLY1305, &bytes)
+            .map_err(|_| anyhow::anyhow!("Invalid secret key length: {} bytes", bytes.len()))?;
+        Ok(Self { key: LessSafeKey::new(unbound) })
+    }
+
+    /// 加密明文为加密文件内容, `scope` 作为附加数据绑定到密文
+    pub fn encrypt(&self, plaintext: &str, scope: &str) -> Result<String> {
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce_bytes)
+            .map_err(|_| anyhow::anyhow!("Failed to generate nonce"))?;
+        let nonce = Nonce::assume_unique_for_key(nonce_bytes);
+
+        let mut data = plaintext.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(nonce, Aad::from(scope.as_bytes()), &mut data)
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt secrets"))?;
+
+        let mut payload = nonce_bytes.to_vec();
+        payload.extend_from_slice(&data);
+
+        Ok(format!("{SECRET_FILE_HEADER}\n{}\n", BASE64.encode(payload)))
+    }
+
+    /// 解密加密文件内容, `scope` 为加密时绑定的附加数据
+    pub fn decrypt(&self, content: &str, scope: &str) -> Result<String> {
+        let mut lines = content.lines();
+        if lines.next().map(str::trim) != Some(SECRET_FILE_HEADER) {
+            return Err(anyhow::anyhow!("Unrecognized secret file format"));
+        }
+
+        let payload: String = lines.map(str::trim).collect();
+        let mut data = BASE64
+            .decode(payload)
+            .map_err(|e| anyhow::anyhow!("Invalid secret file encoding: {}", e))?;
+
+        if data.len() < NONCE_LEN {
+            return Err(anyhow::anyhow!("Secret file is truncated"));
+        }
+
+        let mut ciphertext = data.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&data)
+            .map_err(|_| anyhow::anyhow!("Invalid secret file nonce"))?;
+
+        let plaintext =
+            self.key.open_in_place(nonce, Aad::from(scope.as_bytes()), &mut ciphertext).map_err(
+                |_| anyhow::anyhow!("Failed to decrypt secret file: wrong key or corrupted data"),
+            )?;
+
+        Ok(String::from_utf8(plaintext.to_vec())?)
+    }
+}
@@ -0,0 +1,166 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Secret resolving
+
+pub mod cipher;
+pub mod store;
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fs;
+
+use crate::types::config::{ConfKitSecretConfig, ConfKitSecretSource};
+
+pub use store::{SecretScope, SecretStore};
+
+/// 密钥引用前缀: `${secret:NAME}`
+const SECRET_REFERENCE_PREFIX: &str = "${secret:";
+
+/// 替换 `${secret:NAME}` 引用后的环境变量
+#[derive(Debug, Default)]
+pub struct SecretReferences {
+    /// 替换引用后的变量
+    pub environment: HashMap<String, String>,
+    /// 含有密钥引用的变量名
+    pub keys: Vec<String>,
+    /// 被引用的密钥值
+    pub values: Vec<String>,
+}
+
+pub struct SecretResolver;
+
+impl SecretResolver {
+    /// 解析项目声明的全部密钥, 返回 (变量名, 密钥值)
+    ///
+    /// 任意密钥无法解析时返回错误, 避免任务以缺失的凭据继续执行。
+    pub fn resolve(
+        space_name: &str,
+        project_name: &str,
+        secrets: &[ConfKitSecretConfig],
+    ) -> Result<HashMap<String, String>> {
+        Self::resolve_with(secrets, |key| Self::lookup(space_name, project_name, key))
+    }
+
+    /// 解析不属于项目的密钥 (如镜像推送凭据), from: encrypted 只在 space 级加密存储中查找
+    pub fn resolve_in_space(
+        space_name: &str,
+        secrets: &[ConfKitSecretConfig],
+    ) -> Result<HashMap<String, String>> {
+        Self::resolve_with(secrets, |key| {
+            SecretStore::get(&SecretScope::space(space_name), key)?.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Secret '{}' not found in encrypted secrets of space '{}'",
+                    key,
+                    space_name
+                )
+            })
+        })
+    }
+
+    fn resolve_with(
+        secrets: &[ConfKitSecretConfig],
+        lookup: impl Fn(&str) -> Result<String>,
+    ) -> Result<HashMap<String, String>> {
+        let mut resolved = HashMap::new();
+
+        for secret in secrets {
+            let value = match secret.from {
+                ConfKitSecretSource::File => {
+                    let path = secret.path.as_deref().ok_or_else(|| {
+                        anyhow::anyhow!("Secret '{}' requires 'path' for from: file", secret.name)
+                    })?;
+                    let content = fs::read_to_string(path).map_err(|e| {
+                        anyhow::anyhow!(
+                            "Failed to read secret '{}' from '{}': {}",
+                            secret.name,
+                            path,
+                            e
+                        )
+                    })?;
+                    content.trim_end_matches(['\r', '\n']).to_string()
+                }
+                ConfKitSecretSource::Env => {
+                    let env_name = secret.env.as_deref().unwrap_or(&secret.name);
+                    std::env::var(env_name).map_err(|_| {
+                        anyhow::anyhow!(
+                            "Secret '{}' not found in host environment variable '{}'",
+                            secret.name,
+                            env_name
+                        )
+                    })?
+                }
+                ConfKitSecretSource::Encrypted => {
+                    let key = secret.key.as_deref().unwrap_or(&secret.name);
+                    lookup(key)?
+                }
+            };
+
+            resolved.insert(secret.name.clone(), value);
+        }
+
+        Ok(resolved)
+    }
+
+    /// 替换环境变量值中的 `${secret:NAME}` 引用
+    ///
+    /// 引用按 项目 -> space 的顺序在加密存储中查找, 未找到时返回错误。
+    pub fn resolve_references(
+        space_name: &str,
+        project_name: &str,
+        environment: &HashMap<String, String>,
+    ) -> Result<SecretReferences> {
+        let mut references = SecretReferences::default();
+        // 同一密钥只解密一次
+        let mut cache: HashMap<String, String> = HashMap::new();
+
+        for (key, value) in environment {
+            let mut result = String::new();
+            let mut rest = value.as_str();
+            let mut referenced = false;
+
+            while let Some(start) = rest.find(SECRET_REFERENCE_PREFIX) {
+                let after = &rest[start + SECRET_REFERENCE_PREFIX.len()..];
+                let Some(end) = after.find('}') else {
+                    break;
+                };
+
+                let name = after[..end].trim();
+                let secret = match cache.get(name) {
+                    Some(secret) => secret.clone(),
+                    None => {
+                        let secret = Self::lookup(space_name, project_name, name)?;
+                        cache.insert(name.to_string(), secret.clone());
+                        secret
+                    }
+                };
+
+                result.push_str(&rest[..start]);
+                result.push_str(&secret);
+                rest = &after[end + 1..];
+                referenced = true;
+            }
+            result.push_str(rest);
+
+            if referenced {
+                references.keys.push(key.clone());
+            }
+            references.environment.insert(key.clone(), result);
+        }
+
+        references.values = cache.into_values().collect();
+        references.keys.sort();
+        Ok(references)
+    }
+
+    /// 在加密存储中查找密钥
+    fn lookup(space_name: &str, project_name: &str, name: &str) -> Result<String> {
+        SecretStore::lookup(space_name, project_name, name)?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Secret '{}' not found in encrypted secrets of project '{}' or space '{}'",
+                name,
+                project_name,
+                space_name
+            )
+        })
+    }
+}
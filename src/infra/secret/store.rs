@@ -0,0 +1,216 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Encrypted secret store of a space / project
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::cipher::SecretCipher;
+use crate::shared::constants::CONFKIT_SECRETS_DIR;
+use crate::utils::fs::write_private_file;
+
+/// 加密文件扩展名
+const SECRET_FILE_EXTENSION: &str = "enc";
+
+/// 密钥作用域: space 级或 space 下的项目级
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretScope {
+    pub space: String,
+    pub project: Option<String>,
+}
+
+impl SecretScope {
+    pub fn space(space_name: &str) -> Self {
+        Self { space: space_name.to_string(), project: None }
+    }
+
+    pub fn project(space_name: &str, project_name: &str) -> Self {
+        Self { space: space_name.to_string(), project: Some(project_name.to_string()) }
+    }
+
+    /// 加密文件路径
+    ///
+    /// - space: .confkit/secrets/{space}.enc
+    /// - project: .confkit/secrets/{space}/{project}.enc
+    pub fn file_path(&self) -> PathBuf {
+        let dir = Path::new(CONFKIT_SECRETS_DIR);
+        match &self.project {
+            Some(project) => {
+                dir.join(&self.space).join(format!("{project}.{SECRET_FILE_EXTENSION}"))
+            }
+            None => dir.join(format!("{}.{SECRET_FILE_EXTENSION}", self.space)),
+        }
+    }
+
+    /// 加密时绑定的附加数据, 防止不同作用域的加密文件被互相替换
+    fn aad(&self) -> String {
+        format!("confkit:{self}")
+    }
+}
+
+impl fmt::Display for SecretScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.project {
+            Some(project) => write!(f, "{}/{}", self.space, project),
+            None => write!(f, "{}", self.space),
+        }
+    }
+}
+
+/// 加密密钥存储
+///
+/// 明文只在内存中处理, 磁盘上仅保存密文。
+pub struct SecretStore;
+
+impl SecretStore {
+    /// 读取并解密作用域内的全部密钥, 加密文件不存在时返回空
+    pub fn load(scope: &SecretScope) -> Result<BTreeMap<String, String>> {
+        let path = scope.file_path();
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        Self::load_with(&SecretCipher::load()?, scope)
+    }
+
+    /// 加密并写入作用域内的全部密钥, 主密钥不存在时自动生成
+    pub fn save(scope: &SecretScope, secrets: &BTreeMap<String, String>) -> Result<()> {
+        Self::save_with(&SecretCipher::load_or_create()?, scope, secrets)
+    }
+
+    /// 写入单个密钥
+    pub fn set(scope: &SecretScope, name: &str, value: &str) -> Result<()> {
+        let mut secrets = Self::load(scope)?;
+        secrets.insert(name.to_string(), value.to_string());
+        Self::save(scope, &secrets)
+    }
+
+    /// 读取单个密钥
+    pub fn get(scope: &SecretScope, name: &str) -> Result<Option<String>> {
+        Ok(Self::load(scope)?.remove(name))
+    }
+
+    /// 删除单个密钥, 返回是否存在
+    ///
+    /// 作用域内不再有密钥时删除加密文件。
+    pub fn remove(scope: &SecretScope, name: &str) -> Result<bool> {
+        let mut secrets = Self::load(scope)?;
+        if secrets.remove(name).is_none() {
+            return Ok(false);
+        }
+
+        if secrets.is_empty() {
+            fs::remove_file(scope.file_path())?;
+        } else {
+            Self::save(scope, &secrets)?;
+        }
+        Ok(true)
+    }
+
+    /// 作用域内的密钥名称列表
+    pub fn list(scope: &SecretScope) -> Result<Vec<String>> {
+        Ok(Self::load(scope)?.into_keys().collect())
+    }
+
+    /// 按 项目 -> space 的顺序查找密钥
+    pub fn lookup(space_name: &str, project_name: &str, name: &str) -> Result<Option<String>> {
+        for scope in
+            [SecretScope::project(space_name, project_name), SecretScope::space(space_name)]
+        {
+            if let Some(value) = Self::get(&scope, name)? {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 轮换主密钥: 以新密钥重新加密全部加密文件, 返回处理的文件数
+    ///
+    /// 旧主密钥保留为 master.key.old, 便于轮换中断时恢复。
+    pub fn rotate_key() -> Result<usize> {
+        let old_cipher = SecretCipher::load()?;
+        let scopes = Self::scopes()?;
+
+        // 先用旧密钥解密全部文件, 任一文件失败则不做任何修改
+        let mut decrypted = Vec::with_capacity(scopes.len());
+        for scope in scopes {
+            let secrets = Self::load_with(&old_cipher, &scope)?;
+            decrypted.push((scope, secrets));
+        }
+
+        let encoded = SecretCipher::generate_key()?;
+        let new_cipher = SecretCipher::from_encoded_key(&encoded)?;
+
+        let key_path = SecretCipher::key_path();
+        let backup_path = key_path.with_extension("key.old");
+        fs::copy(&key_path, &backup_path)?;
+        SecretCipher::write_key(&key_path, &encoded)?;
+
+        for (scope, secrets) in &decrypted {
+            Self::save_with(&new_cipher, scope, secrets)?;
+        }
+
+        Ok(decrypted.len())
+    }
+
+    /// 全部已存在的加密文件作用域
+    pub fn scopes() -> Result<Vec<SecretScope>> {
+        let mut scopes = Vec::new();
+        let dir = Path::new(CONFKIT_SECRETS_DIR);
+        if !dir.exists() {
+            return Ok(scopes);
+        }
+
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                let space = file_name(&path);
+                for project_entry in fs::read_dir(&path)? {
+                    let project_path = project_entry?.path();
+                    if is_secret_file(&project_path) {
+                        scopes.push(SecretScope::project(&space, &file_stem(&project_path)));
+                    }
+                }
+            } else if is_secret_file(&path) {
+                scopes.push(SecretScope::space(&file_stem(&path)));
+            }
+        }
+
+        scopes.sort_by_key(|scope| scope.to_string());
+        Ok(scopes)
+    }
+
+    fn load_with(cipher: &SecretCipher, scope: &SecretScope) -> Result<BTreeMap<String, String>> {
+        let path = scope.file_path();
+        let content = fs::read_to_string(&path)?;
+        let plaintext = cipher
+            .decrypt(&content, &scope.aad())
+            .map_err(|e| anyhow::anyhow!("{} ({})", e, path.display()))?;
+
+        Ok(serde_yaml::from_str::<BTreeMap<String, String>>(&plaintext)?)
+    }
+
+    fn save_with(
+        cipher: &SecretCipher,
+        scope: &SecretScope,
+        secrets: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        let plaintext = serde_yaml::to_string(secrets)?;
+        let content = cipher.encrypt(&plaintext, &scope.aad())?;
+        write_private_file(&scope.file_path(), content.as_bytes())
+    }
+}
+
+fn is_secret_file(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|ext| ext == SECRET_FILE_EXTENSION)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default()
+}
@@ -91,22 +91,33 @@ async fn main() -> Result<()> {
 
     // 初始化日志系统
     // INFO/DEBUG/TRACE → stdout, WARN/ERROR → stderr
-    use tracing_subscriber::fmt::writer::MakeWriterExt;
-    let stdout = std::io::stdout.with_min_level(tracing::Level::INFO);
-    let stderr = std::io::stderr.with_max_level(tracing::Level::WARN);
+    // 命令向 stdout 输出数据 (如 JSON) 时, 日志全部输出到 stderr
+    use tracing_subscriber::fmt::writer::{BoxMakeWriter, MakeWriterExt};
+    let writer = if cli.writes_data_to_stdout() {
+        BoxMakeWriter::new(std::io::stderr)
+    } else {
+        let stdout = std::io::stdout.with_min_level(tracing::Level::INFO);
+        let stderr = std::io::stderr.with_max_level(tracing::Level::WARN);
+        BoxMakeWriter::new(stdout.and(stderr))
+    };
 
     tracing_subscriber::fmt()
         .without_time()
         .with_max_level(log_level)
         .with_target(show_path)
         .with_level(!cli.hide_level)
-        .with_writer(stdout.and(stderr))
+        .with_writer(writer)
         .init();
 
+    // 无需项目配置与容器引擎的命令直接执行
+    if !cli.requires_project() {
+        return cli.execute().await;
+    }
+
     // 检查配置文件是否存在
     if !ConfKitConfigLoader::is_config_file_exists().await {
         tracing::error!(
-            "✗ .confkit.yml not found in current directory. This is not a confkit project."
+            "✗ .confkit.yml (or .confkit.toml / .confkit.json) not found in current directory. This is not a confkit project."
         );
         std::process::exit(1);
     }
@@ -121,16 +132,21 @@ async fn main() -> Result<()> {
         tracing::warn!("Failed to initialize task database: {}", e);
     }
 
-    tracing::debug!("Loading .confkit.yml...");
+    tracing::debug!("Loading config file...");
     // 加载全局配置文件
     if let Err(e) = ConfKitConfigLoader::set_config().await {
-        tracing::error!("✗ .confkit.yml not found in current directory: {}", e);
+        tracing::error!("✗ Failed to load config file: {}", e);
         std::process::exit(1);
     }
 
     tracing::debug!("Setting engine...");
     // 设置当前宿主机使用的引擎
-    if let Err(e) = ConfKitEngine::set_engine(ConfKitConfigLoader::get_config().engine).await {
+    let config = ConfKitConfigLoader::get_config();
+    if let Err(e) = ConfKitEngine::set_mode(&config.engine, config.engine_mode) {
+        tracing::error!("✗ Failed to set engine: {}", e);
+        std::process::exit(1);
+    }
+    if let Err(e) = ConfKitEngine::set_engine(config.engine).await {
         tracing::error!("✗ Failed to set engine: {}", e);
         std::process::exit(1);
     }
@@ -5,6 +5,9 @@
 /// 配置文件名
 pub const CONFKIT_CONFIG_FILE: &str = ".confkit.yml";
 
+/// 支持的配置文件名, 同时存在时按顺序取第一个
+pub const CONFKIT_CONFIG_FILES: &[&str] = &[".confkit.yml", ".confkit.toml", ".confkit.json"];
+
 /// Volumes 根目录
 pub const HOST_VOLUMES_DIR: &str = "volumes";
 
@@ -29,8 +32,20 @@ pub const HOST_CACHE_DIR: &str = "volumes/cache";
 /// 临时目录(用于临时文件存储, 仓库信息获取等)
 pub const HOST_TEMP_DIR: &str = "volumes/temp";
 
+/// 密钥目录(主密钥与加密密钥文件)
+pub const CONFKIT_SECRETS_DIR: &str = ".confkit/secrets";
+
+/// 主密钥文件名
+pub const SECRET_KEY_FILE: &str = "master.key";
+
 /// 任务数据库文件名
 pub const TASK_DB_FILE: &str = "tasks.db";
 
+/// 构建镜像时自动添加的标签
+pub const IMAGE_LABEL_CONFKIT_VERSION: &str = "io.confkit.version";
+pub const IMAGE_LABEL_CONFKIT_IMAGE: &str = "io.confkit.image";
+/// Dockerfile 与构建上下文的内容哈希, 用于判断镜像是否需要重新构建
+pub const IMAGE_LABEL_CONTENT_HASH: &str = "io.confkit.content-hash";
+
 // /// 容器缓存目录
 // pub const CONTAINER_CACHE_DIR: &str = "/cache";
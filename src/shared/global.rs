@@ -6,6 +6,8 @@ use std::sync::RwLock;
 
 use once_cell::sync::Lazy;
 
+use crate::engine::registry::EngineRegistry;
+use crate::infra::project_index::ProjectIndex;
 use crate::types::config::ConfKitConfig;
 use crate::types::config::Engine;
 
@@ -14,3 +16,11 @@ pub static CONFIG: Lazy<RwLock<Option<ConfKitConfig>>> = Lazy::new(|| RwLock::ne
 
 // 缓存当前宿主机使用的引擎
 pub static ENGINE: Lazy<RwLock<Option<Engine>>> = Lazy::new(|| RwLock::new(None));
+
+// 引擎实现注册表
+pub static ENGINE_REGISTRY: Lazy<RwLock<EngineRegistry>> =
+    Lazy::new(|| RwLock::new(EngineRegistry::default()));
+
+// 缓存已加载的项目配置
+pub static PROJECT_INDEX: Lazy<RwLock<ProjectIndex>> =
+    Lazy::new(|| RwLock::new(ProjectIndex::default()));
@@ -27,6 +27,12 @@ pub enum ConditionExpression {
         left: Option<Box<ConditionExpression>>,
         right: Option<Box<ConditionExpression>>,
     },
+
+    /// 列表字面量：["a", "b", ${VAR}]
+    List { items: Vec<ConditionExpression> },
+
+    /// 函数调用：name(arg, ...)
+    Function { function: ConditionFunction, args: Vec<ConditionExpression> },
 }
 
 /// 条件值类型
@@ -38,6 +44,10 @@ pub enum ConditionValue {
     Number(f64),
     /// 布尔值
     Boolean(bool),
+    /// 语义化版本
+    Version(semver::Version),
+    /// 列表值
+    List(Vec<ConditionValue>),
     /// 空值
     Null,
 }
@@ -57,6 +67,31 @@ pub enum ComparisonOperator {
     GreaterThanOrEqual,
     /// 小于等于 <=
     LessThanOrEqual,
+    /// 包含于列表 in
+    In,
+    /// 不包含于列表 not in
+    NotIn,
+    /// 正则匹配 matches
+    Matches,
+}
+
+/// 内置函数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConditionFunction {
+    /// contains(haystack, needle)：子串或列表元素
+    Contains,
+    /// startsWith(value, prefix)
+    StartsWith,
+    /// endsWith(value, suffix)
+    EndsWith,
+    /// matches(value, pattern)：正则匹配
+    Matches,
+    /// len(value)：字符串字符数或列表长度
+    Len,
+    /// lower(value)：转小写
+    Lower,
+    /// semver(value)：按语义化版本比较
+    Semver,
 }
 
 /// 逻辑运算符
@@ -70,6 +105,54 @@ pub enum LogicalOperator {
     Not,
 }
 
+/// 诊断信息语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageLang {
+    En,
+    Zh,
+}
+
+impl MessageLang {
+    /// 依次读取 CONFKIT_LANG、LC_ALL、LC_MESSAGES、LANG, 以 zh 开头时使用中文
+    pub fn from_env() -> Self {
+        let lang = ["CONFKIT_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|key| std::env::var(key).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+
+        if lang.to_lowercase().starts_with("zh") {
+            MessageLang::Zh
+        } else {
+            MessageLang::En
+        }
+    }
+}
+
+impl ConditionFunction {
+    /// 按名称查找函数
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "contains" => Some(ConditionFunction::Contains),
+            "startsWith" => Some(ConditionFunction::StartsWith),
+            "endsWith" => Some(ConditionFunction::EndsWith),
+            "matches" => Some(ConditionFunction::Matches),
+            "len" => Some(ConditionFunction::Len),
+            "lower" => Some(ConditionFunction::Lower),
+            "semver" => Some(ConditionFunction::Semver),
+            _ => None,
+        }
+    }
+
+    /// 参数个数
+    pub fn arity(&self) -> usize {
+        match self {
+            ConditionFunction::Len | ConditionFunction::Lower | ConditionFunction::Semver => 1,
+            _ => 2,
+        }
+    }
+}
+
 impl ConditionValue {
     /// 从字符串创建条件值，尝试解析为数字或布尔值
     pub fn from_string(s: &str) -> Self {
@@ -96,6 +179,8 @@ impl ConditionValue {
             ConditionValue::Boolean(b) => *b,
             ConditionValue::Number(n) => *n != 0.0,
             ConditionValue::String(s) => !s.is_empty(),
+            ConditionValue::Version(_) => true,
+            ConditionValue::List(items) => !items.is_empty(),
             ConditionValue::Null => false,
         }
     }
@@ -110,6 +195,9 @@ impl std::fmt::Display for ComparisonOperator {
             ComparisonOperator::LessThan => write!(f, "<"),
             ComparisonOperator::GreaterThanOrEqual => write!(f, ">="),
             ComparisonOperator::LessThanOrEqual => write!(f, "<="),
+            ComparisonOperator::In => write!(f, "in"),
+            ComparisonOperator::NotIn => write!(f, "not in"),
+            ComparisonOperator::Matches => write!(f, "matches"),
         }
     }
 }
@@ -130,7 +218,53 @@ impl fmt::Display for ConditionValue {
             ConditionValue::String(s) => write!(f, "{}", s),
             ConditionValue::Number(n) => write!(f, "{}", n),
             ConditionValue::Boolean(b) => write!(f, "{}", b),
+            ConditionValue::Version(v) => write!(f, "{}", v),
+            ConditionValue::List(items) => {
+                let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
             ConditionValue::Null => write!(f, ""),
         }
     }
 }
+
+/// 解析语义化版本, 允许 `v` 前缀
+///
+/// lenient 为 true 时缺省的 minor/patch 补 0, 如 `1.2` 视为 `1.2.0`。
+pub fn parse_version(s: &str, lenient: bool) -> Option<semver::Version> {
+    let s = s.trim();
+    let s = s.strip_prefix(['v', 'V']).unwrap_or(s);
+    if let Ok(version) = semver::Version::parse(s) {
+        return Some(version);
+    }
+    if !lenient {
+        return None;
+    }
+
+    // 拆出 pre-release / build 后补全核心版本号
+    let split = s.find(['-', '+']).unwrap_or(s.len());
+    let (core, suffix) = s.split_at(split);
+    let parts: Vec<&str> = core.split('.').collect();
+    if parts.is_empty() || parts.len() > 3 || parts.iter().any(|p| p.is_empty()) {
+        return None;
+    }
+    let mut padded = parts.join(".");
+    for _ in parts.len()..3 {
+        padded.push_str(".0");
+    }
+    semver::Version::parse(&format!("{padded}{suffix}")).ok()
+}
+
+impl fmt::Display for ConditionFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConditionFunction::Contains => write!(f, "contains"),
+            ConditionFunction::StartsWith => write!(f, "startsWith"),
+            ConditionFunction::EndsWith => write!(f, "endsWith"),
+            ConditionFunction::Matches => write!(f, "matches"),
+            ConditionFunction::Len => write!(f, "len"),
+            ConditionFunction::Lower => write!(f, "lower"),
+            ConditionFunction::Semver => write!(f, "semver"),
+        }
+    }
+}
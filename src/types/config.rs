@@ -2,8 +2,12 @@
 //! Created: 2025-07-14
 //! Description: ConfKit Config Types
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
 
 /// ================================================ Engine infra types ================================================
 /// engine image info
@@ -79,33 +83,64 @@ pub struct EngineServiceConfig {
 }
 
 /// ================================================ ConfKit Config ================================================
-/// 项目配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 项目配置 (.confkit.yml)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfKitConfig {
+    /// 配置文件版本
     pub version: String,
+    /// 容器引擎
     #[serde(default = "default_engine")]
     pub engine: Engine,
+    /// 引擎调用方式, 缺省 cli
+    #[serde(default)]
+    pub engine_mode: EngineMode,
+    /// 构建器容器的 compose 配置
     pub engine_compose: ConfKitEngineComposeConfig,
+    /// 引入的配置文件 (支持 glob), 相对于 .confkit.yml 所在目录, 其中的 spaces / images 合并到当前配置
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// 空间列表
+    #[serde(default)]
     pub spaces: Vec<ConfKitSpaceConfig>,
+    /// 镜像管理列表
+    #[serde(default)]
     pub images: Vec<ConfKitImageConfig>,
     /// 是否打印环境变量, 缺省 false
     #[serde(default)]
     pub print_environment: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 通过 include 引入的配置文件
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ConfKitConfigInclude {
+    /// 空间列表
+    #[serde(default)]
+    pub spaces: Vec<ConfKitSpaceConfig>,
+    /// 镜像管理列表
+    #[serde(default)]
+    pub images: Vec<ConfKitImageConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfKitSpaceConfig {
+    /// 空间名称
     pub name: String,
+    /// 空间描述
     pub description: String,
+    /// 项目配置文件所在目录
     pub path: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfKitProjectConfig {
+    /// 项目名称, 同时作为 PROJECT_NAME 环境变量
     pub name: String,
+    /// 项目描述
     pub description: String,
+    /// 命令执行使用的 shell
     #[serde(default = "default_shell")]
     pub shell: ConfKitShellConfig,
+    /// Git 源配置
     pub source: Option<ConfKitSourceConfig>,
     /// 环境变量文件, 优先级最低
     pub environment_files: Option<Vec<ConfKitEnvironmentFileConfig>>,
@@ -113,58 +148,241 @@ pub struct ConfKitProjectConfig {
     pub environment: Option<HashMap<String, String>>,
     /// 来自参数的环境变量, 优先级最高(仅 interfactive 模式下生效)
     pub environment_from_args: Option<Vec<ConfKitEnvironmentInteractiveConfig>>,
+    /// 密钥, 以环境变量注入, 日志中自动脱敏
+    pub secrets: Option<Vec<ConfKitSecretConfig>>,
+    /// 任务结束后的清理配置
     pub cleaner: Option<ConfKitCleanerConfig>,
     /// 是否打印环境变量, 缺省 false, 优先级高于 .confkit.yml
     #[serde(default)]
     pub print_environment: Option<bool>,
+    /// 条件表达式解析或求值失败时的处理方式, 缺省 run, 可被步骤覆盖
+    #[serde(default)]
+    pub on_condition_error: Option<ConditionErrorPolicy>,
+    /// 构建步骤
     pub steps: Vec<ConfKitStepConfig>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfKitCleanerConfig {
+    /// 是否清理任务工作空间, 缺省 true
     pub workspace: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfKitSourceConfig {
+    /// Git 仓库地址
     pub git_repo: String,
+    /// 分支名称
     pub git_branch: String,
-    // 项目语言: javascript, rust
+    /// 项目语言: javascript, rust
     pub language: Option<String>,
-    // 项目配置文件: javascript: package.json, rust: Cargo.toml
+    /// 项目配置文件: javascript: package.json, rust: Cargo.toml
     pub manifest_file: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfKitStepConfig {
+    /// 步骤名称
     pub name: String,
+    /// 执行命令的构建器容器, 缺省在宿主机执行
     pub container: Option<String>,
+    /// 工作目录
     pub working_dir: Option<String>,
+    /// 命令列表
     pub commands: Vec<String>,
     /// 超时时间，单位：秒
     pub timeout: Option<u64>,
+    /// 失败后是否继续执行下一步
     #[serde(default)]
     pub continue_on_error: Option<bool>,
     /// 条件表达式，用于条件执行控制
     #[serde(default)]
     pub condition: Option<String>,
+    /// 条件表达式出错时的处理方式, 优先级高于项目配置
+    #[serde(default)]
+    pub on_condition_error: Option<ConditionErrorPolicy>,
+    /// 无容器引擎 (engine: none) 时配置了 container 的步骤的处理方式, 缺省失败
+    #[serde(default)]
+    pub fallback: Option<StepFallback>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 容器不可用时步骤的退路
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StepFallback {
+    /// 在宿主机执行
+    Host,
+}
+
+/// 条件表达式出错时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ConditionErrorPolicy {
+    /// 执行步骤
+    #[default]
+    Run,
+    /// 跳过步骤
+    Skip,
+    /// 步骤失败
+    Fail,
+}
+
+impl std::fmt::Display for ConditionErrorPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConditionErrorPolicy::Run => write!(f, "run"),
+            ConditionErrorPolicy::Skip => write!(f, "skip"),
+            ConditionErrorPolicy::Fail => write!(f, "fail"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfKitEnvironmentFileConfig {
-    /// yaml, env
-    pub format: String,
+    /// 文件格式
+    pub format: ConfKitEnvironmentFileFormat,
+    /// 文件路径
     pub path: String,
 }
 
+/// 环境变量文件格式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfKitEnvironmentFileFormat {
+    /// YAML 键值对
+    Yaml,
+    /// dotenv (.env)
+    Env,
+    /// JSON 顶层对象
+    Json,
+    /// TOML 顶层表
+    Toml,
+}
+
+impl fmt::Display for ConfKitEnvironmentFileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfKitEnvironmentFileFormat::Yaml => write!(f, "yaml"),
+            ConfKitEnvironmentFileFormat::Env => write!(f, "env"),
+            ConfKitEnvironmentFileFormat::Json => write!(f, "json"),
+            ConfKitEnvironmentFileFormat::Toml => write!(f, "toml"),
+        }
+    }
+}
+
+/// 密钥配置
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ConfKitSecretConfig {
+    /// 注入的环境变量名称
+    pub name: String,
+    /// 密钥来源
+    pub from: ConfKitSecretSource,
+    /// from: file 时的文件路径
+    pub path: Option<String>,
+    /// from: env 时的宿主机环境变量名称, 缺省与 name 相同
+    pub env: Option<String>,
+    /// from: encrypted 时加密文件中的键名, 缺省与 name 相同
+    pub key: Option<String>,
+}
+
+/// 密钥来源
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfKitSecretSource {
+    /// 文件内容 (去除末尾换行)
+    File,
+    /// 宿主机环境变量
+    Env,
+    /// space 加密密钥文件
+    Encrypted,
+}
+
 /// === ConfKit Config file image config ===
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfKitImageConfig {
+    /// 目标镜像名称
     pub name: String,
+    /// 基础镜像 (自动拉取)
     pub base_image: String,
+    /// 镜像标签 (基础镜像与目标镜像共用)
     pub tag: String,
+    /// 构建上下文目录
     pub context: String,
+    /// Dockerfile 路径
     pub engine_file: String,
+    /// 构建参数 (--build-arg)
+    #[serde(default)]
+    pub build_args: Option<BTreeMap<String, String>>,
+    /// 多阶段构建的目标阶段
+    #[serde(default)]
+    pub target: Option<String>,
+    /// 镜像标签, 覆盖自动添加的同名标签
+    #[serde(default)]
+    pub labels: Option<BTreeMap<String, String>>,
+    /// 目标平台, 如 linux/amd64
+    #[serde(default)]
+    pub platforms: Option<Vec<String>>,
+    /// 不使用构建缓存
+    #[serde(default)]
+    pub no_cache: Option<bool>,
+    /// 总是拉取基础镜像的最新版本
+    #[serde(default)]
+    pub pull: Option<bool>,
+    /// 构建密钥 (BuildKit `--secret`), name 为 Dockerfile 中引用的 id
+    #[serde(default)]
+    pub secrets: Option<Vec<ConfKitSecretConfig>>,
+    /// 推送到镜像仓库的配置
+    #[serde(default)]
+    pub push: Option<ConfKitImagePushConfig>,
+}
+
+/// 镜像推送配置
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ConfKitImagePushConfig {
+    /// 镜像仓库地址, 如 ghcr.io, localhost:5000
+    pub registry: String,
+    /// 仓库中的镜像路径, 缺省与镜像名称相同
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// 推送的标签, 支持 ${TAG}, ${GIT_HASH}, ${GIT_HASH_SHORT}, ${PROJECT_VERSION}, 缺省为镜像标签
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// 登录凭据, name 为 username / password 的密钥; 缺省读取 docker 配置文件
+    #[serde(default)]
+    pub credentials: Option<Vec<ConfKitSecretConfig>>,
+    /// from: encrypted 凭据所在的 space 加密存储
+    #[serde(default)]
+    pub space: Option<String>,
+}
+
+/// 镜像仓库登录凭据
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryAuth {
+    pub username: String,
+    pub password: String,
+    /// 镜像仓库地址
+    pub server_address: String,
+}
+
+/// 传给引擎的镜像构建选项
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImageBuildOptions {
+    pub build_args: BTreeMap<String, String>,
+    pub target: Option<String>,
+    pub labels: BTreeMap<String, String>,
+    pub platforms: Vec<String>,
+    pub no_cache: bool,
+    pub pull: bool,
+    pub secrets: Vec<BuildSecret>,
+}
+
+/// 构建密钥来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildSecret {
+    /// 文件内容
+    File { id: String, path: String },
+    /// 宿主机环境变量
+    Env { id: String, var: String },
 }
 
 /// === ConfKit Config file image info ===
@@ -185,28 +403,59 @@ pub struct ConfKitImageInfo {
     pub size: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfKitEngineComposeConfig {
+    /// 容器分组名称
     #[serde(default = "default_project")]
     pub project: String,
+    /// compose 文件路径
     #[serde(default)]
     pub file: String,
+    /// 构建器容器加入的 pod, 仅 podman 且 engine_mode 为 api 时生效
+    #[serde(default)]
+    pub pod: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfKitShellConfig {
+    /// 宿主机 shell
     #[serde(default = "default_bash")]
     pub host: String,
+    /// 容器内 shell
     #[serde(default = "default_bash")]
     pub container: String,
 }
 
 /// 宿主机使用的引擎
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Engine {
     Docker,
     Podman,
+    Nerdctl,
+    None,
+}
+
+impl fmt::Display for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Engine::Docker => write!(f, "docker"),
+            Engine::Podman => write!(f, "podman"),
+            Engine::Nerdctl => write!(f, "nerdctl"),
+            Engine::None => write!(f, "none"),
+        }
+    }
+}
+
+/// 引擎调用方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EngineMode {
+    /// 调用引擎命令行
+    #[default]
+    Cli,
+    /// 通过引擎 socket 调用 HTTP 接口
+    Api,
 }
 
 fn default_engine() -> Engine {
@@ -238,6 +487,8 @@ fn default_container_unbuilt() -> ContainerStatus {
 pub enum ImageStatus {
     Unbuilt,
     Built,
+    /// 已构建, 但 Dockerfile 或构建上下文已变更
+    Stale,
 }
 
 impl fmt::Display for ImageStatus {
@@ -245,6 +496,7 @@ impl fmt::Display for ImageStatus {
         match self {
             ImageStatus::Unbuilt => write!(f, "N/A"),
             ImageStatus::Built => write!(f, "Built"),
+            ImageStatus::Stale => write!(f, "Stale"),
         }
     }
 }
@@ -278,7 +530,7 @@ impl fmt::Display for ContainerStatus {
 }
 
 /// 交互式环境变量类型
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ConfKitInteractiveType {
     /// 输入框
@@ -292,7 +544,7 @@ pub enum ConfKitInteractiveType {
 }
 
 /// 交互式环境变量配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfKitEnvironmentInteractiveConfig {
     /// 环境变量名称
     pub name: String,
@@ -0,0 +1,104 @@
+//! Author: xiaoYown
+//! Created: 2026-10-19
+//! Description: `.dockerignore` matching for build contexts
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use glob::{MatchOptions, Pattern};
+use walkdir::WalkDir;
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// `.dockerignore` 规则
+///
+/// 与 docker 一致: 路径相对于构建上下文, 后出现的规则优先, `!` 开头的规则重新包含;
+/// 目录被排除时其下所有文件一并排除。
+#[derive(Debug, Default)]
+pub struct DockerIgnore {
+    rules: Vec<(Pattern, bool)>,
+}
+
+impl DockerIgnore {
+    pub fn parse(content: &str) -> Self {
+        let mut rules = vec![];
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (line, negated) = match line.strip_prefix('!') {
+                Some(rest) => (rest.trim(), true),
+                None => (line, false),
+            };
+            let line = line.trim_start_matches("./").trim_start_matches('/').trim_end_matches('/');
+            if line.is_empty() {
+                continue;
+            }
+
+            match Pattern::new(line) {
+                Ok(pattern) => rules.push((pattern, negated)),
+                Err(e) => tracing::warn!("Invalid .dockerignore pattern '{}': {}", line, e),
+            }
+        }
+
+        Self { rules }
+    }
+
+    /// 读取构建上下文中的 `.dockerignore`, 文件不存在时不排除任何文件
+    pub fn load(context: &Path) -> Result<Self> {
+        let path = context.join(".dockerignore");
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    /// 相对路径 (以 `/` 分隔) 是否被排除
+    pub fn is_ignored(&self, relative: &str) -> bool {
+        let mut ignored = false;
+
+        for (pattern, negated) in &self.rules {
+            // 路径自身或任一上级目录匹配即视为命中
+            let matched = relative
+                .match_indices('/')
+                .map(|(index, _)| &relative[..index])
+                .chain(std::iter::once(relative))
+                .any(|path| pattern.matches_with(path, MATCH_OPTIONS));
+            if matched {
+                ignored = !negated;
+            }
+        }
+
+        ignored
+    }
+}
+
+/// 构建上下文中未被 `.dockerignore` 排除的文件, 返回按路径排序的相对路径
+pub fn context_files(context: &Path) -> Result<Vec<PathBuf>> {
+    let ignore = DockerIgnore::load(context)?;
+    let mut files = vec![];
+
+    for entry in WalkDir::new(context).follow_links(false) {
+        let entry = entry?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+
+        let relative = entry.path().strip_prefix(context)?.to_path_buf();
+        let key = relative.to_string_lossy().replace('\\', "/");
+        if !ignore.is_ignored(&key) {
+            files.push(relative);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
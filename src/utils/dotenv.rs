@@ -0,0 +1,208 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Dotenv (.env) file parser
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// 解析警告, 行号从 1 开始
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotenvWarning {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for DotenvWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// 解析结果
+#[derive(Debug, Clone, Default)]
+pub struct Dotenv {
+    pub values: HashMap<String, String>,
+    pub warnings: Vec<DotenvWarning>,
+}
+
+/// 解析 .env 文件内容
+///
+/// 支持:
+/// - `#` 注释行及未加引号值后的行内注释 (` # ...`)
+/// - `export KEY=VALUE`
+/// - 单引号: 原样保留, 不处理转义与变量
+/// - 双引号: 支持 `\n` `\r` `\t` `\"` `\\` `\$` 转义, 可跨行
+/// - `${VAR}` 引用前面已定义的变量, 未定义的引用原样保留
+///
+/// 无法解析的行记录警告并跳过。
+pub fn parse(content: &str) -> Dotenv {
+    let mut dotenv = Dotenv::default();
+    let lines: Vec<&str> = content.lines().collect();
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line_number = index + 1;
+        let line = lines[index].trim();
+        index += 1;
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = strip_export(line);
+        let Some((key, raw_value)) = line.split_once('=') else {
+            dotenv.warn(line_number, format!("expected KEY=VALUE, got '{line}'"));
+            continue;
+        };
+
+        let key = key.trim();
+        if !is_valid_key(key) {
+            dotenv.warn(line_number, format!("invalid variable name '{key}'"));
+            continue;
+        }
+
+        let raw_value = raw_value.trim_start();
+        let value = match raw_value.chars().next() {
+            Some(quote @ ('\'' | '"')) => {
+                // 引号值可跨行, 持续拼接后续行直到找到闭合引号
+                let mut buffer = raw_value[1..].to_string();
+                let mut end = find_closing_quote(&buffer, quote);
+                let mut consumed = index;
+                while end.is_none() && consumed < lines.len() {
+                    buffer.push('\n');
+                    buffer.push_str(lines[consumed]);
+                    consumed += 1;
+                    end = find_closing_quote(&buffer, quote);
+                }
+
+                let Some(end) = end else {
+                    dotenv.warn(line_number, format!("unterminated quoted value for '{key}'"));
+                    continue;
+                };
+                index = consumed;
+
+                let trailing = buffer[end + 1..].trim();
+                if !trailing.is_empty() && !trailing.starts_with('#') {
+                    dotenv.warn(
+                        line_number,
+                        format!("unexpected characters after quoted value of '{key}'"),
+                    );
+                }
+
+                let inner = &buffer[..end];
+                if quote == '\'' {
+                    inner.to_string()
+                } else {
+                    expand(inner, &dotenv.values, true)
+                }
+            }
+            _ => expand(strip_inline_comment(raw_value).trim_end(), &dotenv.values, false),
+        };
+
+        dotenv.values.insert(key.to_string(), value);
+    }
+
+    dotenv
+}
+
+impl Dotenv {
+    fn warn(&mut self, line: usize, message: String) {
+        self.warnings.push(DotenvWarning { line, message });
+    }
+}
+
+fn strip_export(line: &str) -> &str {
+    match line.strip_prefix("export") {
+        Some(rest) if rest.starts_with(char::is_whitespace) => rest.trim_start(),
+        _ => line,
+    }
+}
+
+/// 变量名: 字母或下划线开头, 由字母、数字、下划线、`.`、`-` 组成
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// 查找闭合引号位置, 双引号跳过转义字符
+fn find_closing_quote(value: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' && quote == '"' {
+            escaped = true;
+        } else if c == quote {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// 去除未加引号值中的行内注释 (`#` 前需有空白)
+fn strip_inline_comment(value: &str) -> &str {
+    if value.starts_with('#') {
+        return "";
+    }
+    let mut previous_whitespace = false;
+    for (i, c) in value.char_indices() {
+        if c == '#' && previous_whitespace {
+            return &value[..i];
+        }
+        previous_whitespace = c.is_whitespace();
+    }
+    value
+}
+
+/// 处理转义并展开 `${VAR}` 引用
+fn expand(value: &str, defined: &HashMap<String, String>, escapes: bool) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if escapes => match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('r') => result.push('\r'),
+                Some('t') => result.push('\t'),
+                Some(escaped @ ('"' | '\\' | '$')) => result.push(escaped),
+                Some(other) => {
+                    result.push('\\');
+                    result.push(other);
+                }
+                None => result.push('\\'),
+            },
+            '$' if chars.peek() == Some(&'{') => {
+                chars.next();
+                let mut name = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        closed = true;
+                        break;
+                    }
+                    name.push(c);
+                }
+
+                match defined.get(&name) {
+                    Some(defined_value) if closed => result.push_str(defined_value),
+                    _ => {
+                        // 未定义的引用原样保留, 交由后续变量替换处理
+                        result.push_str("${");
+                        result.push_str(&name);
+                        if closed {
+                            result.push('}');
+                        }
+                    }
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
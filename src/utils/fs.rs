@@ -4,6 +4,7 @@
 
 use anyhow::Result;
 use std::fs;
+use std::path::Path;
 
 fn get_files_in_current_dir(dir: &str) -> Result<Vec<String>, std::io::Error> {
     let mut files = Vec::new();
@@ -20,16 +21,21 @@ fn get_files_in_current_dir(dir: &str) -> Result<Vec<String>, std::io::Error> {
     Ok(files)
 }
 
-pub fn get_yaml_files_in_dir(dir: &str) -> Result<Vec<String>> {
+/// 获取目录下指定扩展名 (不含 `.`) 的文件
+pub fn get_files_with_extensions_in_dir(dir: &str, extensions: &[&str]) -> Result<Vec<String>> {
     let files = get_files_in_current_dir(dir)?;
 
-    let yaml_files = files
-        .iter()
-        .filter(|file| file.ends_with(".yml") || file.ends_with(".yaml"))
-        .cloned()
+    let matched = files
+        .into_iter()
+        .filter(|file| {
+            Path::new(file)
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| extensions.contains(&extension))
+        })
         .collect();
 
-    Ok(yaml_files)
+    Ok(matched)
 }
 
 // 通用权限设置函数
@@ -55,3 +61,34 @@ pub fn make_dir_with_permissions(dir: &str, permissions: u32) -> Result<()> {
     set_dir_permissions(dir, permissions)?;
     Ok(())
 }
+
+/// 以仅所有者可读写(0600)的权限原子写入文件
+///
+/// 先在目标目录中写入临时文件再重命名, 避免中断时留下不完整的文件。
+pub fn write_private_file(path: &Path, content: &[u8]) -> Result<()> {
+    let parent = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(parent)?;
+
+    let file_name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let temp_path = parent.join(format!(".{file_name}.tmp"));
+
+    fs::write(&temp_path, content)?;
+    set_file_permissions(&temp_path, 0o600)?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn set_file_permissions(path: &Path, permissions: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(permissions))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_file_permissions(path: &Path, permissions: u32) -> Result<()> {
+    // 非 Unix 平台跳过权限设置
+    let _ = path;
+    let _ = permissions;
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! Author: xiaoYown
+//! Created: 2026-10-18
+//! Description: Sensitive value masking utility
+
+/// 脱敏后的占位符
+pub const MASKED_VALUE: &str = "******";
+
+/// 视为敏感变量的名称片段
+const SENSITIVE_KEY_PATTERNS: [&str; 7] =
+    ["TOKEN", "SECRET", "PASSWORD", "PASSWD", "API_KEY", "PRIVATE_KEY", "CREDENTIAL"];
+
+/// 根据变量名判断是否为敏感变量
+pub fn is_sensitive_key(key: &str) -> bool {
+    let upper = key.to_uppercase();
+    SENSITIVE_KEY_PATTERNS.iter().any(|pattern| upper.contains(pattern))
+}
+
+/// 脱敏变量值, 空值保持原样
+pub fn mask_value(value: &str) -> String {
+    if value.is_empty() {
+        return String::new();
+    }
+    MASKED_VALUE.to_string()
+}
+
+/// 多行密钥中单独脱敏的行的最小长度
+const MIN_FRAGMENT_LEN: usize = 8;
+
+/// 展开密钥值为需要脱敏的片段
+///
+/// 命令输出按行记录, 多行密钥(如私钥)的每一行也需要单独脱敏。
+/// 过短的行、纯标点的行(如 JSON 的 `{` `}`)与 PEM 边界行不含秘密, 脱敏它们会误伤其他日志。
+pub fn secret_fragments(value: &str) -> Vec<String> {
+    let mut fragments = vec![];
+    if value.is_empty() {
+        return fragments;
+    }
+    fragments.push(value.to_string());
+    if value.contains('\n') {
+        for line in value.lines().map(str::trim) {
+            if is_secret_line(line) {
+                fragments.push(line.to_string());
+            }
+        }
+    }
+    fragments
+}
+
+fn is_secret_line(line: &str) -> bool {
+    let is_pem_marker = line.ends_with("-----")
+        && (line.starts_with("-----BEGIN ") || line.starts_with("-----END "));
+
+    line.chars().count() >= MIN_FRAGMENT_LEN
+        && !line.chars().all(|c| c.is_ascii_punctuation() || c.is_whitespace())
+        && !is_pem_marker
+}
+
+/// 将消息中出现的所有密钥值替换为占位符
+///
+/// `secrets` 需按长度降序排列, 避免较短的密钥先替换破坏较长密钥的匹配。
+pub fn redact(message: &str, secrets: &[String]) -> String {
+    let mut result = message.to_string();
+    for secret in secrets {
+        if !secret.is_empty() && result.contains(secret.as_str()) {
+            result = result.replace(secret.as_str(), MASKED_VALUE);
+        }
+    }
+    result
+}
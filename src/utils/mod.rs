@@ -3,4 +3,7 @@
 //! Description: Utils module
 
 pub mod command;
+pub mod dockerignore;
+pub mod dotenv;
 pub mod fs;
+pub mod mask;
@@ -0,0 +1,136 @@
+//! 模拟引擎 API (unix socket) 与镜像仓库 (tcp) 的 HTTP 服务, 以及引擎命令行桩程序
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, UnixListener};
+
+/// 收到的请求
+#[derive(Debug, Clone)]
+pub struct Received {
+    pub method: String,
+    pub path: String,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+    /// 请求头, 名称为小写
+    pub headers: HashMap<String, String>,
+}
+
+pub type Handler = dyn Fn(&Received) -> (u16, Vec<u8>) + Send + Sync;
+
+/// 在 unix socket 上模拟 Docker Engine API, 每个连接处理一个请求
+pub fn fake_engine(dir: &Path, handler: Arc<Handler>) -> (PathBuf, Arc<Mutex<Vec<Received>>>) {
+    let socket = dir.join("docker.sock");
+    let listener = UnixListener::bind(&socket).unwrap();
+    let received = Arc::new(Mutex::new(vec![]));
+
+    let log = received.clone();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let handler = handler.clone();
+            let log = log.clone();
+            tokio::spawn(serve(stream, log, move |request| {
+                let (status, body) = handler(request);
+                (status, vec![], body)
+            }));
+        }
+    });
+
+    (socket, received)
+}
+
+pub type RegistryHandler = dyn Fn(&Received) -> (u16, Vec<(String, String)>, Vec<u8>) + Send + Sync;
+
+/// 在本机 tcp 端口上模拟镜像仓库 (registry:2), 返回 `127.0.0.1:port`
+pub async fn fake_registry(handler: Arc<RegistryHandler>) -> (String, Arc<Mutex<Vec<Received>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let received = Arc::new(Mutex::new(vec![]));
+
+    let log = received.clone();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let handler = handler.clone();
+            tokio::spawn(serve(stream, log.clone(), move |request| handler(request)));
+        }
+    });
+
+    (address, received)
+}
+
+/// 读取一个请求并写回响应后关闭连接
+async fn serve<S>(
+    mut stream: S,
+    log: Arc<Mutex<Vec<Received>>>,
+    handler: impl Fn(&Received) -> (u16, Vec<(String, String)>, Vec<u8>),
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut data = vec![];
+    let mut buffer = [0u8; 4096];
+    let head_end = loop {
+        let n = stream.read(&mut buffer).await.unwrap();
+        data.extend_from_slice(&buffer[..n]);
+        if let Some(index) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break index + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&data[..head_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap().split(' ');
+    let method = request_line.next().unwrap().to_string();
+    let path = request_line.next().unwrap().to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let length: usize = headers.get("content-length").and_then(|v| v.parse().ok()).unwrap_or(0);
+    while data.len() < head_end + length {
+        let n = stream.read(&mut buffer).await.unwrap();
+        data.extend_from_slice(&buffer[..n]);
+    }
+
+    let request = Received {
+        method,
+        path,
+        content_type: headers.get("content-type").cloned(),
+        body: data[head_end..head_end + length].to_vec(),
+        headers,
+    };
+    let (status, extra_headers, body) = handler(&request);
+    log.lock().unwrap().push(request);
+
+    let mut head = format!(
+        "HTTP/1.1 {status} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        body.len()
+    );
+    for (name, value) in extra_headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes()).await.unwrap();
+    stream.write_all(&body).await.unwrap();
+    stream.shutdown().await.unwrap();
+}
+
+/// exec 输出的多路复用帧
+pub fn frame(stream: u8, payload: &str) -> Vec<u8> {
+    let mut data = vec![stream, 0, 0, 0];
+    data.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    data.extend_from_slice(payload.as_bytes());
+    data
+}
+
+/// 写入可执行的 shell 脚本作为引擎命令行 (docker / podman / nerdctl) 桩程序, 参数为 `$@`
+pub fn fake_cli(dir: &Path, name: &str, script: &str) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let program = dir.join(name);
+    std::fs::write(&program, format!("#!/bin/sh\n{script}\n")).unwrap();
+    std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+    program
+}
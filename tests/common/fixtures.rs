@@ -0,0 +1,11 @@
+//! 测试用配置构造
+
+use confkit_engine::types::config::ConfKitImageConfig;
+
+/// 以 node:20 为基础镜像的镜像配置, extra 为追加的 YAML 字段
+pub fn image_config(name: &str, extra: &str) -> ConfKitImageConfig {
+    serde_yaml::from_str(&format!(
+        "name: {name}\nbase_image: node\ntag: '20'\ncontext: .\nengine_file: Dockerfile\n{extra}"
+    ))
+    .unwrap()
+}
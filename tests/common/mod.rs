@@ -1 +1,7 @@
-// Common test utilities - currently unused but kept for potential future use
+// Common test utilities
+
+#[allow(dead_code)]
+pub mod fake_engine;
+
+#[allow(dead_code)]
+pub mod fixtures;
@@ -1,7 +1,11 @@
-use confkit_engine::core::condition::evaluator::ConditionEvaluator;
-use confkit_engine::core::condition::parser::parse_condition;
+use confkit_engine::core::condition::evaluator::{ConditionEvaluator, EvaluationError};
+use confkit_engine::core::condition::lint::lint_condition;
+use confkit_engine::core::condition::parser::{parse_condition, ParseErrorKind};
+use confkit_engine::core::executor::step_executor::StepExecutor;
+use confkit_engine::core::executor::types::{StepResult, StepStatus};
 use confkit_engine::types::condition::{
-    ComparisonOperator, ConditionExpression, ConditionValue, LogicalOperator,
+    ComparisonOperator, ConditionExpression, ConditionFunction, ConditionValue, LogicalOperator,
+    MessageLang,
 };
 use std::collections::HashMap;
 
@@ -226,3 +230,240 @@ test_attr! {
         }
     }
 }
+
+test_attr! {
+    #[pretty_test("函数调用与列表解析")]
+    fn test_function_and_list_parsing() {
+        match parse_condition("startsWith(${BRANCH}, \"release/\")").unwrap() {
+            ConditionExpression::Function { function, args } => {
+                assert_eq!(function, ConditionFunction::StartsWith);
+                assert_eq!(args.len(), 2);
+            }
+            other => panic!("Expected Function expression, got {:?}", other),
+        }
+
+        match parse_condition("${ENV} not in [\"prod\", 'staging']").unwrap() {
+            ConditionExpression::Comparison { operator, right, .. } => {
+                assert_eq!(operator, ComparisonOperator::NotIn);
+                assert!(matches!(*right, ConditionExpression::List { ref items } if items.len() == 2));
+            }
+            other => panic!("Expected Comparison expression, got {:?}", other),
+        }
+
+        // 未知函数和参数个数不符均为语法错误
+        assert!(parse_condition("unknown(${ENV})").is_err());
+        assert!(parse_condition("len(${A}, ${B})").is_err());
+        // 关键字需要完整匹配
+        assert!(parse_condition("${ENV} index [1]").is_err());
+    }
+}
+
+test_attr! {
+    #[pretty_test("函数调用与列表求值")]
+    fn test_function_and_list_evaluation() {
+        let mut env_vars = HashMap::new();
+        env_vars.insert("BRANCH".to_string(), "release/1.2".to_string());
+        env_vars.insert("ENV".to_string(), "Staging".to_string());
+        env_vars.insert("BUILD_NUMBER".to_string(), "42".to_string());
+
+        let evaluator = ConditionEvaluator::new(env_vars);
+
+        let test_cases = vec![
+            ("${BRANCH} matches \"^release/\"", true),
+            ("${BRANCH} matches \"^hotfix/\"", false),
+            ("matches(${BRANCH}, \"[0-9]+\\.[0-9]+$\")", true),
+            ("contains(${BRANCH}, \"/1.\")", true),
+            ("startsWith(${BRANCH}, \"release\")", true),
+            ("endsWith(${BRANCH}, \".3\")", false),
+            ("lower(${ENV}) == \"staging\"", true),
+            ("lower(${ENV}) in [\"prod\", \"staging\"]", true),
+            ("${ENV} in [\"prod\", \"staging\"]", false),
+            ("${ENV} not in [\"prod\", \"staging\"]", true),
+            ("${BUILD_NUMBER} in [41, 42, 43]", true),
+            ("${UNDEFINED_VAR} in [\"a\"]", false),
+            ("contains([\"a\", \"b\"], \"b\")", true),
+            ("len(${BRANCH}) == 11", true),
+            ("len([1, 2, 3]) > 2 && len(${UNDEFINED_VAR}) == 0", true),
+            ("!contains(${BRANCH}, \"main\")", true),
+            ("${UNDEFINED_VAR} matches \".*\"", false),
+        ];
+
+        for (input, expected) in test_cases {
+            let result = evaluator.evaluate_string(input).unwrap();
+            println!("eval: {} -> {}", input, result);
+            assert_eq!(result, expected, "Failed for expression: {}", input);
+        }
+
+        // 无效的正则表达式在求值时报错
+        assert!(evaluator.evaluate_string("${BRANCH} matches \"(\"").is_err());
+    }
+}
+
+test_attr! {
+    #[pretty_test("语义化版本比较")]
+    fn test_semver_comparison() {
+        let mut env_vars = HashMap::new();
+        env_vars.insert("PROJECT_VERSION".to_string(), "1.10.0".to_string());
+        env_vars.insert("RC_VERSION".to_string(), "2.0.0-rc.1".to_string());
+        env_vars.insert("TAG".to_string(), "v1.2".to_string());
+
+        let evaluator = ConditionEvaluator::new(env_vars);
+
+        let test_cases = vec![
+            // 两侧都是完整版本号时自动按版本比较, 而不是字符串比较
+            ("${PROJECT_VERSION} >= \"1.9.0\"", true),
+            ("${PROJECT_VERSION} > \"1.2.0\"", true),
+            ("${PROJECT_VERSION} < \"1.10.1\"", true),
+            ("${PROJECT_VERSION} == \"1.10.0+build.5\"", true),
+            // 预发布版本低于正式版本
+            ("${RC_VERSION} < \"2.0.0\"", true),
+            ("${RC_VERSION} > \"2.0.0-beta.2\"", true),
+            ("${RC_VERSION} > \"2.0.0-rc.1\"", false),
+            // semver() 允许 v 前缀并补全缺省部分
+            ("semver(${TAG}) == \"1.2.0\"", true),
+            ("semver(${TAG}) < semver(\"1.10\")", true),
+            ("semver(${PROJECT_VERSION}) >= 1", true),
+            ("semver(${UNDEFINED_VAR}) == null", true),
+            // 非版本号仍按字符串比较
+            ("\"abc\" < \"abd\"", true),
+        ];
+
+        for (input, expected) in test_cases {
+            let result = evaluator.evaluate_string(input).unwrap();
+            println!("eval: {} -> {}", input, result);
+            assert_eq!(result, expected, "Failed for expression: {}", input);
+        }
+
+        assert!(evaluator.evaluate_string("semver(\"not-a-version\") > \"1.0.0\"").is_err());
+    }
+}
+
+fn step_result(name: &str, status: StepStatus, exit_code: Option<i32>) -> StepResult {
+    StepResult {
+        name: name.to_string(),
+        status,
+        started_at: chrono::Utc::now(),
+        finished_at: None,
+        duration_ms: Some(120),
+        exit_code,
+        output: String::new(),
+        error: None,
+    }
+}
+
+test_attr! {
+    #[pretty_test("引用已执行步骤的状态")]
+    fn test_step_status_conditions() {
+        let previous = vec![
+            step_result("install", StepStatus::Success, Some(0)),
+            step_result("Run tests", StepStatus::Failed, Some(2)),
+            step_result("lint", StepStatus::Skipped, Some(0)),
+        ];
+
+        let mut env_vars = HashMap::new();
+        env_vars.insert("ENVIRONMENT".to_string(), "ci".to_string());
+        let evaluator = ConditionEvaluator::new(env_vars)
+            .with_values(StepExecutor::condition_values(&previous));
+
+        let test_cases = vec![
+            ("${steps.install.status} == \"success\"", true),
+            ("${steps.Run_tests.status} == \"failed\"", true),
+            ("${steps.Run_tests.exit_code} == 2", true),
+            ("${steps.lint.status} in [\"skipped\", \"failed\"]", true),
+            ("${steps.install.duration_ms} > 100", true),
+            ("${task.failed} && ${ENVIRONMENT} == \"ci\"", true),
+            ("${task.failed_steps} == 1", true),
+            // 未执行的步骤为空值
+            ("${steps.deploy.status} == null", true),
+            ("${steps.deploy.status} == \"success\"", false),
+        ];
+
+        for (input, expected) in test_cases {
+            let result = evaluator.evaluate_string(input).unwrap();
+            println!("eval: {} -> {}", input, result);
+            assert_eq!(result, expected, "Failed for expression: {}", input);
+        }
+
+        // 尚无步骤执行时 task.failed 为 false
+        let evaluator = ConditionEvaluator::new(HashMap::new())
+            .with_values(StepExecutor::condition_values(&[]));
+        assert!(evaluator.evaluate_string("!${task.failed}").unwrap());
+    }
+}
+
+test_attr! {
+    #[pretty_test("解析错误的位置与描述")]
+    fn test_parse_error_positions() {
+        let cases = vec![
+            ("${X} == = 1", 9, ParseErrorKind::UnexpectedToken("=".to_string())),
+            ("${X} == prod", 9, ParseErrorKind::UnexpectedToken("prod".to_string())),
+            ("${A} && ", 9, ParseErrorKind::UnexpectedEnd),
+            ("(${A} && ${B}", 14, ParseErrorKind::Expected(')')),
+            ("${VAR ===== invalid", 7, ParseErrorKind::Expected('}')),
+            ("${A} == 'abc", 9, ParseErrorKind::UnterminatedString),
+            ("${A} && foo(${B})", 9, ParseErrorKind::UnknownFunction("foo".to_string())),
+            ("  ${A} == 'x' )", 15, ParseErrorKind::UnexpectedToken(")".to_string())),
+            ("   ", 1, ParseErrorKind::Empty),
+        ];
+
+        for (input, column, kind) in cases {
+            let error = parse_condition(input).unwrap_err();
+            println!("{}", error.render(MessageLang::En));
+            assert_eq!(error.column, column, "column for '{}'", input);
+            assert_eq!(error.kind, kind, "kind for '{}'", input);
+        }
+
+        let error = parse_condition("len(${A}, 1)").unwrap_err();
+        assert_eq!(error.message(MessageLang::En), "len() takes 1 argument(s), 2 given at column 1");
+        assert_eq!(error.message(MessageLang::Zh), "第 1 列: len() 需要 1 个参数, 实际为 2 个");
+
+        let error = parse_condition("${X} == = 1").unwrap_err();
+        assert_eq!(
+            error.render(MessageLang::En),
+            "unexpected '=' at column 9\n  ${X} == = 1\n          ^"
+        );
+        assert_eq!(error.message(MessageLang::Zh), "第 9 列: 意外的内容 '='");
+
+        // try_evaluate_string 不降级, 解析错误返回 Err
+        let evaluator = ConditionEvaluator::new(HashMap::new());
+        assert!(evaluator.try_evaluate_string("${X} ==").is_err());
+        assert!(evaluator.evaluate_string("${X} ==").unwrap());
+
+        // 绕过解析器构造的表达式在求值时校验参数个数
+        let expr = ConditionExpression::Function {
+            function: ConditionFunction::Contains,
+            args: vec![ConditionExpression::Literal { value: ConditionValue::String("a".to_string()) }],
+        };
+        let error = evaluator.evaluate(&expr).unwrap_err();
+        let error = error.downcast_ref::<EvaluationError>().unwrap();
+        assert_eq!(error.message(MessageLang::En), "contains() takes 2 argument(s), 1 given");
+        assert_eq!(error.message(MessageLang::Zh), "contains() 需要 2 个参数, 实际为 1 个");
+
+        let expr = ConditionExpression::Logical {
+            operator: LogicalOperator::And,
+            left: Some(Box::new(ConditionExpression::Literal { value: ConditionValue::Boolean(true) })),
+            right: None,
+        };
+        let error = evaluator.evaluate(&expr).unwrap_err();
+        let error = error.downcast_ref::<EvaluationError>().unwrap();
+        assert_eq!(error.message(MessageLang::En), "'&&' requires 2 operand(s)");
+        assert_eq!(error.message(MessageLang::Zh), "'&&' 需要 2 个操作数");
+    }
+}
+
+test_attr! {
+    #[pretty_test("条件表达式静态检查")]
+    fn test_condition_lint() {
+        let lint = |input: &str| lint_condition(&parse_condition(input).unwrap());
+
+        assert!(lint("${BRANCH} matches '^release/' && semver(${V}) > '1.0'").is_empty());
+        // 变量参数无法静态检查
+        assert!(lint("${BRANCH} matches ${PATTERN}").is_empty());
+
+        let issues = lint("${BRANCH} matches '(' || matches(${A}, 'a[') || semver('abc') > '1.0.0'");
+        assert_eq!(issues.len(), 3, "{issues:?}");
+        assert!(issues[0].starts_with("invalid regular expression '('"));
+        assert!(issues[1].starts_with("invalid regular expression 'a['"));
+        assert_eq!(issues[2], "invalid version 'abc' in semver()");
+    }
+}
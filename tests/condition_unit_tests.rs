@@ -171,7 +171,7 @@ fn test_condition_expression_construction() {
     // println!("构造比較表达式: VAR1 == \"test\"");
 
     match comp_expr {
-        ConditionExpression::Comparison { operator, left, right } => {
+        ConditionExpression::Comparison { operator, .. } => {
             // println!("✓ 运算符: {:?}", operator);
             // println!("✓ 左操作数: {:?}", left);
             // println!("✓ 右操作数: {:?}", right);
@@ -197,7 +197,7 @@ fn test_comparison_operators() {
     ];
 
     // println!("支持的比较运算符:");
-    for (symbol, operator) in &operators {
+    for (_symbol, _operator) in &operators {
         // println!("  {} -> {:?}", symbol, operator);
     }
 
@@ -217,7 +217,7 @@ fn test_logical_operators() {
     ];
 
     // println!("支持的逻辑运算符:");
-    for (symbol, operator) in &operators {
+    for (_symbol, _operator) in &operators {
         // println!("  {} -> {:?}", symbol, operator);
     }
 
@@ -265,7 +265,7 @@ fn test_complex_condition_expression() {
 
     // 验证表达式结构正确构建
     match complex_expr {
-        ConditionExpression::Logical { operator, left, right } => {
+        ConditionExpression::Logical { operator, .. } => {
             // println!("✓ 顶层运算符: {:?}", operator);
             // println!("✓ 左操作数: {:?}", left.is_some());
             // println!("✓ 右操作数: {:?}", right.is_some());
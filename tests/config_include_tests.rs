@@ -0,0 +1,87 @@
+use confkit_engine::core::validator::{ConfigValidator, Severity};
+use confkit_engine::infra::config::ConfKitConfigLoader;
+
+const ROOT: &str = "version: 1.0.0\nengine: docker\nengine_compose:\n  file: compose.yml\ninclude:\n  - images/*.yml\n  - spaces.yml\nspaces:\n  - name: hello\n    description: hello\n    path: spaces/hello\n";
+
+fn image(name: &str, tag: &str) -> String {
+    format!(
+        "  - name: {name}\n    base_image: alpine\n    tag: \"{tag}\"\n    context: .\n    engine_file: Dockerfile\n"
+    )
+}
+
+#[test]
+fn test_include_merges_spaces_and_images() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("images")).unwrap();
+    std::fs::write(root.join("confkit.yml"), ROOT).unwrap();
+    std::fs::write(root.join("images/a.yml"), format!("images:\n{}", image("node", "18"))).unwrap();
+    std::fs::write(root.join("images/b.yml"), format!("images:\n{}", image("node", "20"))).unwrap();
+    std::fs::write(
+        root.join("spaces.yml"),
+        "spaces:\n  - name: team\n    description: team\n    path: spaces/team\n",
+    )
+    .unwrap();
+
+    let path = root.join("confkit.yml").to_string_lossy().to_string();
+    let (loaded, diagnostics) = ConfigValidator::load_config(&path);
+    assert!(diagnostics.is_empty(), "unexpected diagnostics: {diagnostics:?}");
+
+    let loaded = loaded.unwrap();
+    let spaces: Vec<&str> = loaded.config.spaces.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(spaces, vec!["hello", "team"]);
+    let images: Vec<&str> = loaded.config.images.iter().map(|i| i.tag.as_str()).collect();
+    assert_eq!(images, vec!["18", "20"]);
+
+    let (file, position) = loaded.locate_image(1, "name");
+    assert!(file.ends_with("images/b.yml"));
+    assert_eq!(position, Some((2, 5)));
+}
+
+#[test]
+fn test_include_conflicts_point_to_originating_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("images")).unwrap();
+    std::fs::write(root.join("confkit.yml"), ROOT).unwrap();
+    std::fs::write(root.join("images/a.yml"), format!("images:\n{}", image("node", "18"))).unwrap();
+    std::fs::write(root.join("images/b.yml"), format!("images:\n{}", image("node", "18"))).unwrap();
+    std::fs::write(
+        root.join("spaces.yml"),
+        "spaces:\n  - name: hello\n    description: dup\n    path: spaces/hello\n",
+    )
+    .unwrap();
+
+    let path = root.join("confkit.yml").to_string_lossy().to_string();
+    let (_, diagnostics) = ConfigValidator::load_config(&path);
+    let errors: Vec<String> = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .map(|d| d.to_string())
+        .collect();
+    assert_eq!(errors.len(), 2, "{errors:?}");
+    assert!(errors[0].contains("images/b.yml:2:5: error: duplicate image 'node:18'"));
+    assert!(errors[0].contains("images/a.yml:2"));
+    assert!(errors[1].contains("spaces.yml:2:5: error: duplicate space name 'hello'"));
+    assert!(errors[1].contains("confkit.yml:9"));
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let err = runtime.block_on(ConfKitConfigLoader::from_file(&path)).unwrap_err();
+    assert!(err.to_string().contains("duplicate image 'node:18'"));
+}
+
+#[test]
+fn test_include_missing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(root.join("confkit.yml"), ROOT).unwrap();
+
+    let path = root.join("confkit.yml").to_string_lossy().to_string();
+    let (_, diagnostics) = ConfigValidator::load_config(&path);
+    let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+
+    // glob 无匹配为警告, 指定文件不存在为错误
+    assert_eq!(messages.len(), 2, "{messages:?}");
+    assert!(messages[0].contains(":6:3: warning: include pattern 'images/*.yml' matched no files"));
+    assert!(messages[1].contains(":7:3: error: included file does not exist: spaces.yml"));
+}
@@ -0,0 +1,104 @@
+use confkit_engine::infra::schema::{ConfigSchema, ConfigSchemaKind};
+use confkit_engine::types::config::{ConfKitEnvironmentFileFormat, ConfKitProjectConfig};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+const PROJECT_YAML: &str = r#"
+name: app
+description: app
+source:
+  git_repo: https://example.com/app.git
+  git_branch: main
+environment_files:
+  - format: env
+    path: ./app.env
+environment:
+  APP_NAME: app
+environment_from_args:
+  - name: ENVIRONMENT
+    type: radio
+    prompt: env
+    options: [dev, prod]
+secrets:
+  - name: TOKEN
+    from: env
+cleaner:
+  workspace: true
+print_environment: true
+steps:
+  - name: build
+    container: builder
+    working_dir: /workspace
+    condition: "${ENVIRONMENT} == 'prod'"
+    timeout: 60
+    continue_on_error: true
+    commands: [echo hi]
+"#;
+
+fn schema_json(kind: ConfigSchemaKind) -> Value {
+    serde_json::from_str(&ConfigSchema::to_json(kind).unwrap()).unwrap()
+}
+
+fn keys(value: &Value) -> BTreeSet<String> {
+    value.as_object().unwrap().keys().cloned().collect()
+}
+
+#[test]
+fn test_committed_schemas_are_up_to_date() {
+    for (kind, file, flag) in [
+        (ConfigSchemaKind::Root, "schemas/confkit.schema.json", "--root"),
+        (ConfigSchemaKind::Project, "schemas/project.schema.json", "--project"),
+    ] {
+        let committed = std::fs::read_to_string(file).unwrap();
+        let generated = format!("{}\n", ConfigSchema::to_json(kind).unwrap());
+        assert!(
+            committed == generated,
+            "{file} is out of date, regenerate with `confkit config schema {flag} > {file}`"
+        );
+    }
+}
+
+#[test]
+fn test_project_schema_matches_serde_fields() {
+    let project: ConfKitProjectConfig = serde_yaml::from_str(PROJECT_YAML).unwrap();
+    let serialized = serde_json::to_value(&project).unwrap();
+    let schema = schema_json(ConfigSchemaKind::Project);
+
+    assert_eq!(keys(&serialized), keys(&schema["properties"]));
+    assert_eq!(
+        keys(&serialized["steps"][0]),
+        keys(&schema["definitions"]["ConfKitStepConfig"]["properties"])
+    );
+    assert_eq!(
+        keys(&serialized["secrets"][0]),
+        keys(&schema["definitions"]["ConfKitSecretConfig"]["properties"])
+    );
+}
+
+#[test]
+fn test_schema_enum_values_match_serde() {
+    let schema = schema_json(ConfigSchemaKind::Project);
+    let formats: BTreeSet<String> = schema["definitions"]["ConfKitEnvironmentFileFormat"]["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|variant| variant["enum"][0].as_str().unwrap().to_string())
+        .collect();
+
+    let serde_formats: BTreeSet<String> = [
+        ConfKitEnvironmentFileFormat::Yaml,
+        ConfKitEnvironmentFileFormat::Env,
+        ConfKitEnvironmentFileFormat::Json,
+        ConfKitEnvironmentFileFormat::Toml,
+    ]
+    .iter()
+    .map(|format| serde_json::to_value(format).unwrap().as_str().unwrap().to_string())
+    .collect();
+    assert_eq!(formats, serde_formats);
+
+    let root = schema_json(ConfigSchemaKind::Root);
+    assert_eq!(
+        root["definitions"]["Engine"]["enum"],
+        serde_json::json!(["docker", "podman", "nerdctl", "none"])
+    );
+}
@@ -0,0 +1,349 @@
+use confkit_engine::core::validator::{ConfigValidator, Severity, YamlLocator};
+use confkit_engine::types::config::ConfKitProjectConfig;
+
+fn path(segments: &[&str]) -> Vec<String> {
+    segments.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_yaml_locator_positions() {
+    let content = r#"name: app
+steps:
+  - name: build
+    commands:
+      - |
+        echo "a: b"
+      - echo done
+  - name: test
+    container: builder
+"#;
+    let locator = YamlLocator::new(content);
+
+    assert_eq!(locator.locate(&path(&["name"])), Some((1, 1)));
+    assert_eq!(locator.locate(&path(&["steps", "0", "commands", "1"])), Some((7, 7)));
+    assert_eq!(locator.locate(&path(&["steps", "1", "container"])), Some((9, 5)));
+    // 未记录的路径回退到父路径
+    assert_eq!(locator.locate(&path(&["steps", "1", "timeout"])), Some((8, 3)));
+    assert_eq!(locator.locate(&path(&["missing"])), None);
+}
+
+#[test]
+fn test_parse_yaml_reports_unknown_fields_and_errors() {
+    let content = "name: app\ndescription: app\nsteps:\n  - name: build\n    contianer: builder\n    commands: []\n";
+    let (project, diagnostics) =
+        ConfigValidator::parse_config::<ConfKitProjectConfig>("app.yml", content);
+    assert!(project.is_some());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "app.yml:5:5: warning: unknown field `steps.0.contianer`"
+    );
+
+    let (project, diagnostics) =
+        ConfigValidator::parse_config::<ConfKitProjectConfig>("bad.yml", "name: app\nsteps: 3\n");
+    assert!(project.is_none());
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert!(diagnostics[0].line.is_some());
+}
+
+#[test]
+fn test_validate_file_cross_references() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let space_dir = root.join("spaces");
+    std::fs::create_dir_all(&space_dir).unwrap();
+    std::fs::write(root.join("Dockerfile"), "FROM alpine\n").unwrap();
+
+    let compose = root.join("compose.yml");
+    std::fs::write(
+        &compose,
+        "services:\n  builder:\n    image: builder:1.0\n    container_name: builder\n",
+    )
+    .unwrap();
+
+    let config = root.join("confkit.yml");
+    std::fs::write(
+        &config,
+        format!(
+            "version: 1.0.0\nengine: docker\nengine_compose:\n  file: {}\nspaces:\n  - name: hello\n    description: hello\n    path: {}\nimages:\n  - name: builder\n    base_image: alpine\n    tag: \"1.0\"\n    context: {}\n    engine_file: {}\n",
+            compose.display(),
+            space_dir.display(),
+            root.display(),
+            root.join("Dockerfile").display()
+        ),
+    )
+    .unwrap();
+
+    std::fs::write(
+        space_dir.join("a.yml"),
+        "name: app\ndescription: a\nsteps:\n  - name: build\n    container: builder\n    commands:\n      - echo ${PROJECT_NAME}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        space_dir.join("b.yml"),
+        "name: app\ndescription: b\nsteps:\n  - name: build\n    container: ghost\n    condition: \"${X} ==\"\n    commands:\n      - echo ${UNKNOWN}\n",
+    )
+    .unwrap();
+
+    let report = ConfigValidator::validate_file(&config.to_string_lossy());
+    let messages: Vec<String> = report.diagnostics.iter().map(|d| d.to_string()).collect();
+
+    assert_eq!(report.error_count(), 3, "{messages:#?}");
+    let b = space_dir.join("b.yml").display().to_string();
+    assert!(messages
+        .iter()
+        .any(|m| m.starts_with(&format!("{b}:1:1: error: duplicate project name"))));
+    assert!(messages
+        .iter()
+        .any(|m| m
+            .starts_with(&format!("{b}:5:5: error: step 'build' uses unknown container 'ghost'"))));
+    assert!(messages.iter().any(|m| m.starts_with(&format!("{b}:6:5: error: invalid condition"))));
+    // 解析错误附带原表达式和指向出错位置的插入符
+    assert!(messages
+        .iter()
+        .any(|m| m.contains("invalid condition") && m.ends_with("\n  ${X} ==\n         ^")));
+    assert!(messages
+        .iter()
+        .any(|m| m == &format!("{b}:8:7: warning: variable '${{UNKNOWN}}' is not defined")));
+    assert!(!messages.iter().any(|m| m.contains("PROJECT_NAME")));
+}
+
+#[test]
+fn test_parse_config_toml_and_json() {
+    let toml = "name = \"app\"\ndescription = \"app\"\n\n[[steps]]\nname = \"build\"\ncontianer = \"builder\"\ncommands = [\"echo build\"]\n";
+    let (project, diagnostics) =
+        ConfigValidator::parse_config::<ConfKitProjectConfig>("app.toml", toml);
+    assert_eq!(project.unwrap().steps[0].commands, vec!["echo build"]);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].to_string(), "app.toml: warning: unknown field `steps.0.contianer`");
+
+    let json = r#"{
+  "$schema": "./schemas/project.schema.json",
+  "name": "app",
+  "description": "app",
+  "steps": [{ "name": "build", "commands": ["echo build"] }]
+}"#;
+    let (project, diagnostics) =
+        ConfigValidator::parse_config::<ConfKitProjectConfig>("app.json", json);
+    assert_eq!(project.unwrap().name, "app");
+    // `$schema` 不视为未知字段
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+
+    let (project, diagnostics) = ConfigValidator::parse_config::<ConfKitProjectConfig>(
+        "bad.json",
+        "{\n  \"name\": \"app\",\n  \"steps\": 3\n}",
+    );
+    assert!(project.is_none());
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].line, Some(3));
+
+    let (project, diagnostics) = ConfigValidator::parse_config::<ConfKitProjectConfig>(
+        "bad.toml",
+        "name = \"app\"\nsteps = 3\n",
+    );
+    assert!(project.is_none());
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert!(diagnostics[0].line.is_some());
+}
+
+#[test]
+fn test_validate_file_json_root_and_toml_project() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let space_dir = root.join("spaces");
+    std::fs::create_dir_all(&space_dir).unwrap();
+    std::fs::write(root.join("Dockerfile"), "FROM alpine\n").unwrap();
+    std::fs::write(
+        root.join("compose.yml"),
+        "services:\n  builder:\n    image: builder:1.0\n    container_name: builder\n",
+    )
+    .unwrap();
+
+    let config = serde_json::json!({
+        "version": "1.0.0",
+        "engine": "docker",
+        "engine_compose": { "file": root.join("compose.yml") },
+        "spaces": [{ "name": "hello", "description": "hello", "path": space_dir }],
+        "images": [{
+            "name": "builder",
+            "base_image": "alpine",
+            "tag": "1.0",
+            "context": root,
+            "engine_file": root.join("Dockerfile"),
+        }],
+    });
+    let config_path = root.join(".confkit.json");
+    std::fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+
+    std::fs::write(
+        space_dir.join("app.toml"),
+        "name = \"app\"\ndescription = \"a\"\n\n[[steps]]\nname = \"build\"\ncontainer = \"missing\"\ncommands = [\"echo ${PROJECT_NAME}\"]\n",
+    )
+    .unwrap();
+    // 不支持的扩展名不作为项目配置
+    std::fs::write(space_dir.join("notes.txt"), "not a project").unwrap();
+
+    let report = ConfigValidator::validate_file(&config_path.to_string_lossy());
+    let messages: Vec<String> = report.diagnostics.iter().map(|d| d.to_string()).collect();
+    assert_eq!(report.error_count(), 1, "{messages:?}");
+    assert!(messages[0].ends_with(
+        "app.toml: error: step 'build' uses unknown container 'missing' (not a compose service)"
+    ));
+}
+
+#[test]
+fn test_validate_step_references_in_conditions() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let space_dir = root.join("spaces");
+    std::fs::create_dir_all(&space_dir).unwrap();
+
+    let compose = root.join("compose.yml");
+    std::fs::write(&compose, "services: {}\n").unwrap();
+
+    let config = root.join("confkit.yml");
+    std::fs::write(
+        &config,
+        format!(
+            "version: 1.0.0\nengine: docker\nengine_compose:\n  file: {}\nspaces:\n  - name: hello\n    description: hello\n    path: {}\n",
+            compose.display(),
+            space_dir.display()
+        ),
+    )
+    .unwrap();
+    std::fs::write(
+        space_dir.join("app.yml"),
+        "name: app\ndescription: app\nenvironment_from_args:\n  - name: ENV\n    type: input\n    prompt: env\n    condition: \"${ENV} matches '('\"\nsteps:\n  - name: Run tests\n    continue_on_error: true\n    condition: \"${steps.report.status} == 'failed'\"\n    commands: [\"true\"]\n  - name: report\n    condition: \"${steps.Run_tests.status} == 'failed' || ${task.failed} || ${steps.ghost.status} == 'x' || ${steps.Run_tests.code} == 1\"\n    commands: [\"true\"]\n",
+    )
+    .unwrap();
+
+    let report = ConfigValidator::validate_file(&config.to_string_lossy());
+    let messages: Vec<String> = report.diagnostics.iter().map(|d| d.to_string()).collect();
+    let app = space_dir.join("app.yml").display().to_string();
+
+    assert_eq!(report.error_count(), 1, "{messages:#?}");
+    assert_eq!(messages.len(), 4, "{messages:#?}");
+    assert!(messages.iter().any(|m| m.starts_with(&format!(
+        "{app}:7:5: error: invalid condition: invalid regular expression '('"
+    ))));
+    assert!(messages.contains(&format!(
+        "{app}:11:5: warning: '${{steps.report.status}}' refers to a step that has not run yet"
+    )));
+    assert!(messages.contains(&format!(
+        "{app}:14:5: warning: '${{steps.ghost.status}}' refers to an unknown step"
+    )));
+    assert!(messages
+        .contains(&format!("{app}:14:5: warning: unknown step field '${{steps.Run_tests.code}}'")));
+}
+
+#[test]
+fn test_validate_host_only_engine() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let space_dir = root.join("spaces");
+    std::fs::create_dir_all(&space_dir).unwrap();
+
+    // engine: none 时不要求 compose 文件存在
+    let config = root.join("confkit.yml");
+    std::fs::write(
+        &config,
+        format!(
+            "version: 1.0.0\nengine: none\nengine_compose:\n  file: {}\nspaces:\n  - name: hello\n    description: hello\n    path: {}\n",
+            root.join("missing.yml").display(),
+            space_dir.display()
+        ),
+    )
+    .unwrap();
+    std::fs::write(
+        space_dir.join("app.yml"),
+        "name: app\ndescription: app\nsteps:\n  - name: host\n    commands: [\"true\"]\n  - name: build\n    container: builder\n    commands: [\"true\"]\n  - name: test\n    container: builder\n    fallback: host\n    commands: [\"true\"]\n",
+    )
+    .unwrap();
+
+    let report = ConfigValidator::validate_file(&config.to_string_lossy());
+    let messages: Vec<String> = report.diagnostics.iter().map(|d| d.to_string()).collect();
+    let app = space_dir.join("app.yml").display().to_string();
+
+    assert_eq!(report.error_count(), 0, "{messages:#?}");
+    assert_eq!(
+        messages,
+        vec![format!(
+            "{app}:7:5: warning: step 'build' uses container 'builder' but engine is none; it will fail unless `fallback: host` is set"
+        )]
+    );
+}
+
+#[test]
+fn test_validate_image_cycle_and_push() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let space_dir = root.join("spaces");
+    std::fs::create_dir_all(&space_dir).unwrap();
+    std::fs::write(root.join("Dockerfile"), "FROM scratch\n").unwrap();
+
+    let image = |name: &str, base: &str, extra: &str| {
+        format!(
+            "  - name: {name}\n    base_image: {base}\n    tag: '1'\n    context: {}\n    engine_file: {}\n{extra}",
+            root.display(),
+            root.join("Dockerfile").display()
+        )
+    };
+    let config = root.join("confkit.yml");
+    std::fs::write(
+        &config,
+        format!(
+            "version: 1.0.0\nengine: none\nengine_compose: {{}}\nspaces:\n  - name: hello\n    description: hello\n    path: {}\nimages:\n{}{}{}{}{}",
+            space_dir.display(),
+            image("base", "app", ""),
+            image("app", "base", ""),
+            image(
+                "tool",
+                "alpine",
+                "    push:\n      registry: localhost:5000\n      credentials:\n        - {name: password, from: encrypted}\n"
+            ),
+            image(
+                "cli",
+                "alpine",
+                "    push:\n      registry: localhost:5000\n      space: missing\n      credentials:\n        - {name: username, from: encrypted}\n        - {name: password, from: encrypted}\n"
+            ),
+            image(
+                "web",
+                "alpine",
+                "    push:\n      registry: localhost:5000\n      space: hello\n      credentials:\n        - {name: username, from: encrypted}\n        - {name: password, from: encrypted}\n"
+            ),
+        ),
+    )
+    .unwrap();
+
+    let report = ConfigValidator::validate_file(&config.to_string_lossy());
+    let messages: Vec<String> = report
+        .diagnostics
+        .iter()
+        .map(|d| d.to_string())
+        .filter(|message| message.contains(": error: "))
+        .collect();
+    let file = config.display().to_string();
+
+    assert_eq!(
+        messages,
+        vec![
+            format!("{file}:24:5: error: image 'tool:1' push credential 'password': from: encrypted requires push.space"),
+            format!("{file}:24:5: error: image 'tool:1' push credentials require 'username' and 'password'"),
+            format!("{file}:33:5: error: image 'cli:1' push space 'missing' is not defined"),
+            format!("{file}:10:5: error: image dependency cycle: base:1 -> app:1 -> base:1"),
+        ]
+    );
+}
+
+#[test]
+fn test_config_validate_does_not_require_project() {
+    use clap::Parser;
+    use confkit_engine::cli::Cli;
+
+    let requires = |args: &[&str]| Cli::try_parse_from(args).unwrap().requires_project();
+
+    // validate 与 schema 不加载配置也不连接引擎, 配置无效时仍能输出报告
+    assert!(!requires(&["confkit", "config", "validate"]));
+    assert!(!requires(&["confkit", "config", "schema"]));
+    assert!(requires(&["confkit", "config", "show"]));
+}
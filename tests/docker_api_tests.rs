@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+
+mod common;
+
+use common::fake_engine::{fake_engine, frame, Received};
+use confkit_engine::engine::api::client::{
+    ApiClient, ApiEndpoint, LineBuffer, OutputStream, StreamDemuxer,
+};
+use confkit_engine::engine::api::docker::{build_context_archive, format_size, DockerApiEngine};
+use confkit_engine::engine::traits::ContainerEngine;
+use confkit_engine::infra::logger::TaskLogger;
+use confkit_engine::types::config::{ImageBuildOptions, ImageStatus};
+use confkit_engine::utils::dockerignore::{context_files, DockerIgnore};
+
+fn engine(socket: PathBuf) -> DockerApiEngine {
+    DockerApiEngine::new(ApiClient::new(ApiEndpoint::Unix(socket)))
+}
+
+fn tar_entries(archive: &[u8]) -> Vec<String> {
+    let mut archive = tar::Archive::new(archive);
+    let mut names: Vec<String> = archive
+        .entries()
+        .unwrap()
+        .map(|entry| entry.unwrap().path().unwrap().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_api_endpoint_parse() {
+    assert_eq!(
+        ApiEndpoint::parse("unix:///run/user/1000/docker.sock").unwrap(),
+        ApiEndpoint::Unix(PathBuf::from("/run/user/1000/docker.sock"))
+    );
+    assert_eq!(
+        ApiEndpoint::parse("tcp://10.0.0.2:2375").unwrap(),
+        ApiEndpoint::Tcp("10.0.0.2:2375".to_string())
+    );
+    assert!(ApiEndpoint::parse("ssh://user@host").is_err());
+    assert!(ApiEndpoint::parse("tcp://").is_err());
+}
+
+#[test]
+fn test_stream_decoding() {
+    let mut lines = LineBuffer::default();
+    assert!(lines.push(b"{\"status\":").is_empty());
+    assert_eq!(lines.push(b"\"a\"}\r\n{\"x\""), vec![b"{\"status\":\"a\"}".to_vec()]);
+    assert_eq!(lines.finish(), Some(b"{\"x\"".to_vec()));
+
+    // 帧头与内容跨块到达, 不完整的行保留到下一帧
+    let mut data = frame(1, "hello\nwor");
+    data.extend(frame(2, "oops\n"));
+    data.extend(frame(1, "ld"));
+    let mut demuxer = StreamDemuxer::default();
+    let mut output = demuxer.push(&data[..5]);
+    output.extend(demuxer.push(&data[5..]));
+    output.extend(demuxer.finish());
+    assert_eq!(
+        output,
+        vec![
+            (OutputStream::Stdout, "hello".to_string()),
+            (OutputStream::Stderr, "oops".to_string()),
+            (OutputStream::Stdout, "world".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_format_size() {
+    assert_eq!(format_size(0), "0B");
+    assert_eq!(format_size(5_600), "5.6kB");
+    assert_eq!(format_size(120_000_000), "120MB");
+    assert_eq!(format_size(1_234_567_890), "1.23GB");
+}
+
+#[test]
+fn test_dockerignore() {
+    let ignore =
+        DockerIgnore::parse("# comment\nnode_modules\n*.log\n!keep.log\n/target/\ndocs/**/*.md\n");
+    assert!(ignore.is_ignored("node_modules"));
+    assert!(ignore.is_ignored("node_modules/a/b.js"));
+    assert!(ignore.is_ignored("debug.log"));
+    assert!(!ignore.is_ignored("keep.log"));
+    assert!(!ignore.is_ignored("src/debug.log"));
+    assert!(ignore.is_ignored("target/release/app"));
+    assert!(ignore.is_ignored("docs/a/b/readme.md"));
+    assert!(!ignore.is_ignored("src/main.rs"));
+}
+
+#[test]
+fn test_build_context_archive() {
+    let temp = tempfile::tempdir().unwrap();
+    let context = temp.path().join("ctx");
+    std::fs::create_dir_all(context.join("src")).unwrap();
+    std::fs::create_dir_all(context.join("node_modules/x")).unwrap();
+    std::fs::write(context.join(".dockerignore"), "node_modules\nDockerfile\n").unwrap();
+    std::fs::write(context.join("Dockerfile"), "FROM alpine\n").unwrap();
+    std::fs::write(context.join("src/main.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(context.join("node_modules/x/index.js"), "").unwrap();
+    std::fs::write(temp.path().join("Dockerfile.outside"), "FROM busybox\n").unwrap();
+
+    let files = context_files(&context).unwrap();
+    assert_eq!(files, vec![PathBuf::from(".dockerignore"), PathBuf::from("src/main.rs")]);
+
+    // 上下文内的 Dockerfile 即使被忽略也会发送
+    let (archive, dockerfile) =
+        build_context_archive(&context, &context.join("Dockerfile")).unwrap();
+    assert_eq!(dockerfile, "Dockerfile");
+    assert_eq!(tar_entries(&archive), vec![".dockerignore", "Dockerfile", "src/main.rs"]);
+
+    let (archive, dockerfile) =
+        build_context_archive(&context, &temp.path().join("Dockerfile.outside")).unwrap();
+    assert_eq!(dockerfile, ".confkit.Dockerfile");
+    assert_eq!(tar_entries(&archive), vec![".confkit.Dockerfile", ".dockerignore", "src/main.rs"]);
+}
+
+#[tokio::test]
+async fn test_image_inspect_and_missing_socket() {
+    let temp = tempfile::tempdir().unwrap();
+    let (socket, received) = fake_engine(
+        temp.path(),
+        Arc::new(|request: &Received| match request.path.as_str() {
+            "/_ping" => (200, b"OK".to_vec()),
+            "/images/app:1.0/json" => (
+                200,
+                json!({
+                    "Id": "sha256:0123456789abcdef0123",
+                    "Created": "2026-10-01T10:00:00.123456789Z",
+                    "Size": 120_000_000,
+                    "Config": { "Labels": { "io.confkit.content-hash": "abc" } }
+                })
+                .to_string()
+                .into_bytes(),
+            ),
+            _ => (404, br#"{"message":"No such image"}"#.to_vec()),
+        }),
+    );
+    let docker = engine(socket);
+
+    docker.check_engine().await.unwrap();
+    docker.ensure_running().await.unwrap();
+    assert!(docker.check_image_exists("app", "1.0").await.unwrap());
+    assert!(!docker.check_image_exists("app", "2.0").await.unwrap());
+
+    let info = docker.get_image_info("app", "1.0").await.unwrap();
+    assert_eq!(info.id, "0123456789ab");
+    assert_eq!(info.created_at, "2026-10-01 10:00:00 +0000 UTC");
+    assert_eq!(info.size, "120MB");
+    assert_eq!(info.status, ImageStatus::Built);
+    assert_eq!(docker.get_image_info("app", "2.0").await.unwrap().status, ImageStatus::Unbuilt);
+    assert_eq!(
+        docker.get_image_labels("app", "1.0").await.unwrap()["io.confkit.content-hash"],
+        "abc"
+    );
+    assert!(docker.get_image_labels("app", "2.0").await.unwrap().is_empty());
+
+    let err = docker.remove_image("app", "2.0").await.unwrap_err();
+    assert!(err.to_string().contains("No such image"), "{err}");
+    assert_eq!(received.lock().unwrap().last().unwrap().method, "DELETE");
+
+    let missing = engine(temp.path().join("missing.sock"));
+    assert!(missing.check_engine().await.is_err());
+    let err = missing.ensure_running().await.unwrap_err();
+    assert!(err.to_string().contains("Docker daemon is not running"), "{err}");
+}
+
+#[tokio::test]
+async fn test_pull_and_build_stream_progress() {
+    let temp = tempfile::tempdir().unwrap();
+    let (socket, received) = fake_engine(
+        temp.path(),
+        Arc::new(|request: &Received| {
+            let body = if request.path.contains("fromImage=broken") {
+                "{\"status\":\"Pulling from library/broken\"}\n{\"error\":\"manifest unknown\",\"errorDetail\":{\"message\":\"manifest unknown\"}}\n"
+            } else if request.path.starts_with("/images/create") {
+                "{\"status\":\"Pulling from library/alpine\",\"id\":\"3.18\"}\r\n{\"status\":\"Downloading\",\"progress\":\"[==>  ] 1MB/3MB\",\"id\":\"abc\"}\r\n{\"status\":\"Status: Downloaded newer image for alpine:3.18\"}\r\n"
+            } else {
+                "{\"stream\":\"Step 1/1 : FROM alpine\\n\"}\n{\"aux\":{\"ID\":\"sha256:abc\"}}\n{\"stream\":\"Successfully tagged app:1.0\\n\"}\n"
+            };
+            (200, body.as_bytes().to_vec())
+        }),
+    );
+    let docker = engine(socket);
+
+    docker.pull_image("alpine", "3.18").await.unwrap();
+    let err = docker.pull_image("broken", "1").await.unwrap_err();
+    assert_eq!(err.to_string(), "manifest unknown");
+
+    let context = temp.path().join("ctx");
+    std::fs::create_dir_all(&context).unwrap();
+    std::fs::write(context.join("Dockerfile"), "FROM alpine\n").unwrap();
+    let dockerfile = context.join("Dockerfile");
+    docker
+        .build_image(
+            "app",
+            "1.0",
+            dockerfile.to_str().unwrap(),
+            Some(context.to_str().unwrap()),
+            &ImageBuildOptions::default(),
+        )
+        .await
+        .unwrap();
+
+    let received = received.lock().unwrap();
+    assert_eq!(received[0].path, "/images/create?fromImage=alpine&tag=3.18");
+    let build = received.last().unwrap();
+    assert_eq!(build.method, "POST");
+    assert_eq!(build.path, "/build?t=app%3A1.0&dockerfile=Dockerfile&rm=1");
+    assert_eq!(build.content_type.as_deref(), Some("application/x-tar"));
+    assert_eq!(tar_entries(&build.body), vec!["Dockerfile"]);
+}
+
+#[tokio::test]
+async fn test_exec_output_and_exit_code() {
+    let temp = tempfile::tempdir().unwrap();
+    let (socket, received) = fake_engine(
+        temp.path(),
+        Arc::new(|request: &Received| match request.path.as_str() {
+            "/containers/builder/exec" => (201, br#"{"Id":"e1"}"#.to_vec()),
+            "/exec/e1/start" => {
+                let mut body = frame(1, "building\n");
+                body.extend(frame(2, "warning: slow\n"));
+                body.extend(frame(1, "done"));
+                (200, body)
+            }
+            "/exec/e1/json" => (200, br#"{"Running":false,"ExitCode":3}"#.to_vec()),
+            _ => (404, br#"{"message":"No such container"}"#.to_vec()),
+        }),
+    );
+    let docker = engine(socket);
+
+    let log_path = temp.path().join("task.log");
+    let logger = TaskLogger::new(log_path.to_string_lossy().to_string());
+    let environment = HashMap::from([("APP_ENV".to_string(), "prod".to_string())]);
+
+    let code = docker
+        .execute_in_container("builder", "sh", "/workspace", "make", &environment, &logger)
+        .await
+        .unwrap();
+    assert_eq!(code, 3);
+
+    logger.flush().await.unwrap();
+    let log = std::fs::read_to_string(&log_path).unwrap();
+    assert!(log.contains("    | building"), "{log}");
+    assert!(log.contains("    | warning: slow"), "{log}");
+    assert!(log.contains("    | done"), "{log}");
+
+    let create: Value = serde_json::from_slice(&received.lock().unwrap()[0].body).unwrap();
+    assert_eq!(create["Cmd"], json!(["sh", "-c", "make"]));
+    assert_eq!(create["Env"], json!(["APP_ENV=prod"]));
+    assert_eq!(create["WorkingDir"], "/workspace");
+
+    let err = docker
+        .execute_in_container("missing", "sh", "/", "true", &environment, &logger)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("No such container"), "{err}");
+}
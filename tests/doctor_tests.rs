@@ -0,0 +1,93 @@
+mod common;
+
+use common::fake_engine::fake_cli;
+use confkit_engine::core::doctor::{
+    parse_df_available, CheckStatus, Doctor, DoctorCheck, DoctorReport,
+};
+
+#[test]
+fn test_parse_df_available() {
+    let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                  /dev/vda1        102400000  30000000  72400000      30% /\n";
+    assert_eq!(parse_df_available(output), Some(72400000 * 1024));
+    assert_eq!(parse_df_available("Filesystem\n"), None);
+    assert_eq!(parse_df_available(""), None);
+}
+
+#[test]
+fn test_check_volumes() {
+    let temp = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(temp.path().join("volumes/workspace")).unwrap();
+
+    let checks = Doctor::check_volumes(temp.path());
+    assert_eq!(checks.len(), 5);
+
+    let workspace = checks.iter().find(|c| c.name == "volumes/workspace").unwrap();
+    assert_eq!(workspace.status, CheckStatus::Pass);
+    assert!(!temp.path().join("volumes/workspace/.confkit-doctor").exists());
+
+    let logs = checks.iter().find(|c| c.name == "volumes/logs").unwrap();
+    assert_eq!(logs.status, CheckStatus::Warn);
+}
+
+#[test]
+fn test_check_task_db() {
+    let temp = tempfile::tempdir().unwrap();
+    let path = temp.path().join("tasks.db");
+
+    assert_eq!(Doctor::check_task_db(&path).status, CheckStatus::Warn);
+
+    rusqlite::Connection::open(&path)
+        .unwrap()
+        .execute_batch("CREATE TABLE t (id INTEGER);")
+        .unwrap();
+    assert_eq!(Doctor::check_task_db(&path).status, CheckStatus::Pass);
+
+    std::fs::write(&path, b"not a sqlite database at all, just some garbage bytes").unwrap();
+    assert_eq!(Doctor::check_task_db(&path).status, CheckStatus::Fail);
+}
+
+#[test]
+fn test_report_counts() {
+    let mut report = DoctorReport::default();
+    report.push(DoctorCheck::pass("git", "ok"));
+    report.push(DoctorCheck::warn("disk space", "low"));
+    assert!(!report.has_failures());
+
+    report.push(DoctorCheck::fail("engine", "missing"));
+    assert!(report.has_failures());
+    assert_eq!(report.count(CheckStatus::Pass), 1);
+    assert_eq!(report.count(CheckStatus::Warn), 1);
+    assert_eq!(report.count(CheckStatus::Fail), 1);
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["checks"][2]["status"], "fail");
+}
+
+// Doctor::run 读取当前目录的配置, 因此在单个测试中切换工作目录
+#[tokio::test]
+async fn test_run_reports_unreachable_api_socket() {
+    let temp = tempfile::tempdir().unwrap();
+    let root = temp.path();
+    std::fs::create_dir_all(root.join("bin")).unwrap();
+    std::fs::create_dir_all(root.join("spaces")).unwrap();
+    std::fs::write(root.join("docker-compose.yml"), "services: {}\n").unwrap();
+    std::fs::write(
+        root.join(".confkit.yml"),
+        "version: 1.0.0\nengine: docker\nengine_mode: api\nengine_compose:\n  file: ./docker-compose.yml\nspaces:\n  - {name: hello, description: hello, path: ./spaces}\n",
+    )
+    .unwrap();
+
+    // docker 命令已安装, 但 DOCKER_HOST 指向的 socket 不存在
+    fake_cli(&root.join("bin"), "docker", "echo 'Docker version 27.0.0'");
+    let path = std::env::var("PATH").unwrap_or_default();
+    std::env::set_var("PATH", format!("{}:{path}", root.join("bin").display()));
+    std::env::set_var("DOCKER_HOST", format!("unix://{}", root.join("missing.sock").display()));
+    std::env::set_current_dir(root).unwrap();
+
+    let report = Doctor::run().await;
+    let daemon = report.checks.iter().find(|check| check.name == "engine daemon").unwrap();
+    assert_eq!(daemon.status, CheckStatus::Fail, "{:?}", report.checks);
+    assert!(daemon.detail.contains("Docker API socket not found"), "{}", daemon.detail);
+    assert!(report.has_failures());
+}
@@ -0,0 +1,84 @@
+use confkit_engine::infra::config::ConfKitConfigLoader;
+use confkit_engine::types::config::ConfKitProjectConfig;
+use confkit_engine::utils::dotenv;
+
+#[test]
+fn test_dotenv_quotes_export_and_comments() {
+    let content = r#"
+# comment
+export APP_NAME=hello # inline comment
+PLAIN = value with spaces
+URL=http://example.com/#anchor
+SINGLE='a b ${APP_NAME} \n'
+DOUBLE="a b\t\"c\""
+EMPTY=
+"#;
+    let parsed = dotenv::parse(content);
+    assert!(parsed.warnings.is_empty(), "{:?}", parsed.warnings);
+
+    let values = parsed.values;
+    assert_eq!(values["APP_NAME"], "hello");
+    assert_eq!(values["PLAIN"], "value with spaces");
+    assert_eq!(values["URL"], "http://example.com/#anchor");
+    assert_eq!(values["SINGLE"], "a b ${APP_NAME} \\n");
+    assert_eq!(values["DOUBLE"], "a b\t\"c\"");
+    assert_eq!(values["EMPTY"], "");
+}
+
+#[test]
+fn test_dotenv_multiline_and_expansion() {
+    let content = "HOST=db\nPORT=5432\nURL=\"postgres://${HOST}:${PORT}/app\"\nLATER=${GIT_HASH}\nESCAPED=\"\\${HOST}\"\nKEY=\"-----BEGIN-----\nabc\n-----END-----\"\nAFTER=1\n";
+    let parsed = dotenv::parse(content);
+    assert!(parsed.warnings.is_empty(), "{:?}", parsed.warnings);
+
+    let values = parsed.values;
+    assert_eq!(values["URL"], "postgres://db:5432/app");
+    assert_eq!(values["LATER"], "${GIT_HASH}");
+    assert_eq!(values["ESCAPED"], "${HOST}");
+    assert_eq!(values["KEY"], "-----BEGIN-----\nabc\n-----END-----");
+    assert_eq!(values["AFTER"], "1");
+}
+
+#[test]
+fn test_dotenv_warnings_with_line_numbers() {
+    let content = "GOOD=1\nnot a pair\n1BAD=x\nOPEN=\"never closed\n";
+    let parsed = dotenv::parse(content);
+
+    let lines: Vec<usize> = parsed.warnings.iter().map(|w| w.line).collect();
+    assert_eq!(lines, vec![2, 3, 4]);
+    assert_eq!(parsed.values.len(), 1);
+    assert_eq!(parsed.warnings[0].to_string(), "line 2: expected KEY=VALUE, got 'not a pair'");
+}
+
+#[test]
+fn test_load_json_and_toml_environment_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let json_path = dir.path().join("env.json");
+    let toml_path = dir.path().join("env.toml");
+    std::fs::write(
+        &json_path,
+        r#"{"NAME": "json", "PORT": 8080, "DEBUG": true, "NESTED": {"a": 1}}"#,
+    )
+    .unwrap();
+    std::fs::write(&toml_path, "NAME = \"toml\"\nRETRIES = 3\n").unwrap();
+
+    let project_config: ConfKitProjectConfig = serde_yaml::from_str(&format!(
+        "name: app\ndescription: app\nenvironment_files:\n  - format: json\n    path: {}\n  - format: toml\n    path: {}\nsteps: []\n",
+        json_path.display(),
+        toml_path.display()
+    ))
+    .unwrap();
+
+    let files = ConfKitConfigLoader::load_environment_files(&project_config);
+    assert_eq!(files.len(), 2);
+
+    let json = &files[0].1;
+    assert_eq!(json["NAME"], "json");
+    assert_eq!(json["PORT"], "8080");
+    assert_eq!(json["DEBUG"], "true");
+    assert!(!json.contains_key("NESTED"));
+
+    let toml = &files[1].1;
+    assert_eq!(toml["NAME"], "toml");
+    assert_eq!(toml["RETRIES"], "3");
+}
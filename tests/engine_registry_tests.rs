@@ -0,0 +1,280 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use confkit_engine::engine::cli::{
+    parse_container_output, parse_container_status, parse_image_list_output, parse_image_output,
+};
+use confkit_engine::engine::registry::EngineRegistry;
+use confkit_engine::engine::traits::ContainerEngine;
+use confkit_engine::engine::ConfKitEngine;
+use confkit_engine::infra::logger::TaskLogger;
+use confkit_engine::shared::global::ENGINE_REGISTRY;
+use confkit_engine::types::config::{
+    ContainerStatus, Engine, EngineContainerInfo, EngineImageInfo, EngineMode, EngineServiceConfig,
+    ImageBuildOptions, ImageStatus, RegistryAuth,
+};
+
+/// 记录调用的测试引擎, 仅存在名为 "present" 的镜像
+#[derive(Default)]
+struct FakeEngine {
+    calls: Mutex<Vec<String>>,
+}
+
+impl FakeEngine {
+    fn record(&self, call: String) {
+        self.calls.lock().unwrap().push(call);
+    }
+}
+
+#[async_trait]
+impl ContainerEngine for FakeEngine {
+    async fn check_engine(&self) -> Result<()> {
+        self.record("check_engine".to_string());
+        Ok(())
+    }
+
+    async fn ensure_running(&self) -> Result<()> {
+        self.record("ensure_running".to_string());
+        Ok(())
+    }
+
+    async fn check_image_exists(&self, image: &str, tag: &str) -> Result<bool> {
+        self.record(format!("check_image_exists {image}:{tag}"));
+        Ok(image == "present")
+    }
+
+    async fn get_image_info(&self, image: &str, tag: &str) -> Result<EngineImageInfo> {
+        Ok(parse_image_output(image, tag, ""))
+    }
+
+    async fn list_image_tags(&self, image: &str) -> Result<Vec<EngineImageInfo>> {
+        self.record(format!("list_image_tags {image}"));
+        Ok(vec![])
+    }
+
+    async fn get_image_labels(&self, _image: &str, _tag: &str) -> Result<BTreeMap<String, String>> {
+        Ok(BTreeMap::new())
+    }
+
+    async fn pull_image(&self, image: &str, tag: &str) -> Result<()> {
+        self.record(format!("pull_image {image}:{tag}"));
+        Ok(())
+    }
+
+    async fn build_image(
+        &self,
+        name: &str,
+        tag: &str,
+        dockerfile: &str,
+        context: Option<&str>,
+        _options: &ImageBuildOptions,
+    ) -> Result<()> {
+        self.record(format!("build_image {name}:{tag} {dockerfile} {}", context.unwrap_or(".")));
+        Ok(())
+    }
+
+    async fn tag_image(
+        &self,
+        image: &str,
+        tag: &str,
+        repository: &str,
+        target_tag: &str,
+    ) -> Result<()> {
+        self.record(format!("tag_image {image}:{tag} {repository}:{target_tag}"));
+        Ok(())
+    }
+
+    async fn push_image(
+        &self,
+        repository: &str,
+        tag: &str,
+        auth: Option<&RegistryAuth>,
+    ) -> Result<()> {
+        let user = auth.map_or("anonymous", |auth| auth.username.as_str());
+        self.record(format!("push_image {repository}:{tag} {user}"));
+        Ok(())
+    }
+
+    async fn remove_image(&self, image: &str, tag: &str) -> Result<()> {
+        self.record(format!("remove_image {image}:{tag}"));
+        Ok(())
+    }
+
+    async fn check_container_exists(&self, _name: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    async fn create_container(&self, name: &str) -> Result<()> {
+        self.record(format!("create_container {name}"));
+        Ok(())
+    }
+
+    async fn remove_container(&self, name: &str) -> Result<()> {
+        self.record(format!("remove_container {name}"));
+        Ok(())
+    }
+
+    async fn start_container(&self, name: &str) -> Result<()> {
+        self.record(format!("start_container {name}"));
+        Ok(())
+    }
+
+    async fn stop_container(&self, name: &str) -> Result<()> {
+        self.record(format!("stop_container {name}"));
+        Ok(())
+    }
+
+    async fn restart_container(&self, name: &str) -> Result<()> {
+        self.record(format!("restart_container {name}"));
+        Ok(())
+    }
+
+    async fn get_container_info(&self, name: &str) -> Result<EngineContainerInfo> {
+        Ok(parse_container_output(name, "", service("")))
+    }
+
+    async fn execute_in_container(
+        &self,
+        container: &str,
+        _shell: &str,
+        _working_dir: &str,
+        cmd: &str,
+        _environment: &HashMap<String, String>,
+        _task_logger: &TaskLogger,
+    ) -> Result<i32> {
+        self.record(format!("execute_in_container {container} {cmd}"));
+        Ok(0)
+    }
+}
+
+fn service(image: &str) -> EngineServiceConfig {
+    serde_yaml::from_str(&format!(
+        "container_name: builder\nimage: '{image}'\nworking_dir: /workspace\n"
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_registry_register_and_replace() {
+    let mut registry = EngineRegistry::empty();
+    assert!(registry.get(&Engine::Docker).is_none());
+
+    assert!(registry.register(Engine::Docker, Arc::new(FakeEngine::default())).is_none());
+    assert!(registry.get(&Engine::Docker).is_some());
+    assert!(registry.get(&Engine::Podman).is_none());
+    assert!(registry.register(Engine::Docker, Arc::new(FakeEngine::default())).is_some());
+
+    let registry = EngineRegistry::default();
+    assert!(registry.get(&Engine::Docker).is_some());
+    assert!(registry.get(&Engine::Podman).is_some());
+    assert!(registry.get(&Engine::Nerdctl).is_some());
+}
+
+#[test]
+fn test_nerdctl_engine() {
+    let engine: Engine = serde_yaml::from_str("nerdctl").unwrap();
+    assert_eq!(engine, Engine::Nerdctl);
+    assert_eq!(engine.to_string(), "nerdctl");
+
+    // nerdctl 只有命令行实现
+    ConfKitEngine::set_mode(&Engine::Nerdctl, EngineMode::Cli).unwrap();
+    let err = ConfKitEngine::set_mode(&Engine::Nerdctl, EngineMode::Api).unwrap_err();
+    assert!(err.to_string().contains("not supported by nerdctl"), "{err}");
+}
+
+#[tokio::test]
+async fn test_host_only_engine() {
+    // engine: none 无需检测, 也没有可调用的实现
+    ConfKitEngine::set_mode(&Engine::None, EngineMode::Api).unwrap();
+    ConfKitEngine::check_engine(Engine::None).await.unwrap();
+    assert!(ENGINE_REGISTRY.read().unwrap().get(&Engine::None).is_none());
+    assert_eq!(serde_yaml::from_str::<Engine>("none").unwrap(), Engine::None);
+}
+
+#[tokio::test]
+async fn test_facade_dispatches_to_registered_engine() {
+    let fake = Arc::new(FakeEngine::default());
+    ENGINE_REGISTRY.write().unwrap().register(Engine::Podman, fake.clone());
+
+    ConfKitEngine::set_engine(Engine::Podman).await.unwrap();
+    assert!(matches!(ConfKitEngine::get_engine().await.unwrap(), Engine::Podman));
+
+    assert!(ConfKitEngine::check_image_exists("present", "1.0").await.unwrap());
+    assert!(!ConfKitEngine::check_image_exists("missing", "1.0").await.unwrap());
+    ConfKitEngine::build_image(
+        "app",
+        "1.0",
+        "Dockerfile",
+        Some("./ctx"),
+        &ImageBuildOptions::default(),
+    )
+    .await
+    .unwrap();
+    ConfKitEngine::tag_image("app", "1.0", "localhost:5000/app", "latest").await.unwrap();
+    ConfKitEngine::push_image("localhost:5000/app", "latest", None).await.unwrap();
+    ConfKitEngine::restart_container("builder").await.unwrap();
+    assert!(ConfKitEngine::list_image_tags("app").await.unwrap().is_empty());
+
+    let info = ConfKitEngine::get_image_info("missing", "1.0").await.unwrap();
+    assert_eq!(info.status, ImageStatus::Unbuilt);
+
+    assert_eq!(
+        *fake.calls.lock().unwrap(),
+        vec![
+            "check_engine",
+            "check_image_exists present:1.0",
+            "check_image_exists missing:1.0",
+            "build_image app:1.0 Dockerfile ./ctx",
+            "tag_image app:1.0 localhost:5000/app:latest",
+            "push_image localhost:5000/app:latest anonymous",
+            "restart_container builder",
+            "list_image_tags app",
+        ]
+    );
+}
+
+#[test]
+fn test_parse_container_status() {
+    assert_eq!(parse_container_status("Up 3 minutes"), ContainerStatus::Up);
+    assert_eq!(parse_container_status("Exited (0) 2 hours ago"), ContainerStatus::Exited);
+    assert_eq!(parse_container_status("Created"), ContainerStatus::Created);
+    assert_eq!(parse_container_status("Up 1 second (Paused)"), ContainerStatus::Up);
+    assert_eq!(parse_container_status(""), ContainerStatus::Unbuilt);
+}
+
+#[test]
+fn test_parse_cli_output() {
+    let info =
+        parse_image_output("app", "1.0", "\nabc123\t1.0\t2026-10-01 10:00:00 +0000 UTC\t120MB\n");
+    assert_eq!(info.id, "abc123");
+    assert_eq!(info.created_at, "2026-10-01 10:00:00 +0000 UTC");
+    assert_eq!(info.size, "120MB");
+    assert_eq!(info.status, ImageStatus::Built);
+
+    let tags = parse_image_list_output(
+        "app",
+        "abc123\t1.0\t2026-10-01 10:00:00 +0000 UTC\t120MB\ndef456\t<none>\t2026-09-01 10:00:00 +0000 UTC\t90MB\n\nabc123\tlatest\t2026-10-01 10:00:00 +0000 UTC\t120MB\n",
+    );
+    let tags: Vec<&str> = tags.iter().map(|info| info.tag.as_str()).collect();
+    assert_eq!(tags, vec!["1.0", "latest"]);
+
+    let info = parse_image_output("app", "1.0", "  \n");
+    assert_eq!(info.status, ImageStatus::Unbuilt);
+    assert_eq!(info.id, "");
+
+    // 列数不足时补全, 镜像列为空时使用 compose 中的镜像
+    let info =
+        parse_container_output("builder", "c1\t\tExited (1) 1 hour ago\n", service("node:20"));
+    assert_eq!(info.id, "c1");
+    assert_eq!(info.image, "node:20");
+    assert_eq!(info.status, ContainerStatus::Exited);
+    assert_eq!(info.size, "");
+    assert_eq!(info.working_dir.as_deref(), Some("/workspace"));
+
+    let info = parse_container_output("builder", "", service("node:20"));
+    assert_eq!(info.status, ContainerStatus::Unbuilt);
+    assert_eq!(info.image, "node:20");
+}
@@ -0,0 +1,44 @@
+use confkit_engine::core::executor::environment::{EnvironmentLayers, EnvironmentSource};
+use std::collections::HashMap;
+
+#[test]
+fn test_environment_layers_override_tracking() {
+    let mut layers = EnvironmentLayers::new();
+
+    let mut from_file = HashMap::new();
+    from_file.insert("APP_NAME".to_string(), "from-file".to_string());
+    layers.extend(&from_file, EnvironmentSource::File { path: "./app.env".to_string() });
+    layers.insert("TASK_ID", "abc", EnvironmentSource::Builtin);
+    layers.insert("APP_NAME", "from-project", EnvironmentSource::Project);
+    layers.insert("APP_NAME", "from-args", EnvironmentSource::Args);
+
+    let entry = layers.entries().find(|e| e.key == "APP_NAME").unwrap();
+    assert_eq!(entry.value, "from-args");
+    assert_eq!(entry.source, EnvironmentSource::Args);
+    assert_eq!(entry.overridden.len(), 2);
+    assert_eq!(entry.overridden[0].value, "from-file");
+    assert_eq!(entry.overridden[0].source.to_string(), "file(./app.env)");
+    assert_eq!(entry.overridden[1].source, EnvironmentSource::Project);
+
+    let map = layers.to_map();
+    assert_eq!(map.get("APP_NAME").unwrap(), "from-args");
+    assert_eq!(map.get("TASK_ID").unwrap(), "abc");
+}
+
+#[test]
+fn test_environment_layers_masking() {
+    let mut layers = EnvironmentLayers::new();
+    layers.insert("API_TOKEN", "first", EnvironmentSource::Project);
+    layers.insert("API_TOKEN", "second", EnvironmentSource::Args);
+    layers.insert("APP_NAME", "demo", EnvironmentSource::Project);
+
+    let masked = layers.masked();
+    let token = masked.entries().find(|e| e.key == "API_TOKEN").unwrap();
+    assert_eq!(token.value, "******");
+    assert_eq!(token.overridden[0].value, "******");
+    let name = masked.entries().find(|e| e.key == "APP_NAME").unwrap();
+    assert_eq!(name.value, "demo");
+
+    // 原始值不受影响
+    assert_eq!(layers.to_map().get("API_TOKEN").unwrap(), "second");
+}
@@ -0,0 +1,85 @@
+use confkit_engine::core::builder::graph::ImageGraph;
+use confkit_engine::formatter::builder_image::BuilderImageFormatter;
+use confkit_engine::types::config::{ConfKitImageInfo, ImageStatus};
+
+fn graph(images: &[(&str, &str)]) -> ImageGraph {
+    ImageGraph::new(images.iter().map(|(name, base)| (*name, *base, "1")))
+}
+
+fn info(name: &str, base_image: &str, status: ImageStatus) -> ConfKitImageInfo {
+    ConfKitImageInfo {
+        name: name.to_string(),
+        base_image: base_image.to_string(),
+        tag: "20".to_string(),
+        context: ".".to_string(),
+        engine_file: "Dockerfile".to_string(),
+        status,
+        id: None,
+        created_at: None,
+        size: None,
+    }
+}
+
+#[test]
+fn test_build_order_puts_local_bases_first() {
+    // app -> builder -> base -> node (远程)
+    let images =
+        graph(&[("app", "builder"), ("tool", "alpine"), ("builder", "base"), ("base", "node")]);
+
+    assert_eq!(images.local_base(0), Some(2));
+    assert_eq!(images.local_base(1), None);
+    assert_eq!(images.roots(), vec![1, 3]);
+    assert_eq!(images.children(3), vec![2]);
+    assert_eq!(images.build_order().unwrap(), vec![3, 2, 0, 1]);
+    assert_eq!(images.ancestors(0).unwrap(), vec![3, 2, 0]);
+    assert_eq!(images.ancestors(1).unwrap(), vec![1]);
+
+    // 标签不同时不是本地基础镜像
+    let images = ImageGraph::new([("app", "base", "2"), ("base", "node", "1")]);
+    assert_eq!(images.local_base(0), None);
+    assert_eq!(images.build_order().unwrap(), vec![0, 1]);
+}
+
+#[test]
+fn test_dependency_cycles() {
+    let images = graph(&[("tool", "alpine"), ("a", "b"), ("b", "c"), ("c", "a")]);
+    assert_eq!(images.find_cycle(), Some(vec![1, 2, 3, 1]));
+    let err = images.build_order().unwrap_err();
+    assert_eq!(err.to_string(), "Image dependency cycle: a:1 -> b:1 -> c:1 -> a:1");
+    assert!(images.ancestors(0).is_err());
+
+    let images = graph(&[("self", "self")]);
+    assert_eq!(images.describe(&images.find_cycle().unwrap()), "self:1 -> self:1");
+
+    assert_eq!(graph(&[("a", "node"), ("b", "a")]).find_cycle(), None);
+}
+
+#[test]
+fn test_image_tree() {
+    let images = vec![
+        info("app", "builder", ImageStatus::Unbuilt),
+        info("base", "node", ImageStatus::Built),
+        info("builder", "base", ImageStatus::Stale),
+        info("lint", "base", ImageStatus::Built),
+        info("tool", "alpine", ImageStatus::Built),
+        info("base-slim", "node", ImageStatus::Unbuilt),
+    ];
+
+    assert_eq!(
+        BuilderImageFormatter::format_tree(&images).unwrap(),
+        [
+            "node:20",
+            "├── base:20 (Built)",
+            "│   ├── builder:20 (Stale)",
+            "│   │   └── app:20 (N/A)",
+            "│   └── lint:20 (Built)",
+            "└── base-slim:20 (N/A)",
+            "alpine:20",
+            "└── tool:20 (Built)",
+        ]
+        .join("\n")
+    );
+
+    let cyclic = vec![info("a", "b", ImageStatus::Built), info("b", "a", ImageStatus::Built)];
+    assert!(BuilderImageFormatter::format_tree(&cyclic).is_err());
+}
@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+
+mod common;
+
+use common::fake_engine::{fake_cli, fake_engine, Received};
+use confkit_engine::core::builder::image::ImageBuilder;
+use confkit_engine::core::builder::prune::{
+    parse_age, parse_created_at, protected_references, PrunePolicy,
+};
+use confkit_engine::engine::api::client::{ApiClient, ApiEndpoint};
+use confkit_engine::engine::api::docker::DockerApiEngine;
+use confkit_engine::engine::cli::CliEngine;
+use confkit_engine::engine::traits::ContainerEngine;
+use confkit_engine::engine::ConfKitEngine;
+use confkit_engine::shared::global::{CONFIG, ENGINE_REGISTRY};
+use confkit_engine::types::config::{
+    ConfKitConfig, ConfKitImageConfig, Engine, EngineImageInfo, ImageStatus,
+};
+
+fn tag(tag: &str, created_at: &str) -> EngineImageInfo {
+    EngineImageInfo {
+        id: "abc".to_string(),
+        name: "node-builder".to_string(),
+        tag: tag.to_string(),
+        created_at: created_at.to_string(),
+        size: "120MB".to_string(),
+        status: ImageStatus::Built,
+    }
+}
+
+fn now() -> DateTime<Utc> {
+    parse_created_at("2026-10-19T00:00:00Z").unwrap()
+}
+
+fn selected(policy: &PrunePolicy, tags: &[EngineImageInfo], protected: &[&str]) -> Vec<String> {
+    let protected: HashSet<String> = protected.iter().map(|s| s.to_string()).collect();
+    policy.select(tags, &protected, now()).into_iter().map(|info| info.tag).collect()
+}
+
+#[test]
+fn test_parse_age_and_created_at() {
+    assert_eq!(parse_age("30d").unwrap(), Duration::days(30));
+    assert_eq!(parse_age("12h").unwrap(), Duration::hours(12));
+    assert_eq!(parse_age("2w").unwrap(), Duration::weeks(2));
+    assert_eq!(parse_age("90m").unwrap(), Duration::minutes(90));
+    for invalid in ["", "30", "d", "30x", "-1d", "1.5d"] {
+        assert!(parse_age(invalid).is_err(), "{invalid}");
+    }
+
+    let expected = parse_created_at("2026-10-01T10:00:00Z").unwrap();
+    assert_eq!(parse_created_at("2026-10-01 10:00:00 +0000 UTC"), Some(expected));
+    assert_eq!(parse_created_at("2026-10-01 18:00:00 +0800 CST"), Some(expected));
+    assert_eq!(
+        parse_created_at("2026-10-01 10:00:00.123 +0000"),
+        Some(expected + Duration::milliseconds(123))
+    );
+    assert_eq!(parse_created_at("2026-10-01 10:00:00"), Some(expected));
+    assert_eq!(parse_created_at("3 weeks ago"), None);
+}
+
+#[test]
+fn test_prune_policy() {
+    assert!(PrunePolicy::new(None, None).is_err());
+    assert!(PrunePolicy::new(Some("soon"), None).is_err());
+
+    let tags = vec![
+        tag("20", "2026-08-01 00:00:00 +0000 UTC"),
+        tag("1.0.0", "2026-09-01 00:00:00 +0000 UTC"),
+        tag("1.1.0", "2026-10-01 00:00:00 +0000 UTC"),
+        tag("1.2.0", "2026-10-18 00:00:00 +0000 UTC"),
+        tag("0.9.0", "2026-07-01 00:00:00 +0000 UTC"),
+        tag("unknown", "3 months ago"),
+    ];
+
+    // 只按时间: 早于 30 天且未被引用的标签
+    let policy = PrunePolicy::new(Some("30d"), None).unwrap();
+    assert_eq!(selected(&policy, &tags, &["node-builder:20"]), vec!["1.0.0", "0.9.0"]);
+
+    // 只按数量: 保留最新 2 个, 被引用的标签计入数量
+    let policy = PrunePolicy::new(None, Some(2)).unwrap();
+    assert_eq!(selected(&policy, &tags, &["node-builder:1.0.0"]), vec!["20", "0.9.0"]);
+
+    // 同时指定: 最新 3 个之外且早于 30 天
+    let policy = PrunePolicy::new(Some("30d"), Some(3)).unwrap();
+    assert_eq!(selected(&policy, &tags, &["node-builder:20"]), vec!["0.9.0"]);
+
+    let policy = PrunePolicy::new(Some("1w"), Some(10)).unwrap();
+    assert!(selected(&policy, &tags, &[]).is_empty());
+}
+
+#[test]
+fn test_protected_references() {
+    let images: Vec<ConfKitImageConfig> = serde_yaml::from_str(
+        "- {name: node-builder, base_image: node, tag: '20', context: ., engine_file: Dockerfile}\n",
+    )
+    .unwrap();
+    let in_use = vec![
+        "node-builder:1.1.0".to_string(),
+        "docker.io/library/node-builder".to_string(),
+        "localhost:5000/team/rust".to_string(),
+        "".to_string(),
+    ];
+
+    let mut protected: Vec<String> = protected_references(&images, &in_use).into_iter().collect();
+    protected.sort();
+    assert_eq!(
+        protected,
+        vec![
+            "localhost:5000/team/rust:latest",
+            "node-builder:1.1.0",
+            "node-builder:20",
+            "node-builder:latest",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_api_list_image_tags() {
+    let temp = tempfile::tempdir().unwrap();
+    let (socket, received) = fake_engine(
+        temp.path(),
+        Arc::new(|_: &Received| {
+            let summaries = json!([
+                {
+                    "Id": "sha256:0123456789abcdef0123",
+                    "RepoTags": ["node-builder:20", "node-builder:latest", "node-builder-slim:20"],
+                    "Created": 1790848800,
+                    "Size": 120_000_000
+                },
+                { "Id": "sha256:fedcba9876543210", "RepoTags": null, "Created": 1790000000 },
+                { "Id": "sha256:aaaaaaaaaaaaaaaa", "RepoTags": ["node-builder:<none>"], "Created": 1 }
+            ]);
+            (200, summaries.to_string().into_bytes())
+        }),
+    );
+    let docker = DockerApiEngine::new(ApiClient::new(ApiEndpoint::Unix(socket)));
+
+    let tags = docker.list_image_tags("node-builder").await.unwrap();
+    let tags: Vec<(&str, &str, &str)> = tags
+        .iter()
+        .map(|info| (info.id.as_str(), info.tag.as_str(), info.created_at.as_str()))
+        .collect();
+    assert_eq!(
+        tags,
+        vec![
+            ("0123456789ab", "20", "2026-10-01 10:00:00 +0000 UTC"),
+            ("0123456789ab", "latest", "2026-10-01 10:00:00 +0000 UTC"),
+        ]
+    );
+
+    let received = received.lock().unwrap();
+    assert_eq!(
+        received[0].path,
+        "/images/json?filters=%7B%22reference%22%3A%5B%22node-builder%22%5D%7D"
+    );
+}
+
+#[tokio::test]
+async fn test_prune_fails_when_engine_refuses_removal() {
+    let temp = tempfile::tempdir().unwrap();
+    let calls = temp.path().join("calls.log");
+    let compose = temp.path().join("docker-compose.yml");
+    std::fs::write(&compose, "services: {}\n").unwrap();
+
+    // 1.0.0 仍被其他镜像引用, docker rmi 以状态码 1 退出
+    let docker = fake_cli(
+        temp.path(),
+        "docker",
+        &format!(
+            r#"echo "$@" >> {calls}
+case "$1" in
+  images) printf 'abc\t1.0.0\t2026-08-01 00:00:00 +0000 UTC\t120MB\ndef\t1.1.0\t2026-08-02 00:00:00 +0000 UTC\t120MB\n' ;;
+  rmi) [ "$2" = node-builder:1.0.0 ] && echo 'conflict: image has dependent child images' >&2 && exit 1 ;;
+esac
+exit 0"#,
+            calls = calls.display()
+        ),
+    );
+
+    let config: ConfKitConfig = serde_yaml::from_str(&format!(
+        "version: 1.0.0\nengine: docker\nengine_compose: {{file: {}}}\nimages:\n  - {{name: node-builder, base_image: node, tag: '20', context: ., engine_file: Dockerfile}}\n",
+        compose.display()
+    ))
+    .unwrap();
+    *CONFIG.write().unwrap() = Some(config);
+    ENGINE_REGISTRY.write().unwrap().register(
+        Engine::Docker,
+        Arc::new(CliEngine::docker().with_program(docker.display().to_string())),
+    );
+    ConfKitEngine::set_engine(Engine::Docker).await.unwrap();
+
+    let policy = PrunePolicy::new(Some("30d"), None).unwrap();
+    let err = ImageBuilder::prune(None, &policy, false).await.unwrap_err();
+    assert_eq!(err.to_string(), "Failed to remove 1 image tag(s)");
+
+    // 失败的标签不影响其余标签的清理
+    let calls = std::fs::read_to_string(calls).unwrap();
+    let removed: Vec<&str> = calls.lines().filter(|line| line.starts_with("rmi")).collect();
+    assert_eq!(removed, vec!["rmi node-builder:1.1.0", "rmi node-builder:1.0.0"]);
+}
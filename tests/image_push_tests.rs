@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::URL_SAFE, Engine as _};
+use serde_json::{json, Value};
+
+mod common;
+
+use common::fake_engine::{fake_cli, fake_engine, fake_registry, Received};
+use common::fixtures::image_config;
+use confkit_engine::core::builder::image::ImageBuilder;
+use confkit_engine::engine::api::client::{ApiClient, ApiEndpoint};
+use confkit_engine::engine::api::docker::{registry_auth_header, DockerApiEngine};
+use confkit_engine::engine::cli::CliEngine;
+use confkit_engine::engine::traits::ContainerEngine;
+use confkit_engine::infra::git::LocalRevision;
+use confkit_engine::infra::registry::{
+    normalize_registry, parse_challenge, registry_base_url, DockerConfig, RegistryClient,
+};
+use confkit_engine::types::config::RegistryAuth;
+
+fn auth(username: &str, password: &str, registry: &str) -> RegistryAuth {
+    RegistryAuth {
+        username: username.to_string(),
+        password: password.to_string(),
+        server_address: registry.to_string(),
+    }
+}
+
+#[test]
+fn test_push_tag_templates() {
+    let config = image_config("node-builder", "push: {registry: localhost:5000}");
+    let revision = LocalRevision {
+        commit_hash: "0123456789abcdef0123456789abcdef01234567".to_string(),
+        repo_url: None,
+    };
+    let variables = ImageBuilder::push_variables(&config, Some(&revision), Some("1.2.0".into()));
+    assert_eq!(variables["TAG"], "20");
+    assert_eq!(variables["GIT_HASH_SHORT"], "01234567");
+    assert_eq!(variables["PROJECT_VERSION"], "1.2.0");
+
+    assert_eq!(
+        ImageBuilder::render_tag("${PROJECT_VERSION}-${GIT_HASH_SHORT}", &variables).unwrap(),
+        "1.2.0-01234567"
+    );
+    let err = ImageBuilder::render_tag("v${BUILD_NUMBER}", &variables).unwrap_err();
+    assert!(err.to_string().contains("undefined variable ${BUILD_NUMBER}"), "{err}");
+
+    // 不在 git 仓库中时没有 GIT_HASH_SHORT
+    let variables = ImageBuilder::push_variables(&config, None, Some("".into()));
+    assert!(!variables.contains_key("GIT_HASH_SHORT"));
+    let err = ImageBuilder::render_tag("${PROJECT_VERSION}", &variables).unwrap_err();
+    assert!(err.to_string().contains("invalid tag ''"), "{err}");
+}
+
+#[test]
+fn test_push_targets() {
+    let variables = HashMap::from([
+        ("TAG".to_string(), "20".to_string()),
+        ("PROJECT_VERSION".to_string(), "1.2.0".to_string()),
+    ]);
+
+    let config = image_config("node-builder", "push: {registry: localhost:5000}");
+    assert_eq!(
+        ImageBuilder::push_targets(&config, &variables).unwrap(),
+        vec![("localhost:5000/node-builder".to_string(), "20".to_string())]
+    );
+
+    let config = image_config("node-builder", 
+        "push:\n  registry: https://ghcr.io/\n  repository: team/node\n  tags: ['${TAG}', latest, '${PROJECT_VERSION}', '20']\n",
+    );
+    let targets: Vec<String> = ImageBuilder::push_targets(&config, &variables)
+        .unwrap()
+        .into_iter()
+        .map(|(repository, tag)| format!("{repository}:{tag}"))
+        .collect();
+    assert_eq!(
+        targets,
+        vec!["ghcr.io/team/node:20", "ghcr.io/team/node:latest", "ghcr.io/team/node:1.2.0"]
+    );
+
+    assert!(ImageBuilder::push_targets(&image_config("node-builder", ""), &variables).is_err());
+}
+
+#[test]
+fn test_push_credentials() {
+    let temp = tempfile::tempdir().unwrap();
+    let password_file = temp.path().join("token");
+    fs::write(&password_file, "s3cret\n").unwrap();
+    std::env::set_var("CONFKIT_TEST_PUSH_USER", "ci-bot");
+
+    // 配置中的 file / env 密钥
+    let config = image_config("node-builder", &format!(
+        "push:\n  registry: localhost:5000\n  credentials:\n    - {{name: username, from: env, env: CONFKIT_TEST_PUSH_USER}}\n    - {{name: password, from: file, path: {}}}\n",
+        password_file.display()
+    ));
+    let docker_config = temp.path().join("config.json");
+    assert_eq!(
+        ImageBuilder::registry_auth(config.push.as_ref().unwrap(), &docker_config).unwrap(),
+        Some(auth("ci-bot", "s3cret", "localhost:5000"))
+    );
+
+    let config = image_config("node-builder", 
+        "push:\n  registry: localhost:5000\n  credentials:\n    - {name: username, from: env, env: CONFKIT_TEST_PUSH_USER}\n",
+    );
+    let err =
+        ImageBuilder::registry_auth(config.push.as_ref().unwrap(), &docker_config).unwrap_err();
+    assert!(err.to_string().contains("require 'username' and 'password'"), "{err}");
+
+    let config = image_config("node-builder", 
+        "push:\n  registry: localhost:5000\n  credentials:\n    - {name: password, from: encrypted}\n",
+    );
+    let err =
+        ImageBuilder::registry_auth(config.push.as_ref().unwrap(), &docker_config).unwrap_err();
+    assert!(err.to_string().contains("requires push.space"), "{err}");
+
+    // 未配置凭据时读取 docker 配置文件, 文件不存在时匿名推送
+    let config = image_config("node-builder", "push: {registry: localhost:5000}");
+    let push = config.push.as_ref().unwrap();
+    assert_eq!(ImageBuilder::registry_auth(push, &docker_config).unwrap(), None);
+
+    let mut saved = DockerConfig::load(&docker_config).unwrap();
+    saved.set_auth(&auth("admin", "pa:ss", "localhost:5000"));
+    saved.save().unwrap();
+    assert_eq!(
+        ImageBuilder::registry_auth(push, &docker_config).unwrap(),
+        Some(auth("admin", "pa:ss", "localhost:5000"))
+    );
+}
+
+#[test]
+fn test_docker_config_auths() {
+    let temp = tempfile::tempdir().unwrap();
+    let path = temp.path().join("docker/config.json");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(
+        &path,
+        r#"{"auths":{"https://index.docker.io/v1/":{"auth":"dXNlcjpodWI="}},"currentContext":"default"}"#,
+    )
+    .unwrap();
+
+    let mut config = DockerConfig::load(&path).unwrap();
+    assert_eq!(config.auth("docker.io").unwrap(), Some(auth("user", "hub", "docker.io")));
+    assert_eq!(config.auth("localhost:5000").unwrap(), None);
+
+    config.set_auth(&auth("admin", "secret", "http://localhost:5000/"));
+    config.save().unwrap();
+
+    // 其余字段保留, 仓库地址按主机名存储
+    let saved: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(saved["currentContext"], "default");
+    assert_eq!(saved["auths"]["localhost:5000"]["auth"], "YWRtaW46c2VjcmV0");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+    }
+    // 经临时文件重命名写入, 不留下临时文件
+    assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+
+    let mut config = DockerConfig::load(&path).unwrap();
+    assert!(config.remove_auth("localhost:5000"));
+    assert!(!config.remove_auth("localhost:5000"));
+    assert!(config.auth("index.docker.io").unwrap().is_some());
+}
+
+#[test]
+fn test_registry_addresses() {
+    assert_eq!(normalize_registry("https://Registry-1.Docker.io/v2/"), "index.docker.io");
+    assert_eq!(normalize_registry("ghcr.io/team/app"), "ghcr.io");
+    assert_eq!(registry_base_url("docker.io", false), "https://registry-1.docker.io");
+    assert_eq!(registry_base_url("localhost:5000", false), "http://localhost:5000");
+    assert_eq!(registry_base_url("127.0.0.1:5000", false), "http://127.0.0.1:5000");
+    assert_eq!(registry_base_url("registry.lan:5000", true), "http://registry.lan:5000");
+    assert_eq!(registry_base_url("ghcr.io", false), "https://ghcr.io");
+
+    let (scheme, params) =
+        parse_challenge(r#"Bearer realm="https://auth.example.com/token",service="registry""#);
+    assert_eq!(scheme, "bearer");
+    assert_eq!(params["realm"], "https://auth.example.com/token");
+    assert_eq!(params["service"], "registry");
+}
+
+#[tokio::test]
+async fn test_registry_login_basic_and_bearer() {
+    // registry:2 的 htpasswd 认证: /v2/ 返回 Basic 质询
+    let (address, _) = fake_registry(Arc::new(|request: &Received| {
+        match request.headers.get("authorization").map(String::as_str) {
+            // admin:secret
+            Some("Basic YWRtaW46c2VjcmV0") => (200, vec![], b"{}".to_vec()),
+            _ => (
+                401,
+                vec![("WWW-Authenticate".to_string(), r#"Basic realm="Registry""#.to_string())],
+                br#"{"errors":[{"code":"UNAUTHORIZED"}]}"#.to_vec(),
+            ),
+        }
+    }))
+    .await;
+
+    let client = RegistryClient::new(&address, false);
+    client.check_login(&auth("admin", "secret", &address)).await.unwrap();
+    let err = client.check_login(&auth("admin", "wrong", &address)).await.unwrap_err();
+    assert!(err.to_string().contains("invalid username or password"), "{err}");
+
+    // token 认证: 凭据发送到质询中的 realm
+    let (address, received) = fake_registry(Arc::new(|request: &Received| {
+        if request.path.starts_with("/token") {
+            match request.headers.get("authorization").map(String::as_str) {
+                Some("Basic YWRtaW46c2VjcmV0") => (200, vec![], br#"{"token":"t"}"#.to_vec()),
+                _ => (401, vec![], vec![]),
+            }
+        } else {
+            let host = request.headers["host"].clone();
+            let challenge = format!(r#"Bearer realm="http://{host}/token",service="fake""#);
+            (401, vec![("WWW-Authenticate".to_string(), challenge)], vec![])
+        }
+    }))
+    .await;
+
+    RegistryClient::new(&address, true)
+        .check_login(&auth("admin", "secret", &address))
+        .await
+        .unwrap();
+    let token = received.lock().unwrap().last().unwrap().path.clone();
+    assert_eq!(token, "/token?account=admin&service=fake");
+}
+
+#[tokio::test]
+async fn test_api_tag_and_push() {
+    let temp = tempfile::tempdir().unwrap();
+    let (socket, received) = fake_engine(
+        temp.path(),
+        Arc::new(|request: &Received| {
+            if request.path.contains("/push") {
+                (200, b"{\"status\":\"Pushed\",\"id\":\"abc\"}\n{\"status\":\"20: digest: sha256:1 size: 1\"}\n".to_vec())
+            } else {
+                (201, vec![])
+            }
+        }),
+    );
+    let docker = DockerApiEngine::new(ApiClient::new(ApiEndpoint::Unix(socket)));
+
+    docker.tag_image("node-builder", "20", "localhost:5000/team/node", "20").await.unwrap();
+    docker
+        .push_image(
+            "localhost:5000/team/node",
+            "20",
+            Some(&auth("admin", "secret", "localhost:5000")),
+        )
+        .await
+        .unwrap();
+    docker.push_image("localhost:5000/team/node", "20", None).await.unwrap();
+
+    let received = received.lock().unwrap();
+    assert_eq!(
+        received[0].path,
+        "/images/node-builder:20/tag?repo=localhost%3A5000%2Fteam%2Fnode&tag=20"
+    );
+    assert_eq!(received[1].path, "/images/localhost:5000/team/node/push?tag=20");
+
+    let header = URL_SAFE.decode(&received[1].headers["x-registry-auth"]).unwrap();
+    let header: Value = serde_json::from_slice(&header).unwrap();
+    assert_eq!(
+        header,
+        json!({ "username": "admin", "password": "secret", "serveraddress": "localhost:5000" })
+    );
+    assert_eq!(received[2].headers["x-registry-auth"], registry_auth_header(None).unwrap());
+    assert_eq!(URL_SAFE.decode(registry_auth_header(None).unwrap()).unwrap(), b"{}");
+}
+
+#[tokio::test]
+async fn test_cli_tag_and_push_fail_on_exit_status() {
+    let temp = tempfile::tempdir().unwrap();
+
+    // 仓库拒绝推送时 docker 以状态码 1 退出
+    let rejected = fake_cli(
+        temp.path(),
+        "docker-rejected",
+        "echo 'denied: requested access to the resource is denied' >&2\nexit 1",
+    );
+    let docker = CliEngine::docker().with_program(rejected.display().to_string());
+    let err = docker.push_image("localhost:5000/team/node", "20", None).await.unwrap_err();
+    assert_eq!(err.to_string(), "Docker push failed with exit code 1");
+    let err =
+        docker.tag_image("node-builder", "20", "localhost:5000/team/node", "20").await.unwrap_err();
+    assert_eq!(err.to_string(), "Docker tag failed with exit code 1");
+
+    let accepted = fake_cli(temp.path(), "docker-accepted", "echo \"$@\"");
+    let docker = CliEngine::docker().with_program(accepted.display().to_string());
+    docker.tag_image("node-builder", "20", "localhost:5000/team/node", "20").await.unwrap();
+    docker.push_image("localhost:5000/team/node", "20", None).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_cli_push_logs_in_to_temporary_auth_store() {
+    let temp = tempfile::tempdir().unwrap();
+    let log = temp.path().join("log");
+    fs::create_dir(&log).unwrap();
+
+    // 当前的 docker 配置: 凭据存储与 context 设置
+    let user_config = temp.path().join("docker-config");
+    fs::create_dir_all(user_config.join("contexts")).unwrap();
+    let original =
+        r#"{"auths":{"localhost:5000":{}},"credsStore":"desktop","currentContext":"colima"}"#;
+    fs::write(user_config.join("config.json"), original).unwrap();
+    // 单个测试中修改 DOCKER_CONFIG, 其他测试均显式传入配置路径
+    std::env::set_var("DOCKER_CONFIG", &user_config);
+
+    let log_dir = log.display();
+    let docker = fake_cli(
+        temp.path(),
+        "docker",
+        &format!(
+            "echo \"$1 $DOCKER_CONFIG\" >> {log_dir}/calls\n\
+             if [ \"$1\" = login ]; then\n\
+               cp \"$DOCKER_CONFIG/config.json\" {log_dir}/login-config.json\n\
+               [ -d \"$DOCKER_CONFIG/contexts\" ] && touch {log_dir}/contexts\n\
+               cat > {log_dir}/password\n\
+               echo logged-in > \"$DOCKER_CONFIG/config.json\"\n\
+             else\n\
+               cp \"$DOCKER_CONFIG/config.json\" {log_dir}/push-config.json\n\
+             fi"
+        ),
+    );
+    let auth = RegistryAuth {
+        username: "admin".to_string(),
+        password: "secret".to_string(),
+        server_address: "localhost:5000".to_string(),
+    };
+    let docker = CliEngine::docker().with_program(docker.display().to_string());
+    docker.push_image("localhost:5000/team/node", "20", Some(&auth)).await.unwrap();
+
+    let calls = fs::read_to_string(log.join("calls")).unwrap();
+    let (actions, dirs): (Vec<&str>, Vec<&str>) =
+        calls.lines().map(|line| line.split_once(' ').unwrap()).unzip();
+    assert_eq!(actions, ["login", "push"]);
+    // login 与 push 使用同一个临时配置目录, 推送后删除
+    assert_eq!(dirs[0], dirs[1]);
+    assert_ne!(std::path::Path::new(dirs[0]), user_config);
+    assert!(!std::path::Path::new(dirs[0]).exists());
+    assert_eq!(fs::read_to_string(log.join("password")).unwrap(), "secret");
+    assert_eq!(fs::read_to_string(log.join("push-config.json")).unwrap(), "logged-in\n");
+
+    // 临时配置保留 context 设置, 不含凭据存储
+    let login_config: Value =
+        serde_json::from_str(&fs::read_to_string(log.join("login-config.json")).unwrap()).unwrap();
+    assert_eq!(login_config, json!({ "currentContext": "colima" }));
+    assert!(log.join("contexts").exists());
+    assert_eq!(fs::read_to_string(user_config.join("config.json")).unwrap(), original);
+
+    // podman 通过 REGISTRY_AUTH_FILE 指向临时认证文件
+    let podman = fake_cli(
+        temp.path(),
+        "podman",
+        &format!(
+            "echo \"$1 $REGISTRY_AUTH_FILE\" >> {log_dir}/podman-calls\n\
+             [ \"$1\" = login ] && cat > /dev/null && echo '{{}}' > \"$REGISTRY_AUTH_FILE\"\n\
+             exit 0"
+        ),
+    );
+    let podman = CliEngine::podman().with_program(podman.display().to_string());
+    podman.push_image("localhost:5000/team/node", "20", Some(&auth)).await.unwrap();
+
+    let calls = fs::read_to_string(log.join("podman-calls")).unwrap();
+    let files: Vec<&str> = calls.lines().map(|line| line.split_once(' ').unwrap().1).collect();
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0], files[1]);
+    assert!(files[0].ends_with("/auth.json"));
+    assert!(!std::path::Path::new(files[0]).parent().unwrap().exists());
+}
@@ -0,0 +1,34 @@
+use confkit_engine::core::init::{InitOptions, ProjectScaffold};
+use confkit_engine::core::validator::ConfigValidator;
+use confkit_engine::shared::constants::CONFKIT_CONFIG_FILE;
+
+#[test]
+fn test_init_generates_valid_project() {
+    let temp = tempfile::tempdir().unwrap();
+    let root = temp.path();
+    let options = InitOptions::default();
+
+    let written = ProjectScaffold::generate(root, &options, false).unwrap();
+    assert_eq!(written.len(), 4);
+    for dir in ["workspace", "artifacts", "logs", "cache", "temp", "context"] {
+        assert!(root.join("volumes").join(dir).is_dir(), "missing volumes/{dir}");
+    }
+
+    // 校验器按相对路径解析, 需切换到生成目录
+    let previous = std::env::current_dir().unwrap();
+    std::env::set_current_dir(root).unwrap();
+    let report = ConfigValidator::validate_file(CONFKIT_CONFIG_FILE);
+    std::env::set_current_dir(previous).unwrap();
+
+    assert!(report.diagnostics.is_empty(), "unexpected diagnostics: {:?}", report.diagnostics);
+
+    let project =
+        std::fs::read_to_string(root.join(".confkit/spaces/hello/hello-app.yml")).unwrap();
+    assert!(project.contains("source:"));
+    assert!(project.contains("git_repo:"));
+
+    // 已存在的文件未指定 force 时拒绝覆盖
+    let err = ProjectScaffold::generate(root, &options, false).unwrap_err();
+    assert!(err.to_string().contains(".confkit.yml"));
+    assert!(ProjectScaffold::generate(root, &options, true).is_ok());
+}
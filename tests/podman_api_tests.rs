@@ -0,0 +1,201 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+
+mod common;
+
+use common::fake_engine::{fake_engine, Received};
+use confkit_engine::engine::api::client::{ApiClient, ApiEndpoint};
+use confkit_engine::engine::api::podman::{
+    container_spec, discover_socket, parse_port, parse_volume, pod_spec, PodmanApiEngine,
+    VolumeMount,
+};
+use confkit_engine::engine::traits::ContainerEngine;
+use confkit_engine::types::config::EngineServiceConfig;
+
+fn service(yaml: &str) -> EngineServiceConfig {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+#[test]
+fn test_discover_socket() {
+    assert_eq!(
+        discover_socket(None, Some("/run/user/1000"), 1000).unwrap(),
+        ApiEndpoint::Unix(PathBuf::from("/run/user/1000/podman/podman.sock"))
+    );
+    assert_eq!(
+        discover_socket(None, None, 1001).unwrap(),
+        ApiEndpoint::Unix(PathBuf::from("/run/user/1001/podman/podman.sock"))
+    );
+    assert_eq!(
+        discover_socket(None, Some("/run/user/0"), 0).unwrap(),
+        ApiEndpoint::Unix(PathBuf::from("/run/podman/podman.sock"))
+    );
+    assert_eq!(
+        discover_socket(Some("unix:///tmp/podman.sock"), Some("/run/user/1000"), 1000).unwrap(),
+        ApiEndpoint::Unix(PathBuf::from("/tmp/podman.sock"))
+    );
+    assert!(discover_socket(Some("ssh://core@localhost:22/run/podman.sock"), None, 0).is_err());
+}
+
+#[test]
+fn test_compose_service_to_pod_specs() {
+    let compose_dir = Path::new("/srv/project/.confkit");
+    assert_eq!(
+        parse_volume(&serde_yaml::Value::from("./volumes/workspace:/workspace"), compose_dir)
+            .unwrap(),
+        Some(VolumeMount::Bind {
+            source: PathBuf::from("/srv/project/.confkit/volumes/workspace"),
+            target: "/workspace".to_string(),
+            read_only: false,
+        })
+    );
+    assert_eq!(
+        parse_volume(&serde_yaml::Value::from("cache:/cache:ro"), compose_dir).unwrap(),
+        Some(VolumeMount::Named {
+            name: "cache".to_string(),
+            target: "/cache".to_string(),
+            read_only: true,
+        })
+    );
+    let long: serde_yaml::Value =
+        serde_yaml::from_str("{type: bind, source: /data, target: /data, read_only: true}")
+            .unwrap();
+    assert_eq!(
+        parse_volume(&long, compose_dir).unwrap(),
+        Some(VolumeMount::Bind {
+            source: PathBuf::from("/data"),
+            target: "/data".to_string(),
+            read_only: true,
+        })
+    );
+    assert_eq!(parse_volume(&serde_yaml::Value::from("/anonymous"), compose_dir).unwrap(), None);
+
+    assert_eq!(
+        parse_port("127.0.0.1:8080:80/udp"),
+        Some(
+            json!({ "host_ip": "127.0.0.1", "host_port": 8080, "container_port": 80, "protocol": "udp" })
+        )
+    );
+    assert_eq!(parse_port("3000"), Some(json!({ "container_port": 3000, "protocol": "tcp" })));
+    assert_eq!(parse_port("a:b"), None);
+    assert_eq!(
+        parse_port("8000-8010:9000-9010"),
+        Some(json!({ "host_port": 8000, "container_port": 9000, "protocol": "tcp", "range": 11 }))
+    );
+    assert_eq!(
+        parse_port("5000-5001"),
+        Some(json!({ "container_port": 5000, "protocol": "tcp", "range": 2 }))
+    );
+    // 范围长度不一致与 IPv6 地址暂不支持, 创建 pod 时给出警告
+    assert_eq!(parse_port("8000-8010:9000"), None);
+    assert_eq!(parse_port("[::1]:8080:80"), None);
+
+    let builder = service(
+        "service_name: node\ncontainer_name: node-builder\nimage: node:20\nworking_dir: /workspace\n\
+         ports: ['8080:80']\nenvironment: {TZ: UTC}\nvolumes: ['./volumes/workspace:/workspace', 'cache:/cache']\n\
+         command: tail -f /dev/null\ntty: true\n",
+    );
+    let spec = container_spec(&builder, "builders", compose_dir).unwrap();
+    assert_eq!(spec["name"], "node-builder");
+    assert_eq!(spec["image"], "node:20");
+    assert_eq!(spec["pod"], "builders");
+    assert_eq!(spec["work_dir"], "/workspace");
+    assert_eq!(spec["env"], json!({ "TZ": "UTC" }));
+    assert_eq!(spec["command"], json!(["tail", "-f", "/dev/null"]));
+    assert_eq!(spec["terminal"], json!(true));
+    assert_eq!(spec["mounts"][0]["source"], "/srv/project/.confkit/volumes/workspace");
+    assert_eq!(spec["mounts"][0]["options"], json!(["rbind"]));
+    assert_eq!(spec["volumes"][0], json!({ "Name": "cache", "Dest": "/cache", "Options": [] }));
+    // 端口声明在 pod 上
+    assert!(spec.get("portmappings").is_none());
+
+    let other = service(
+        "service_name: go\ncontainer_name: go\nimage: golang\nports: ['9090:90', '1-2:3']\n",
+    );
+    let pod = pod_spec("builders", &[builder, other]);
+    assert_eq!(pod["name"], "builders");
+    // 无法解析的端口被跳过
+    assert_eq!(pod["portmappings"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_podman_libpod_endpoints_and_pods() {
+    let temp = tempfile::tempdir().unwrap();
+    let pod_created = Arc::new(Mutex::new(false));
+    let state = pod_created.clone();
+    let (socket, received) = fake_engine(
+        temp.path(),
+        Arc::new(move |request: &Received| {
+            let mut pod_created = state.lock().unwrap();
+            match (request.method.as_str(), request.path.as_str()) {
+                ("GET", "/libpod/_ping") => (200, b"OK".to_vec()),
+                ("GET", "/libpod/images/app:1.0/exists") => (204, vec![]),
+                ("GET", "/libpod/pods/builders/exists") if *pod_created => (204, vec![]),
+                ("POST", "/libpod/pods/create") => {
+                    *pod_created = true;
+                    (201, br#"{"Id":"p1"}"#.to_vec())
+                }
+                ("POST", "/libpod/containers/create") => (201, br#"{"Id":"c1"}"#.to_vec()),
+                ("GET", "/libpod/pods/builders/json") => (
+                    200,
+                    json!({ "InfraContainerID": "infra", "Containers": [{ "Id": "infra" }] })
+                        .to_string()
+                        .into_bytes(),
+                ),
+                ("DELETE", "/libpod/pods/builders?force=true") => (200, b"{}".to_vec()),
+                _ => (404, br#"{"message":"no such object"}"#.to_vec()),
+            }
+        }),
+    );
+    let podman = PodmanApiEngine::new(ApiClient::new(ApiEndpoint::Unix(socket)));
+
+    podman.check_engine().await.unwrap();
+    podman.ensure_running().await.unwrap();
+    assert!(podman.check_image_exists("app", "1.0").await.unwrap());
+    assert!(!podman.check_image_exists("app", "2.0").await.unwrap());
+    assert!(!podman.check_container_exists("node-builder").await.unwrap());
+
+    let builder = service("service_name: node\ncontainer_name: node-builder\nimage: node:20\n");
+    let services = vec![builder.clone()];
+    podman.create_in_pod("builders", &builder, &services, temp.path()).await.unwrap();
+    // pod 已存在时不再创建
+    podman.create_in_pod("builders", &builder, &services, temp.path()).await.unwrap();
+    podman.remove_pod_if_empty("builders").await.unwrap();
+
+    let requests: Vec<(String, String)> = received
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|request| (request.method.clone(), request.path.clone()))
+        .filter(|(_, path)| path.contains("pods") || path.contains("containers/create"))
+        .collect();
+    let expected: Vec<(String, String)> = [
+        ("GET", "/libpod/pods/builders/exists"),
+        ("POST", "/libpod/pods/create"),
+        ("POST", "/libpod/containers/create"),
+        ("GET", "/libpod/pods/builders/exists"),
+        ("POST", "/libpod/containers/create"),
+        ("GET", "/libpod/pods/builders/json"),
+        ("DELETE", "/libpod/pods/builders?force=true"),
+    ]
+    .iter()
+    .map(|(method, path)| (method.to_string(), path.to_string()))
+    .collect();
+    assert_eq!(requests, expected);
+
+    let create = received
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|request| request.path == "/libpod/containers/create")
+        .map(|request| serde_json::from_slice::<Value>(&request.body).unwrap())
+        .unwrap();
+    assert_eq!(create["pod"], "builders");
+
+    let missing =
+        PodmanApiEngine::new(ApiClient::new(ApiEndpoint::Unix(temp.path().join("missing.sock"))));
+    let err = missing.check_engine().await.unwrap_err();
+    assert!(err.to_string().contains("podman.socket"), "{err}");
+}
@@ -0,0 +1,54 @@
+use std::time::{Duration, SystemTime};
+
+use confkit_engine::infra::project_index::ProjectIndex;
+
+fn project(name: &str, step: &str) -> String {
+    format!("name: {name}\ndescription: {name}\nsteps:\n  - name: {step}\n    commands: []\n")
+}
+
+/// 修改文件并推进 mtime, 避免同一时间戳内的修改被忽略
+fn touch(path: &std::path::Path, content: &str) {
+    std::fs::write(path, content).unwrap();
+    let file = std::fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(5)).unwrap();
+}
+
+#[test]
+fn test_project_index_lookup_and_reload() {
+    let dir = tempfile::tempdir().unwrap();
+    let space = dir.path().to_string_lossy().to_string();
+    std::fs::write(dir.path().join("b.yml"), project("api", "build")).unwrap();
+    std::fs::write(
+        dir.path().join("a.toml"),
+        "name = \"web\"\ndescription = \"web\"\nsteps = []\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("c.yml"), project("api", "duplicate")).unwrap();
+
+    let mut index = ProjectIndex::default();
+    assert!(!index.is_loaded("hello"));
+    assert!(index.projects("hello").is_none());
+
+    index.load_space("hello", &space).unwrap();
+    let names: Vec<&str> =
+        index.projects("hello").unwrap().iter().map(|p| p.config.name.as_str()).collect();
+    assert_eq!(names, vec!["web", "api"]);
+
+    // 重复项目名保留按文件名排序的第一个
+    let api = index.get("hello", "api").unwrap();
+    assert!(api.path.ends_with("b.yml"));
+    assert!(api.modified.is_some());
+    assert_eq!(api.config.steps[0].name, "build");
+    assert!(index.get("hello", "missing").is_none());
+    assert!(index.get("other", "api").is_none());
+
+    // 文件变更在 reload 之前不可见
+    touch(&dir.path().join("b.yml"), &project("api", "release"));
+    std::fs::remove_file(dir.path().join("a.toml")).unwrap();
+    assert_eq!(index.get("hello", "api").unwrap().config.steps[0].name, "build");
+
+    index.reload().unwrap();
+    assert_eq!(index.get("hello", "api").unwrap().config.steps[0].name, "release");
+    assert!(index.get("hello", "web").is_none());
+    assert_eq!(index.projects("hello").unwrap().len(), 1);
+}
@@ -0,0 +1,61 @@
+use confkit_engine::core::builder::image::ImageBuilder;
+use confkit_engine::infra::secret::{SecretResolver, SecretScope, SecretStore};
+use confkit_engine::types::config::ConfKitImageConfig;
+use std::collections::HashMap;
+
+// 加密存储使用相对路径 .confkit/secrets, 因此在单个测试中切换工作目录
+#[test]
+fn test_secret_store_scopes_references_and_rotation() {
+    let dir = tempfile::tempdir().unwrap();
+    std::env::set_current_dir(dir.path()).unwrap();
+
+    let space = SecretScope::space("hello");
+    let project = SecretScope::project("hello", "app");
+
+    SecretStore::set(&space, "API_TOKEN", "space-token").unwrap();
+    SecretStore::set(&space, "DB_PASS", "space-pass").unwrap();
+    SecretStore::set(&project, "DB_PASS", "project-pass").unwrap();
+
+    // 磁盘上不保存明文
+    let content = std::fs::read_to_string(project.file_path()).unwrap();
+    assert!(!content.contains("project-pass"));
+
+    assert_eq!(SecretStore::list(&space).unwrap(), vec!["API_TOKEN", "DB_PASS"]);
+    assert_eq!(SecretStore::lookup("hello", "app", "DB_PASS").unwrap().unwrap(), "project-pass");
+    assert_eq!(SecretStore::lookup("hello", "web", "DB_PASS").unwrap().unwrap(), "space-pass");
+
+    let mut environment = HashMap::new();
+    environment.insert("DB_URL".to_string(), "postgres://u:${secret:DB_PASS}@db".to_string());
+    environment.insert("APP_NAME".to_string(), "app".to_string());
+    let references = SecretResolver::resolve_references("hello", "app", &environment).unwrap();
+    assert_eq!(references.environment["DB_URL"], "postgres://u:project-pass@db");
+    assert_eq!(references.keys, vec!["DB_URL"]);
+    assert_eq!(references.values, vec!["project-pass"]);
+
+    environment.insert("MISSING".to_string(), "${secret:NOPE}".to_string());
+    assert!(SecretResolver::resolve_references("hello", "app", &environment).is_err());
+
+    assert_eq!(SecretStore::rotate_key().unwrap(), 2);
+    assert_eq!(SecretStore::get(&project, "DB_PASS").unwrap().unwrap(), "project-pass");
+    assert_ne!(std::fs::read_to_string(project.file_path()).unwrap(), content);
+
+    assert!(SecretStore::remove(&project, "DB_PASS").unwrap());
+    assert!(!SecretStore::remove(&project, "DB_PASS").unwrap());
+    assert!(!project.file_path().exists());
+
+    // 镜像推送凭据只从 push.space 的 space 级存储读取
+    SecretStore::set(&space, "REGISTRY_USER", "ci-bot").unwrap();
+    SecretStore::set(&project, "REGISTRY_TOKEN", "project-token").unwrap();
+    let config: ConfKitImageConfig = serde_yaml::from_str(
+        "name: app\nbase_image: node\ntag: '20'\ncontext: .\nengine_file: Dockerfile\npush:\n  registry: localhost:5000\n  space: hello\n  credentials:\n    - {name: username, from: encrypted, key: REGISTRY_USER}\n    - {name: password, from: encrypted, key: REGISTRY_TOKEN}\n",
+    )
+    .unwrap();
+    let push = config.push.as_ref().unwrap();
+    let docker_config = dir.path().join("config.json");
+    let err = ImageBuilder::registry_auth(push, &docker_config).unwrap_err();
+    assert!(err.to_string().contains("not found in encrypted secrets of space 'hello'"), "{err}");
+
+    SecretStore::set(&space, "REGISTRY_TOKEN", "space-token").unwrap();
+    let auth = ImageBuilder::registry_auth(push, &docker_config).unwrap().unwrap();
+    assert_eq!((auth.username.as_str(), auth.password.as_str()), ("ci-bot", "space-token"));
+}
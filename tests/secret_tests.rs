@@ -0,0 +1,71 @@
+use confkit_engine::infra::secret::cipher::{SecretCipher, SECRET_FILE_HEADER};
+use confkit_engine::infra::secret::SecretResolver;
+use confkit_engine::types::config::{ConfKitSecretConfig, ConfKitSecretSource};
+use confkit_engine::utils::mask::{redact, secret_fragments};
+
+fn secret(name: &str, from: ConfKitSecretSource) -> ConfKitSecretConfig {
+    ConfKitSecretConfig { name: name.to_string(), from, path: None, env: None, key: None }
+}
+
+#[test]
+fn test_redact_secret_values() {
+    let secrets = vec!["tok-123456".to_string(), "tok".to_string()];
+    let message = "curl -H 'Authorization: tok-123456' && echo tok";
+    assert_eq!(redact(message, &secrets), "curl -H 'Authorization: ******' && echo ******");
+    assert_eq!(redact("nothing here", &secrets), "nothing here");
+}
+
+#[test]
+fn test_secret_fragments_multiline() {
+    let key = "-----BEGIN KEY-----\nMIIEvQIBADANBgkq\nabc\n-----END KEY-----\n";
+    let fragments = secret_fragments(key);
+    // 整个值与足够长的行需要脱敏, 短行与 PEM 边界行不需要
+    assert_eq!(fragments, vec![key.to_string(), "MIIEvQIBADANBgkq".to_string()]);
+    assert!(secret_fragments("").is_empty());
+
+    // JSON 密钥的括号行不会把其他日志中的括号替换掉
+    let json =
+        "{\n  \"type\": \"service_account\",\n  \"private_key_id\": \"0123456789abcdef\"\n}\n";
+    let mut fragments = secret_fragments(json);
+    assert!(!fragments.iter().any(|fragment| fragment == "{" || fragment == "}"));
+    fragments.sort_by_key(|fragment| std::cmp::Reverse(fragment.len()));
+    assert_eq!(redact("{\"ok\": true}", &fragments), "{\"ok\": true}");
+    assert_eq!(redact("key: \"private_key_id\": \"0123456789abcdef\"", &fragments), "key: ******");
+}
+
+#[test]
+fn test_resolve_file_and_env_secrets() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("token");
+    std::fs::write(&path, "file-secret\n").unwrap();
+    std::env::set_var("CONFKIT_TEST_HOST_SECRET", "env-secret");
+
+    let mut from_file = secret("FILE_TOKEN", ConfKitSecretSource::File);
+    from_file.path = Some(path.to_string_lossy().to_string());
+    let mut from_env = secret("ENV_TOKEN", ConfKitSecretSource::Env);
+    from_env.env = Some("CONFKIT_TEST_HOST_SECRET".to_string());
+
+    let resolved = SecretResolver::resolve("hello", "app", &[from_file, from_env]).unwrap();
+    assert_eq!(resolved.get("FILE_TOKEN").unwrap(), "file-secret");
+    assert_eq!(resolved.get("ENV_TOKEN").unwrap(), "env-secret");
+}
+
+#[test]
+fn test_resolve_missing_secret_fails() {
+    let missing = secret("CONFKIT_TEST_MISSING_SECRET", ConfKitSecretSource::Env);
+    assert!(SecretResolver::resolve("hello", "app", &[missing]).is_err());
+}
+
+#[test]
+fn test_cipher_roundtrip_bound_to_scope() {
+    let cipher = SecretCipher::from_encoded_key(&SecretCipher::generate_key().unwrap()).unwrap();
+    let content = cipher.encrypt("API_TOKEN: tok\n", "confkit:hello").unwrap();
+
+    assert!(content.starts_with(SECRET_FILE_HEADER));
+    assert!(!content.contains("tok"));
+    assert_eq!(cipher.decrypt(&content, "confkit:hello").unwrap(), "API_TOKEN: tok\n");
+    assert!(cipher.decrypt(&content, "confkit:other").is_err());
+
+    let other = SecretCipher::from_encoded_key(&SecretCipher::generate_key().unwrap()).unwrap();
+    assert!(other.decrypt(&content, "confkit:hello").is_err());
+}
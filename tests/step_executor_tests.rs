@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use confkit_engine::core::executor::context::ExecutionContext;
+use confkit_engine::core::executor::step_executor::StepExecutor;
+use confkit_engine::core::executor::types::StepStatus;
+use confkit_engine::infra::logger::TaskLogger;
+use confkit_engine::types::config::{
+    ConditionErrorPolicy, ConfKitProjectConfig, ConfKitStepConfig,
+};
+
+/// engine: none 时的执行器, 宿主机工作空间为 host_workspace_dir
+fn host_only_executor(host_workspace_dir: &std::path::Path) -> StepExecutor {
+    let project_config: ConfKitProjectConfig =
+        serde_yaml::from_str("name: app\ndescription: app\nsteps: []\n").unwrap();
+    let context = ExecutionContext {
+        task_id: "task".to_string(),
+        space_name: "space".to_string(),
+        project_name: "app".to_string(),
+        project_config,
+        environment: HashMap::new(),
+        git_info: None,
+        secret_values: vec![],
+        clean_workspace: false,
+        host_workspace_dir: host_workspace_dir.to_string_lossy().to_string(),
+        container_workspace_dir: "/workspace/space/app/task".to_string(),
+        host_only: true,
+    };
+    let log_path = host_workspace_dir.join("task.log").to_string_lossy().to_string();
+    StepExecutor::new(context, TaskLogger::new(log_path), ConditionErrorPolicy::Run)
+}
+
+fn step(yaml: &str) -> ConfKitStepConfig {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+#[tokio::test]
+async fn test_container_step_fails_without_engine() {
+    let temp = tempfile::tempdir().unwrap();
+    let executor = host_only_executor(temp.path());
+
+    let step = step("name: build\ncontainer: node-builder\ncommands: ['touch built']\n");
+    let result = executor.execute_step(&step, 1, 1, &[]).await.unwrap();
+
+    assert_eq!(result.status, StepStatus::Failed);
+    assert_eq!(result.exit_code, Some(1));
+    assert!(result.error.unwrap().contains("no container engine is configured"));
+    // 命令没有执行
+    assert!(!temp.path().join("built").exists());
+}
+
+#[tokio::test]
+async fn test_container_step_falls_back_to_host() {
+    let temp = tempfile::tempdir().unwrap();
+    std::fs::create_dir(temp.path().join("app")).unwrap();
+    let executor = host_only_executor(temp.path());
+
+    // 容器内的工作目录映射为宿主机工作空间下的同名目录
+    let step = step(
+        "name: build\ncontainer: node-builder\nfallback: host\n\
+         working_dir: /workspace/space/app/task/app\ncommands: ['pwd > cwd']\n",
+    );
+    let result = executor.execute_step(&step, 1, 1, &[]).await.unwrap();
+
+    assert_eq!(result.status, StepStatus::Success);
+    let cwd = std::fs::read_to_string(temp.path().join("app/cwd")).unwrap();
+    assert_eq!(
+        std::fs::canonicalize(cwd.trim()).unwrap(),
+        std::fs::canonicalize(temp.path().join("app")).unwrap()
+    );
+}